@@ -1,11 +1,8 @@
 use crate::app::App;
 use crate::app_state::AppState;
-use crate::server::fileserv::file_and_error_handler;
 use anyhow::anyhow;
 use async_trait::async_trait;
-use axum::Router;
-use leptos::{get_configuration, provide_context};
-use leptos_axum::{generate_route_list, LeptosRoutes};
+use leptos::get_configuration;
 use migration::Migrator;
 use roadster::app::context::AppContext;
 use roadster::app::metadata::AppMetadata;
@@ -15,9 +12,6 @@ use roadster::error::RoadsterResult;
 use roadster::service::http::service::HttpService;
 use roadster::service::registry::ServiceRegistry;
 
-#[cfg(feature = "ssr")]
-pub mod fileserv;
-
 const BASE: &str = "/api";
 
 #[derive(Default)]
@@ -49,7 +43,6 @@ impl RoadsterApp<AppState> for Server {
         registry: &mut ServiceRegistry<Self, AppState>,
         state: &AppState,
     ) -> RoadsterResult<()> {
-        let state = state.clone();
         assert_eq!(
             state.leptos_options.site_addr,
             state
@@ -62,20 +55,8 @@ impl RoadsterApp<AppState> for Server {
                 .socket_addr()?,
             "Leptos address does not match the Roadster http address."
         );
-        let routes = generate_route_list(App);
         registry
-            .register_builder(
-                HttpService::builder(Some(BASE), &state.clone()).router(
-                    Router::<AppState>::new()
-                        .leptos_routes_with_context(
-                            &state.clone(),
-                            routes,
-                            move || provide_context(state.clone()),
-                            App,
-                        )
-                        .fallback(file_and_error_handler),
-                ),
-            )
+            .register_builder(HttpService::builder_leptos(Some(BASE), state, App))
             .await?;
 
         Ok(())
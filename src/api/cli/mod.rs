@@ -177,6 +177,10 @@ mod tests {
     #[cfg_attr(feature = "open-api", case::list_routes(Some("r list-routes"), None))]
     #[cfg_attr(feature = "open-api", case::open_api(Some("r open-api"), None))]
     #[cfg_attr(feature = "db-sql", case::migrate(Some("r migrate up"), None))]
+    #[cfg_attr(
+        feature = "db-sql",
+        case::migrate_dry_run(Some("r migrate up --dry-run"), None)
+    )]
     #[cfg_attr(coverage_nightly, coverage(off))]
     fn parse_cli(_case: TestCase, #[case] args: Option<&str>, #[case] arg_list: Option<Vec<&str>>) {
         // Arrange
@@ -3,9 +3,12 @@ use async_trait::async_trait;
 
 use axum::extract::FromRef;
 use clap::{Parser, Subcommand};
+use sea_orm::DatabaseConnection;
 use sea_orm_migration::MigratorTrait;
 use serde_derive::Serialize;
-use tracing::warn;
+use std::collections::HashSet;
+use strum_macros::Display;
+use tracing::{info, warn};
 
 use crate::api::cli::roadster::{RoadsterCli, RunRoadsterCommand};
 use crate::app::context::AppContext;
@@ -68,12 +71,19 @@ where
             );
         }
         match self {
+            MigrateCommand::Up(UpArgs { dry_run: true, .. })
+            | MigrateCommand::Down(DownArgs { dry_run: true, .. }) => {
+                dry_run::<A::M>(context.db(), self).await?
+            }
             MigrateCommand::Up(args) => A::M::up(context.db(), args.steps).await?,
             MigrateCommand::Down(args) => A::M::down(context.db(), args.steps).await?,
             MigrateCommand::Refresh => A::M::refresh(context.db()).await?,
             MigrateCommand::Reset => A::M::reset(context.db()).await?,
             MigrateCommand::Fresh => A::M::fresh(context.db()).await?,
-            MigrateCommand::Status => A::M::status(context.db()).await?,
+            MigrateCommand::Status => {
+                let migrations = migration_status::<A::M>(&context).await?;
+                print_status(migrations);
+            }
         };
         Ok(true)
     }
@@ -85,6 +95,9 @@ pub struct UpArgs {
     /// The number of pending migration steps to apply.
     #[clap(short = 'n', long)]
     pub steps: Option<u32>,
+    /// Print the migrations that would be applied without actually applying them.
+    #[clap(long, default_value_t = false)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Parser, Serialize)]
@@ -93,8 +106,299 @@ pub struct DownArgs {
     /// The number of applied migration steps to rollback.
     #[clap(short = 'n', long)]
     pub steps: Option<u32>,
+    /// Print the migrations that would be rolled back without actually rolling them back.
+    #[clap(long, default_value_t = false)]
+    pub dry_run: bool,
+}
+
+/// Print the migrations that would be applied (for `up`) or rolled back (for `down`) without
+/// actually applying or rolling them back.
+async fn dry_run<M>(db: &DatabaseConnection, command: &MigrateCommand) -> RoadsterResult<()>
+where
+    M: MigratorTrait,
+{
+    match command {
+        MigrateCommand::Up(args) => {
+            print_plan("applied", pending_migrations::<M>(db, args.steps).await?)
+        }
+        MigrateCommand::Down(args) => print_plan(
+            "rolled back",
+            applied_migrations::<M>(db, args.steps).await?,
+        ),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Get the names of the pending migrations that would be applied by `up`, in the order they
+/// would be applied.
+async fn pending_migrations<M>(
+    db: &DatabaseConnection,
+    steps: Option<u32>,
+) -> RoadsterResult<Vec<String>>
+where
+    M: MigratorTrait,
+{
+    let migrations = M::get_pending_migrations(db)
+        .await?
+        .iter()
+        .map(|migration| migration.name().to_string())
+        .collect();
+    Ok(take_steps(migrations, steps))
+}
+
+/// Get the names of the applied migrations that would be rolled back by `down`, in the order
+/// they would be rolled back.
+async fn applied_migrations<M>(
+    db: &DatabaseConnection,
+    steps: Option<u32>,
+) -> RoadsterResult<Vec<String>>
+where
+    M: MigratorTrait,
+{
+    let migrations = M::get_applied_migrations(db)
+        .await?
+        .iter()
+        .rev()
+        .map(|migration| migration.name().to_string())
+        .collect();
+    Ok(take_steps(migrations, steps))
+}
+
+fn take_steps(migrations: Vec<String>, steps: Option<u32>) -> Vec<String> {
+    if let Some(steps) = steps {
+        migrations.into_iter().take(steps as usize).collect()
+    } else {
+        migrations
+    }
+}
+
+fn print_plan(action: &str, migrations: Vec<String>) {
+    if migrations.is_empty() {
+        info!("Dry run: no migrations would be {action}");
+    }
+    for migration in migrations {
+        info!("Dry run: migration `{migration}` would be {action}");
+    }
+}
+
+/// The status of a migration, as reported by the `migrate status` command.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Display)]
+enum MigrationStatus {
+    /// The migration has been applied.
+    Applied,
+    /// The migration has not been applied yet.
+    Pending,
+    /// The migration is recorded as applied in the DB, but is not known to the migrator. This
+    /// can happen if a migration was removed or renamed after it was applied, or if a
+    /// different version of the app applied it, and indicates that the DB schema may not be
+    /// consistent with the app's migrations.
+    Dirty,
+}
+
+/// Get the status of all migrations. Migrations known to the migrator are returned first, in
+/// the order they would be applied, followed by any migrations that are recorded as applied in
+/// the DB but are not known to the migrator.
+async fn migration_status<M>(context: &AppContext) -> RoadsterResult<Vec<(String, MigrationStatus)>>
+where
+    M: MigratorTrait,
+{
+    let known = M::migrations()
+        .iter()
+        .map(|migration| migration.name().to_string())
+        .collect();
+    let applied = M::get_migration_models(context.db())
+        .await?
+        .into_iter()
+        .map(|model| model.version)
+        .collect();
+    Ok(compute_status(known, applied))
+}
+
+fn compute_status(known: Vec<String>, applied: Vec<String>) -> Vec<(String, MigrationStatus)> {
+    let known_set: HashSet<&String> = known.iter().collect();
+    let applied_set: HashSet<&String> = applied.iter().collect();
+
+    let dirty = applied
+        .iter()
+        .filter(|version| !known_set.contains(version))
+        .map(|version| (version.clone(), MigrationStatus::Dirty));
+
+    known
+        .iter()
+        .map(|name| {
+            let status = if applied_set.contains(name) {
+                MigrationStatus::Applied
+            } else {
+                MigrationStatus::Pending
+            };
+            (name.clone(), status)
+        })
+        .chain(dirty)
+        .collect()
+}
+
+fn print_status(migrations: Vec<(String, MigrationStatus)>) {
+    info!("Checking migration status");
+    for (name, status) in migrations.iter() {
+        match status {
+            MigrationStatus::Dirty => warn!(
+                "Migration `{name}`... {status} (applied in the DB, but not known to the migrator)"
+            ),
+            _ => info!("Migration `{name}`... {status}"),
+        }
+    }
 }
 
 fn is_destructive(command: &MigrateCommand) -> bool {
-    !matches!(command, MigrateCommand::Status)
+    match command {
+        MigrateCommand::Status => false,
+        MigrateCommand::Up(args) => !args.dry_run,
+        MigrateCommand::Down(args) => !args.dry_run,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::tracing::capture_events;
+    use rstest::rstest;
+    use sea_orm::DbErr;
+    use sea_orm_migration::{MigrationName, MigrationTrait, SchemaManager};
+
+    struct TestMigration(&'static str);
+
+    impl MigrationName for TestMigration {
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[async_trait]
+    impl MigrationTrait for TestMigration {
+        async fn up(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+            Ok(())
+        }
+
+        async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+            Ok(())
+        }
+    }
+
+    struct TestMigrator;
+
+    impl MigratorTrait for TestMigrator {
+        fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+            vec![
+                Box::new(TestMigration("m1")),
+                Box::new(TestMigration("m2")),
+                Box::new(TestMigration("m3")),
+            ]
+        }
+    }
+
+    async fn applied_names(db: &DatabaseConnection) -> Vec<String> {
+        TestMigrator::get_applied_migrations(db)
+            .await
+            .unwrap()
+            .iter()
+            .map(|migration| migration.name().to_string())
+            .collect()
+    }
+
+    #[rstest]
+    #[case::up(
+        MigrateCommand::Up(UpArgs { steps: None, dry_run: true }),
+        vec!["Dry run: migration `m2` would be applied", "Dry run: migration `m3` would be applied"]
+    )]
+    #[case::up_steps(
+        MigrateCommand::Up(UpArgs { steps: Some(1), dry_run: true }),
+        vec!["Dry run: migration `m2` would be applied"]
+    )]
+    #[case::down(
+        MigrateCommand::Down(DownArgs { steps: None, dry_run: true }),
+        vec!["Dry run: migration `m1` would be rolled back"]
+    )]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn dry_run(#[case] command: MigrateCommand, #[case] expected_plan: Vec<&str>) {
+        // Arrange
+        // Each connection to an in-memory SQLite DB gets its own DB, so only use one connection.
+        let mut options = sea_orm::ConnectOptions::new("sqlite::memory:");
+        options.max_connections(1);
+        let db = sea_orm::Database::connect(options).await.unwrap();
+        TestMigrator::up(&db, Some(1)).await.unwrap();
+        let (events, _guard) = capture_events();
+
+        // Act
+        super::dry_run::<TestMigrator>(&db, &command).await.unwrap();
+
+        // Assert
+        assert_eq!(applied_names(&db).await, vec!["m1"]);
+        let plan: Vec<String> = events
+            .events()
+            .into_iter()
+            .filter_map(|event| event.message)
+            .filter(|message| message.starts_with("Dry run"))
+            .collect();
+        assert_eq!(plan, expected_plan);
+    }
+
+    #[rstest]
+    #[case(vec!["a", "b", "c"], None, vec!["a", "b", "c"])]
+    #[case(vec!["a", "b", "c"], Some(2), vec!["a", "b"])]
+    #[case(vec!["a", "b", "c"], Some(5), vec!["a", "b", "c"])]
+    #[case(vec![], Some(1), vec![])]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn take_steps(
+        #[case] migrations: Vec<&str>,
+        #[case] steps: Option<u32>,
+        #[case] expected: Vec<&str>,
+    ) {
+        let migrations = migrations.into_iter().map(|m| m.to_string()).collect();
+
+        let migrations = super::take_steps(migrations, steps);
+
+        assert_eq!(migrations, expected);
+    }
+
+    #[rstest]
+    #[case(vec!["a", "b"], vec![], vec![("a", MigrationStatus::Pending), ("b", MigrationStatus::Pending)])]
+    #[case(vec!["a", "b"], vec!["a"], vec![("a", MigrationStatus::Applied), ("b", MigrationStatus::Pending)])]
+    #[case(vec!["a", "b"], vec!["a", "b"], vec![("a", MigrationStatus::Applied), ("b", MigrationStatus::Applied)])]
+    #[case(vec!["a", "c"], vec!["a", "b"], vec![("a", MigrationStatus::Applied), ("c", MigrationStatus::Pending), ("b", MigrationStatus::Dirty)])]
+    #[case(vec![], vec!["a"], vec![("a", MigrationStatus::Dirty)])]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn compute_status(
+        #[case] known: Vec<&str>,
+        #[case] applied: Vec<&str>,
+        #[case] expected: Vec<(&str, MigrationStatus)>,
+    ) {
+        // Arrange
+        let known = known.into_iter().map(|m| m.to_string()).collect();
+        let applied = applied.into_iter().map(|m| m.to_string()).collect();
+
+        // Act
+        let status = super::compute_status(known, applied);
+
+        // Assert
+        let expected: Vec<(String, MigrationStatus)> = expected
+            .into_iter()
+            .map(|(name, status)| (name.to_string(), status))
+            .collect();
+        assert_eq!(status, expected);
+    }
+
+    #[rstest]
+    #[case(MigrateCommand::Up(UpArgs { steps: None, dry_run: false }), true)]
+    #[case(MigrateCommand::Up(UpArgs { steps: None, dry_run: true }), false)]
+    #[case(MigrateCommand::Down(DownArgs { steps: None, dry_run: false }), true)]
+    #[case(MigrateCommand::Down(DownArgs { steps: None, dry_run: true }), false)]
+    #[case(MigrateCommand::Refresh, true)]
+    #[case(MigrateCommand::Status, false)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn is_destructive(#[case] command: MigrateCommand, #[case] expected: bool) {
+        assert_eq!(super::is_destructive(&command), expected);
+    }
 }
@@ -6,6 +6,7 @@ use crate::api::cli::roadster::migrate::MigrateArgs;
 #[cfg(feature = "open-api")]
 use crate::api::cli::roadster::open_api_schema::OpenApiArgs;
 use crate::api::cli::roadster::print_config::PrintConfigArgs;
+use crate::api::cli::roadster::validate_config::ValidateConfigArgs;
 use crate::app::context::AppContext;
 use crate::app::App;
 use crate::config::environment::Environment;
@@ -24,6 +25,7 @@ pub mod migrate;
 #[cfg(feature = "open-api")]
 pub mod open_api_schema;
 pub mod print_config;
+pub mod validate_config;
 
 /// Internal version of [RunCommand][crate::cli::RunCommand] that uses the [RoadsterCli] and
 /// [AppContext] instead of the consuming app's versions of these objects. This (slightly) reduces
@@ -68,6 +70,18 @@ impl RoadsterCli {
     pub fn allow_dangerous(&self, context: &AppContext) -> bool {
         context.config().environment != Environment::Production || self.allow_dangerous
     }
+
+    /// Get the [ValidateConfigArgs] if the `validate-config` command was provided. This command
+    /// is handled separately from the other commands because it needs to run before the app
+    /// config is validated.
+    pub(crate) fn validate_config_args(&self) -> Option<&ValidateConfigArgs> {
+        match self.command.as_ref() {
+            Some(RoadsterCommand::Roadster(RoadsterArgs {
+                command: RoadsterSubCommand::ValidateConfig(args),
+            })) => Some(args),
+            _ => None,
+        }
+    }
 }
 
 #[async_trait]
@@ -153,6 +167,11 @@ where
             #[cfg(feature = "db-sql")]
             RoadsterSubCommand::Migrate(args) => args.run(app, cli, state).await,
             RoadsterSubCommand::PrintConfig(args) => args.run(app, cli, state).await,
+            RoadsterSubCommand::ValidateConfig(_) => {
+                #[allow(unused_doc_comments)]
+                /// Implemented by [crate::app::run]
+                Ok(false)
+            }
             RoadsterSubCommand::Health(args) => args.run(app, cli, state).await,
         }
     }
@@ -180,6 +199,10 @@ pub enum RoadsterSubCommand {
     /// Print the AppConfig
     PrintConfig(PrintConfigArgs),
 
+    /// Validate the AppConfig and print any validation errors as JSON. Exits with a non-zero
+    /// exit code if the config is invalid.
+    ValidateConfig(ValidateConfigArgs),
+
     /// Check the health of the app's resources. Note: This runs without starting the app's service(s)
     /// and only requires creating the [AppContext] that would normally be used by the app.
     Health(HealthArgs),
@@ -0,0 +1,97 @@
+use anyhow::anyhow;
+use clap::Parser;
+use serde_derive::Serialize;
+
+use crate::config::app_config::{AppConfig, ConfigValidationError};
+use crate::error::RoadsterResult;
+
+#[derive(Debug, Parser, Serialize)]
+#[non_exhaustive]
+pub struct ValidateConfigArgs {
+    /// Pretty-print the JSON validation errors.
+    #[clap(long, default_value_t = false)]
+    pub pretty: bool,
+}
+
+impl ValidateConfigArgs {
+    /// Validate the app config and print any validation errors as JSON. Returns an error if the
+    /// config is invalid so the process exits with a non-zero exit code.
+    ///
+    /// Unlike most commands, this runs before tracing is initialized, before the app config is
+    /// validated, and before the [AppContext][crate::app::context::AppContext] is created, so
+    /// the command can report errors in the config instead of failing at startup. The report is
+    /// printed directly to stdout (instead of being logged) so it can be consumed by tooling.
+    pub(crate) fn validate(&self, config: &AppConfig) -> RoadsterResult<()> {
+        let errors = config.validation_errors();
+        println!("{}", format_errors(&errors, self.pretty)?);
+        if errors.is_empty() {
+            return Ok(());
+        }
+
+        Err(anyhow!("The app config is invalid: {} error(s) found", errors.len()).into())
+    }
+}
+
+fn format_errors(errors: &[ConfigValidationError], pretty: bool) -> RoadsterResult<String> {
+    let errors = if pretty {
+        serde_json::to_string_pretty(errors)?
+    } else {
+        serde_json::to_string(errors)?
+    };
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn format_errors_invalid_config(#[case] pretty: bool) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.runtime.worker_threads = Some(0);
+        let errors = config.validation_errors();
+
+        // Act
+        let errors = format_errors(&errors, pretty).unwrap();
+
+        // Assert
+        let errors: Vec<ConfigValidationError> = serde_json::from_str(&errors).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "runtime.worker-threads");
+        assert_eq!(errors[0].code, "range");
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn validate_invalid_config() {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.runtime.worker_threads = Some(0);
+        let args = ValidateConfigArgs { pretty: false };
+
+        // Act
+        let result = args.validate(&config);
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn validate_valid_config() {
+        // Arrange
+        let config = AppConfig::test(None).unwrap();
+        let args = ValidateConfigArgs { pretty: false };
+
+        // Act
+        let result = args.validate(&config);
+
+        // Assert
+        assert!(result.is_ok());
+    }
+}
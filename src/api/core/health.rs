@@ -22,6 +22,10 @@ use std::time::Instant;
 use tokio::time::timeout;
 use tracing::{info, instrument};
 
+/// The name of the resource that's reported as unhealthy while the app is not
+/// [ready][AppContext::ready], e.g. during the app's pre-shutdown delay.
+pub const SHUTDOWN_RESOURCE: &str = "shutdown";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "open-api", derive(JsonSchema, OperationIo))]
 #[serde(rename_all = "camelCase")]
@@ -75,7 +79,24 @@ where
         })
     });
 
-    let resources = join_all(check_futures).await.into_iter().collect();
+    let mut resources: BTreeMap<String, CheckResponse> =
+        join_all(check_futures).await.into_iter().collect();
+
+    // Report the app as unhealthy while it's preparing to shut down, so load balancers stop
+    // routing requests to it. See `AppContext::ready`.
+    if !context.ready() {
+        resources.insert(
+            SHUTDOWN_RESOURCE.to_string(),
+            CheckResponse::builder()
+                .status(Status::Err(
+                    ErrorData::builder()
+                        .msg("The app is shutting down".to_string())
+                        .build(),
+                ))
+                .latency(Duration::ZERO)
+                .build(),
+        );
+    }
 
     Ok(HeathCheckResponse {
         latency: timer.elapsed().as_millis(),
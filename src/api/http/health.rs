@@ -1,22 +1,33 @@
 use crate::api::core::health::{health_check, HeathCheckResponse};
 use crate::api::http::build_path;
 use crate::app::context::AppContext;
+use crate::config::service::http::default_routes::HealthAccess;
+use crate::error::api::http::HttpError;
 use crate::error::RoadsterResult;
+use crate::health_check::Status;
 #[cfg(feature = "open-api")]
-use crate::health_check::{CheckResponse, ErrorData, Status};
+use crate::health_check::{CheckResponse, ErrorData};
+#[cfg(feature = "jwt")]
+use crate::middleware::http::auth::jwt::Jwt;
 #[cfg(feature = "open-api")]
 use aide::axum::routing::get_with;
 #[cfg(feature = "open-api")]
 use aide::axum::ApiRouter;
 #[cfg(feature = "open-api")]
 use aide::transform::TransformOperation;
+#[cfg(feature = "jwt")]
+use axum::extract::FromRequestParts;
 use axum::extract::State;
-use axum::extract::{FromRef, Query};
+use axum::extract::{ConnectInfo, FromRef, Query, Request};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
 use axum::routing::get;
 use axum::{Json, Router};
 #[cfg(feature = "open-api")]
 use schemars::JsonSchema;
 use serde_derive::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
 use tracing::instrument;
 
@@ -34,7 +45,14 @@ where
         return router;
     }
     let root = build_path(parent, route(&context));
-    router.route(&root, get(health_get::<S>))
+    let router = router.route(&root, get(health_get::<S>));
+    if matches!(access(&context), HealthAccess::Public) {
+        return router;
+    }
+    router.route_layer(axum::middleware::from_fn_with_state(
+        state.clone(),
+        health_access::<S>,
+    ))
 }
 
 #[cfg(feature = "open-api")]
@@ -49,7 +67,14 @@ where
         return router;
     }
     let root = build_path(parent, route(&context));
-    router.api_route(&root, get_with(health_get::<S>, health_get_docs))
+    let router = router.api_route(&root, get_with(health_get::<S>, health_get_docs));
+    if matches!(access(&context), HealthAccess::Public) {
+        return router;
+    }
+    router.route_layer(axum::middleware::from_fn_with_state(
+        state.clone(),
+        health_access::<S>,
+    ))
 }
 
 fn enabled(context: &AppContext) -> bool {
@@ -60,6 +85,7 @@ fn enabled(context: &AppContext) -> bool {
         .custom
         .default_routes
         .health
+        .common
         .enabled(context)
 }
 
@@ -71,9 +97,73 @@ fn route(context: &AppContext) -> &str {
         .custom
         .default_routes
         .health
+        .common
         .route
 }
 
+fn access(context: &AppContext) -> &HealthAccess {
+    &context
+        .config()
+        .service
+        .http
+        .custom
+        .default_routes
+        .health
+        .access
+}
+
+/// Rejects requests that are not allowed to access the health route according to the
+/// configured [HealthAccess].
+async fn health_access<S>(
+    State(state): State<S>,
+    request: Request,
+    next: Next,
+) -> RoadsterResult<Response>
+where
+    S: Clone + Send + Sync + 'static,
+    AppContext: FromRef<S>,
+{
+    let context = AppContext::from_ref(&state);
+    let request = match access(&context) {
+        HealthAccess::Public => request,
+        #[cfg(feature = "jwt")]
+        HealthAccess::RequireJwt => {
+            let (mut parts, body) = request.into_parts();
+            Jwt::<serde_json::Value>::from_request_parts(&mut parts, &state).await?;
+            Request::from_parts(parts, body)
+        }
+        HealthAccess::IpAllowlist { allow } => {
+            // The client's address is only available if the request was received by the
+            // `HttpService`'s server; deny access if it's missing.
+            let allowed = request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| {
+                    let ip = canonical_ip(addr.ip());
+                    allow.iter().any(|allowed| allowed.contains(&ip))
+                })
+                .unwrap_or_default();
+            if !allowed {
+                return Err(HttpError::forbidden()
+                    .error("Client is not allowed to access the health route")
+                    .into());
+            }
+            request
+        }
+    };
+    Ok(next.run(request).await)
+}
+
+/// Convert an IPv4-mapped IPv6 address (e.g., `::ffff:127.0.0.1`) to its IPv4 equivalent so it
+/// can be matched against IPv4 ranges in the allowlist.
+// Todo: Replace with `IpAddr::to_canonical` once the MSRV is at least 1.75
+fn canonical_ip(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        IpAddr::V4(_) => ip,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "open-api", derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
@@ -87,47 +177,74 @@ pub struct HeathCheckRequest {
     pub max_duration: Option<u64>,
 }
 
+/// Responds with `503 Service Unavailable` if any of the health checks failed, so load balancers
+/// and orchestrators can detect an unhealthy instance from the status code alone.
 #[instrument(skip_all)]
 async fn health_get<S>(
     State(state): State<S>,
     Query(query): Query<HeathCheckRequest>,
-) -> RoadsterResult<Json<HeathCheckResponse>>
+) -> RoadsterResult<Response>
 where
     S: Clone + Send + Sync + 'static,
     AppContext: FromRef<S>,
 {
     let duration = Duration::from_millis(query.max_duration.unwrap_or(1000));
-    Ok(Json(health_check(&state, Some(duration)).await?))
+    let response = health_check(&state, Some(duration)).await?;
+    let status = if response
+        .resources
+        .values()
+        .all(|resource| matches!(resource.status, Status::Ok))
+    {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    Ok((status, Json(response)).into_response())
 }
 
 #[cfg(feature = "open-api")]
 fn health_get_docs(op: TransformOperation) -> TransformOperation {
-    op.description("Check the health of the server and its resources.")
+    op.summary("Health check")
+        .description("Check the health of the server and its resources.")
         .tag(TAG)
         .response_with::<200, Json<HeathCheckResponse>, _>(|res| {
-            res.example(HeathCheckResponse {
-                latency: 20,
-                resources: std::collections::BTreeMap::from([
-                    (
+            res.description("All of the server's resources are healthy.")
+                .example(HeathCheckResponse {
+                    latency: 20,
+                    resources: std::collections::BTreeMap::from([(
                         "db".to_string(),
                         CheckResponse::builder()
                             .status(Status::Ok)
                             .latency(Duration::from_secs(1))
                             .build(),
-                    ),
-                    (
-                        "redis".to_string(),
-                        CheckResponse::builder()
-                            .status(Status::Err(
-                                ErrorData::builder()
-                                    .msg("An error occurred".to_string())
-                                    .build(),
-                            ))
-                            .latency(Duration::from_secs(2))
-                            .build(),
-                    ),
-                ]),
-            })
+                    )]),
+                })
+        })
+        .response_with::<503, Json<HeathCheckResponse>, _>(|res| {
+            res.description("One or more of the server's resources is unhealthy.")
+                .example(HeathCheckResponse {
+                    latency: 20,
+                    resources: std::collections::BTreeMap::from([
+                        (
+                            "db".to_string(),
+                            CheckResponse::builder()
+                                .status(Status::Ok)
+                                .latency(Duration::from_secs(1))
+                                .build(),
+                        ),
+                        (
+                            "redis".to_string(),
+                            CheckResponse::builder()
+                                .status(Status::Err(
+                                    ErrorData::builder()
+                                        .msg("An error occurred".to_string())
+                                        .build(),
+                                ))
+                                .latency(Duration::from_secs(2))
+                                .build(),
+                        ),
+                    ]),
+                })
         })
 }
 
@@ -135,7 +252,20 @@ fn health_get_docs(op: TransformOperation) -> TransformOperation {
 mod tests {
     use crate::app::context::AppContext;
     use crate::config::app_config::AppConfig;
+    use crate::config::service::http::default_routes::HealthAccess;
+    use crate::health_check::registry::HealthCheckRegistry;
+    use crate::health_check::{CheckResponse, ErrorData, MockHealthCheck, Status};
+    use axum::body::{to_bytes, Body};
+    use axum::extract::ConnectInfo;
+    use axum::http::{Request, StatusCode};
+    use axum::Router;
     use rstest::rstest;
+    use serde_json::Value;
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tower::ServiceExt;
 
     // Todo: Is there a better way to structure this test (and the ones in `docs` and `ping`)
     //  to reduce duplication?
@@ -153,7 +283,14 @@ mod tests {
     ) {
         let mut config = AppConfig::test(None).unwrap();
         config.service.http.custom.default_routes.default_enable = default_enable;
-        config.service.http.custom.default_routes.health.enable = enable;
+        config
+            .service
+            .http
+            .custom
+            .default_routes
+            .health
+            .common
+            .enable = enable;
         if let Some(route) = route.as_ref() {
             config
                 .service
@@ -161,6 +298,7 @@ mod tests {
                 .custom
                 .default_routes
                 .health
+                .common
                 .route
                 .clone_from(route);
         }
@@ -172,4 +310,221 @@ mod tests {
             route.unwrap_or_else(|| "_health".to_string())
         );
     }
+
+    #[rstest]
+    #[case::public(HealthAccess::Public, "10.0.0.1", StatusCode::OK)]
+    #[case::allowed(
+        HealthAccess::IpAllowlist { allow: vec!["127.0.0.1/32".parse().unwrap()] },
+        "127.0.0.1",
+        StatusCode::OK
+    )]
+    #[case::not_allowed(
+        HealthAccess::IpAllowlist { allow: vec!["127.0.0.1/32".parse().unwrap()] },
+        "10.0.0.1",
+        StatusCode::FORBIDDEN
+    )]
+    #[case::allowed_cidr(
+        HealthAccess::IpAllowlist { allow: vec!["10.0.0.0/8".parse().unwrap()] },
+        "10.1.2.3",
+        StatusCode::OK
+    )]
+    #[case::not_allowed_cidr(
+        HealthAccess::IpAllowlist { allow: vec!["10.0.0.0/8".parse().unwrap()] },
+        "192.168.0.1",
+        StatusCode::FORBIDDEN
+    )]
+    #[case::allowed_ipv4_mapped(
+        HealthAccess::IpAllowlist { allow: vec!["127.0.0.1/32".parse().unwrap()] },
+        "::ffff:127.0.0.1",
+        StatusCode::OK
+    )]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn health_access(
+        #[case] access: HealthAccess,
+        #[case] remote_ip: &str,
+        #[case] expected_status: StatusCode,
+    ) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.service.http.custom.default_routes.default_enable = true;
+        config
+            .service
+            .http
+            .custom
+            .default_routes
+            .health
+            .common
+            .route = "status".to_string();
+        config.service.http.custom.default_routes.health.access = access;
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let router = super::routes("/api", &context).with_state(context);
+        let remote_addr = SocketAddr::new(remote_ip.parse().unwrap(), 1234);
+
+        // Act
+        let response = router
+            .oneshot(
+                Request::get("/api/status")
+                    .extension(ConnectInfo(remote_addr))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), expected_status);
+    }
+
+    #[rstest]
+    #[case::healthy(true, true, StatusCode::OK)]
+    #[case::check_failed(true, false, StatusCode::SERVICE_UNAVAILABLE)]
+    #[case::not_ready(false, true, StatusCode::SERVICE_UNAVAILABLE)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn health_get_status(
+        #[case] ready: bool,
+        #[case] check_ok: bool,
+        #[case] expected_status: StatusCode,
+    ) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.health_check.default_enable = false;
+        config.service.http.custom.default_routes.default_enable = true;
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        let mut check: MockHealthCheck = MockHealthCheck::default();
+        check.expect_enabled().return_const(true);
+        check.expect_name().return_const("test".to_string());
+        check.expect_cache_interval().return_const(None);
+        check.expect_circuit_breaker().return_const(None);
+        check.expect_check().returning(move || {
+            let status = if check_ok {
+                Status::Ok
+            } else {
+                Status::Err(ErrorData::builder().msg("error".to_string()).build())
+            };
+            Ok(CheckResponse::builder()
+                .status(status)
+                .latency(Duration::ZERO)
+                .build())
+        });
+        let mut registry = HealthCheckRegistry::new(&context);
+        registry.register(check).unwrap();
+        context.set_health_checks(registry).unwrap();
+        context.set_ready(ready);
+
+        let router = super::routes("/api", &context).with_state(context);
+
+        // Act
+        let response = router
+            .oneshot(Request::get("/api/_health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), expected_status);
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn health_get_cached() {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.health_check.default_enable = false;
+        config.service.http.custom.default_routes.default_enable = true;
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        let count = Arc::new(AtomicU64::new(0));
+        let mut check: MockHealthCheck = MockHealthCheck::default();
+        check.expect_enabled().return_const(true);
+        check.expect_name().return_const("test".to_string());
+        check
+            .expect_cache_interval()
+            .return_const(Some(Duration::from_millis(200)));
+        check.expect_cache_max_age().return_const(None);
+        check.expect_circuit_breaker().return_const(None);
+        {
+            let count = count.clone();
+            check.expect_check().returning(move || {
+                let count = count.fetch_add(1, Ordering::SeqCst) + 1;
+                Ok(CheckResponse::builder()
+                    .status(Status::Ok)
+                    .latency(Duration::from_millis(count))
+                    .build())
+            });
+        }
+        let mut registry = HealthCheckRegistry::new(&context);
+        registry.register(check).unwrap();
+        let cached_checks = registry.cached_checks();
+        context.set_health_checks(registry).unwrap();
+
+        let router = super::routes("/api", &context).with_state(context.clone());
+
+        async fn check_latency(router: &Router) -> u64 {
+            let response = router
+                .clone()
+                .oneshot(Request::get("/api/_health").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&body).unwrap();
+            body["test"]["latency"].as_u64().unwrap()
+        }
+
+        // Act
+        cached_checks
+            .iter()
+            .for_each(|check| check.run_in_background(&context));
+        // Let the background task run the check for the first time
+        tokio::task::yield_now().await;
+        tokio::time::advance(Duration::from_millis(50)).await;
+        let first = check_latency(&router).await;
+        let second = check_latency(&router).await;
+        // Advance past the next interval and let the background task refresh the cached result
+        tokio::time::advance(Duration::from_millis(200)).await;
+        tokio::task::yield_now().await;
+        let third = check_latency(&router).await;
+
+        // Assert
+        // The endpoint served the cached result, and the check only ran on its interval
+        assert_eq!(first, 1);
+        assert_eq!(second, 1);
+        assert_eq!(third, 2);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        context.cancellation_token().cancel();
+    }
+
+    #[test]
+    #[cfg(feature = "open-api")]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn health_open_api() {
+        use aide::openapi::{OpenApi, StatusCode};
+
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.service.http.custom.default_routes.default_enable = true;
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let mut open_api = OpenApi::default();
+
+        // Act
+        let _router = super::api_routes("/api", &context).finish_api(&mut open_api);
+
+        // Assert
+        let paths = open_api.paths.unwrap();
+        let operation = paths
+            .paths
+            .get("/api/_health")
+            .unwrap()
+            .as_item()
+            .unwrap()
+            .get
+            .as_ref()
+            .unwrap();
+        assert_eq!(operation.summary.as_deref(), Some("Health check"));
+        let responses = &operation.responses.as_ref().unwrap().responses;
+        assert!(responses.contains_key(&StatusCode::Code(200)));
+        assert!(responses.contains_key(&StatusCode::Code(503)));
+    }
 }
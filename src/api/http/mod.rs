@@ -8,7 +8,10 @@ use itertools::Itertools;
 #[cfg(feature = "open-api")]
 pub mod docs;
 pub mod health;
+#[cfg(feature = "http-content-negotiation")]
+pub mod negotiate;
 pub mod ping;
+pub mod stream;
 
 pub fn build_path(parent: &str, child: &str) -> String {
     // Clean the path to make sure it is valid:
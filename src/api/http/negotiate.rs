@@ -0,0 +1,307 @@
+//! Helpers to respond with the format (JSON, YAML, or TOML) requested by the client via the
+//! request's `Accept` header.
+
+use crate::error::api::http::HttpError;
+#[cfg(feature = "open-api")]
+use aide::gen::GenContext;
+#[cfg(feature = "open-api")]
+use aide::openapi::Operation;
+#[cfg(feature = "open-api")]
+use aide::{OperationInput, OperationOutput};
+use async_trait::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::header::{ACCEPT, CONTENT_TYPE, VARY};
+use axum::http::request::Parts;
+use axum::http::HeaderMap;
+use axum::response::{IntoResponse, Response};
+#[cfg(feature = "open-api")]
+use axum::Json;
+use serde::Serialize;
+use std::convert::Infallible;
+
+/// The format to use to serialize a response body. Implements [FromRequestParts] to allow
+/// extracting the format from the request's `Accept` header.
+///
+/// The supported format with the highest quality value (`q` parameter, which defaults to `1`) in
+/// the `Accept` header is used; if multiple supported formats have the same quality value, the
+/// one that appears first is used. Media types with a quality value of `0` are not acceptable to
+/// the client and are ignored. If none of the media types match a supported format, or if the
+/// `Accept` header is missing, [ResponseFormat::Json] is used.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ResponseFormat {
+    /// `application/json`
+    #[default]
+    Json,
+    /// `application/yaml`; `application/x-yaml` and `text/yaml` are also accepted.
+    Yaml,
+    /// `application/toml`
+    Toml,
+}
+
+impl ResponseFormat {
+    /// Get the [ResponseFormat] to use based on the provided request headers.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        headers
+            .get_all(ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|media_range| {
+                let mut parts = media_range.split(';');
+                let format = Self::from_media_type(parts.next()?.trim())?;
+                Some((format, quality(parts)))
+            })
+            .filter(|(_, quality)| *quality > 0.0)
+            // Only replace the current best format if the quality is strictly higher so the
+            // earliest format wins if there's a tie.
+            .fold(
+                None,
+                |best: Option<(Self, f32)>, (format, quality)| match best {
+                    Some((_, best_quality)) if best_quality >= quality => best,
+                    _ => Some((format, quality)),
+                },
+            )
+            .map(|(format, _)| format)
+            .unwrap_or_default()
+    }
+
+    fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type.to_ascii_lowercase().as_str() {
+            "application/json" | "application/*" | "*/*" => Some(ResponseFormat::Json),
+            "application/yaml" | "application/x-yaml" | "text/yaml" => Some(ResponseFormat::Yaml),
+            "application/toml" => Some(ResponseFormat::Toml),
+            _ => None,
+        }
+    }
+
+    /// The `Content-Type` header value to use for the format.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ResponseFormat::Json => "application/json",
+            ResponseFormat::Yaml => "application/yaml",
+            ResponseFormat::Toml => "application/toml",
+        }
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<String, HttpError> {
+        match self {
+            ResponseFormat::Json => serde_json::to_string(value).map_err(|err| {
+                HttpError::internal_server_error()
+                    .error("Unable to serialize response as JSON")
+                    .source(err)
+            }),
+            ResponseFormat::Yaml => serde_norway::to_string(value).map_err(|err| {
+                HttpError::internal_server_error()
+                    .error("Unable to serialize response as YAML")
+                    .source(err)
+            }),
+            ResponseFormat::Toml => toml::to_string(value).map_err(|err| {
+                HttpError::internal_server_error()
+                    .error("Unable to serialize response as TOML")
+                    .source(err)
+            }),
+        }
+    }
+}
+
+/// Get the quality value from the parameters of a media range in an `Accept` header. Defaults to
+/// `1` if the `q` parameter is missing or invalid.
+fn quality<'a>(params: impl Iterator<Item = &'a str>) -> f32 {
+    params
+        .filter_map(|param| param.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+        .and_then(|(_, value)| value.trim().parse::<f32>().ok())
+        .unwrap_or(1.0)
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ResponseFormat
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(ResponseFormat::from_headers(&parts.headers))
+    }
+}
+
+// Required in order to use `ResponseFormat` in an Aide route.
+#[cfg(feature = "open-api")]
+impl OperationInput for ResponseFormat {}
+
+/// Response that serializes `T` using the provided [ResponseFormat] and sets the corresponding
+/// `Content-Type` header. The `Vary: Accept` header is also set so caches don't serve the
+/// response to clients that requested a different format.
+///
+/// # Examples
+///
+/// ```rust
+/// use roadster::api::http::negotiate::{Negotiated, ResponseFormat};
+/// use serde_derive::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Example {
+///     foo: String,
+/// }
+///
+/// async fn example(format: ResponseFormat) -> Negotiated<Example> {
+///     Negotiated(
+///         format,
+///         Example {
+///             foo: "bar".to_string(),
+///         },
+///     )
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Negotiated<T>(pub ResponseFormat, pub T);
+
+impl<T> IntoResponse for Negotiated<T>
+where
+    T: Serialize,
+{
+    fn into_response(self) -> Response {
+        let Negotiated(format, value) = self;
+        match format.serialize(&value) {
+            Ok(body) => (
+                [
+                    (CONTENT_TYPE, format.content_type()),
+                    (VARY, ACCEPT.as_str()),
+                ],
+                body,
+            )
+                .into_response(),
+            Err(err) => err.into_response(),
+        }
+    }
+}
+
+// Required in order to use `Negotiated` in an Aide route. The schema is documented as JSON.
+#[cfg(feature = "open-api")]
+impl<T> OperationOutput for Negotiated<T>
+where
+    T: schemars::JsonSchema,
+{
+    type Inner = T;
+
+    fn operation_response(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Option<aide::openapi::Response> {
+        Json::<T>::operation_response(ctx, operation)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, aide::openapi::Response)> {
+        Json::<T>::inferred_responses(ctx, operation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use rstest::rstest;
+    use serde_derive::{Deserialize, Serialize};
+    use tower::ServiceExt;
+
+    #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+    struct Example {
+        foo: String,
+    }
+
+    async fn handler(format: ResponseFormat) -> Negotiated<Example> {
+        Negotiated(
+            format,
+            Example {
+                foo: "bar".to_string(),
+            },
+        )
+    }
+
+    #[rstest]
+    #[case(None, ResponseFormat::Json)]
+    #[case(Some("application/json"), ResponseFormat::Json)]
+    #[case(Some("*/*"), ResponseFormat::Json)]
+    #[case(Some("text/html"), ResponseFormat::Json)]
+    #[case(Some("application/yaml"), ResponseFormat::Yaml)]
+    #[case(Some("text/html, application/x-yaml;q=0.9"), ResponseFormat::Yaml)]
+    #[case(Some("text/yaml"), ResponseFormat::Yaml)]
+    #[case(Some("application/toml"), ResponseFormat::Toml)]
+    #[case(Some("application/json, application/toml"), ResponseFormat::Json)]
+    #[case::quality(Some("application/json;q=0.5, application/toml"), ResponseFormat::Toml)]
+    #[case::quality_tie(
+        Some("application/toml;q=0.8, application/yaml;q=0.8"),
+        ResponseFormat::Toml
+    )]
+    #[case::quality_zero(Some("application/yaml;q=0, */*;q=0.1"), ResponseFormat::Json)]
+    #[case::quality_with_params(
+        Some("application/json;charset=utf-8;q=0.1, text/yaml; Q=0.2"),
+        ResponseFormat::Yaml
+    )]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn response_format_from_headers(
+        #[case] accept: Option<&str>,
+        #[case] expected: ResponseFormat,
+    ) {
+        // Arrange
+        let mut headers = HeaderMap::new();
+        if let Some(accept) = accept {
+            headers.insert(ACCEPT, accept.parse().unwrap());
+        }
+
+        // Act
+        let format = ResponseFormat::from_headers(&headers);
+
+        // Assert
+        assert_eq!(format, expected);
+    }
+
+    #[rstest]
+    #[case(None, "application/json")]
+    #[case(Some("application/json"), "application/json")]
+    #[case(Some("text/html"), "application/json")]
+    #[case(Some("application/yaml"), "application/yaml")]
+    #[case(Some("application/toml"), "application/toml")]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn negotiated_response(#[case] accept: Option<&str>, #[case] content_type: &str) {
+        // Arrange
+        let router = Router::new().route("/", get(handler));
+        let mut request = Request::builder().uri("/");
+        if let Some(accept) = accept {
+            request = request.header(ACCEPT, accept);
+        }
+
+        // Act
+        let response = router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), content_type);
+        assert_eq!(response.headers().get(VARY).unwrap(), "accept");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        let body: Example = match content_type {
+            "application/yaml" => serde_norway::from_str(&body).unwrap(),
+            "application/toml" => toml::from_str(&body).unwrap(),
+            _ => serde_json::from_str(&body).unwrap(),
+        };
+        assert_eq!(
+            body,
+            Example {
+                foo: "bar".to_string()
+            }
+        );
+    }
+}
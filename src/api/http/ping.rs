@@ -83,7 +83,8 @@ async fn ping_get() -> RoadsterResult<Json<PingResponse>> {
 
 #[cfg(feature = "open-api")]
 fn ping_get_docs(op: TransformOperation) -> TransformOperation {
-    op.description("Ping the server to confirm that it is running.")
+    op.summary("Ping")
+        .description("Ping the server to confirm that it is running.")
         .tag(TAG)
         .response_with::<200, Json<PingResponse>, _>(|res| res.example(PingResponse::default()))
 }
@@ -0,0 +1,180 @@
+//! Helpers to send streaming (chunked) responses, e.g. to export a large dataset without
+//! buffering it all in memory.
+//!
+//! The response body is polled lazily by the server, so the stream will only be polled for the
+//! next chunk once the previous chunk has been sent to the client (backpressure). When the
+//! [tracing middleware][crate::service::http::middleware::tracing::TracingMiddleware] is enabled,
+//! the time it took to send the full response body is recorded once the stream completes (see
+//! [CustomOnEos][crate::service::http::middleware::tracing::CustomOnEos]), in addition to the
+//! latency until the response headers were sent.
+
+use axum::body::{Body, Bytes};
+use axum::http::header::CONTENT_TYPE;
+use axum::http::HeaderValue;
+use axum::response::{IntoResponse, Response};
+use axum::BoxError;
+use futures::{Stream, StreamExt, TryStream};
+use serde::Serialize;
+
+/// The `Content-Type` of newline-delimited JSON responses.
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Response that streams the chunks of the provided [TryStream] to the client with the provided
+/// `Content-Type`. If the stream returns an error, the response is aborted.
+///
+/// # Examples
+///
+/// ```rust
+/// use axum::http::HeaderValue;
+/// use axum::response::IntoResponse;
+/// use futures::{stream, StreamExt};
+/// use roadster::api::http::stream::Streaming;
+///
+/// async fn export() -> impl IntoResponse {
+///     let rows = stream::iter(0..1000).map(|id| Ok::<_, std::io::Error>(format!("{id}\n")));
+///     Streaming::new(HeaderValue::from_static("text/csv"), rows)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Streaming<S> {
+    content_type: HeaderValue,
+    stream: S,
+}
+
+impl<S> Streaming<S> {
+    pub fn new(content_type: HeaderValue, stream: S) -> Self {
+        Self {
+            content_type,
+            stream,
+        }
+    }
+}
+
+impl<S> IntoResponse for Streaming<S>
+where
+    S: TryStream + Send + 'static,
+    S::Ok: Into<Bytes>,
+    S::Error: Into<BoxError>,
+{
+    fn into_response(self) -> Response {
+        (
+            [(CONTENT_TYPE, self.content_type)],
+            Body::from_stream(self.stream),
+        )
+            .into_response()
+    }
+}
+
+/// Response that streams each item of the provided [Stream] to the client as a line of
+/// newline-delimited JSON (NDJSON). If an item can't be serialized, the response is aborted.
+///
+/// # Examples
+///
+/// ```rust
+/// use axum::response::IntoResponse;
+/// use futures::{stream, StreamExt};
+/// use roadster::api::http::stream::NdJson;
+/// use serde_derive::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Row {
+///     id: u64,
+/// }
+///
+/// async fn export() -> impl IntoResponse {
+///     NdJson(stream::iter(0..1000).map(|id| Row { id }))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct NdJson<S>(pub S);
+
+impl<S, T> IntoResponse for NdJson<S>
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: Serialize,
+{
+    fn into_response(self) -> Response {
+        let lines = self.0.map(|item| {
+            let mut line = serde_json::to_vec(&item)?;
+            line.push(b'\n');
+            Ok::<_, serde_json::Error>(line)
+        });
+        Streaming::new(HeaderValue::from_static(NDJSON_CONTENT_TYPE), lines).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use axum::http::{Request, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use futures::stream;
+    use serde_derive::Deserialize;
+    use tower::ServiceExt;
+
+    const ROWS: u64 = 1000;
+
+    #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+    struct Row {
+        id: u64,
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn ndjson() {
+        // Arrange
+        let router = Router::new().route(
+            "/",
+            get(|| async { NdJson(stream::iter(0..ROWS).map(|id| Row { id })) }),
+        );
+
+        // Act
+        let response = router
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            NDJSON_CONTENT_TYPE
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        let rows = body
+            .lines()
+            .map(|line| serde_json::from_str::<Row>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), ROWS as usize);
+        assert!(rows.iter().zip(0..ROWS).all(|(row, id)| row.id == id));
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn streaming() {
+        // Arrange
+        let router = Router::new().route(
+            "/",
+            get(|| async {
+                let rows = stream::iter(0..ROWS).map(|id| Ok::<_, BoxError>(format!("{id}\n")));
+                Streaming::new(HeaderValue::from_static("text/csv"), rows)
+            }),
+        );
+
+        // Act
+        let response = router
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), "text/csv");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert_eq!(body.lines().count(), ROWS as usize);
+    }
+}
@@ -4,11 +4,26 @@ use crate::config::app_config::AppConfig;
 use crate::error::RoadsterResult;
 use crate::health_check::registry::HealthCheckRegistry;
 use crate::health_check::HealthCheck;
+#[cfg(all(feature = "http", feature = "jwt"))]
+use crate::middleware::http::auth::jwt::claims_validator::ClaimsValidator;
+#[cfg(all(feature = "http", feature = "jwt"))]
+use crate::middleware::http::auth::jwt::key_provider::JwtKeyProvider;
+#[cfg(feature = "sidekiq")]
+use crate::service::worker::sidekiq::dyn_enqueuer::DynEnqueuer;
 use anyhow::anyhow;
 use axum::extract::FromRef;
 #[cfg(feature = "db-sql")]
 use sea_orm::DatabaseConnection;
+use std::future::Future;
+#[cfg(feature = "http")]
+use std::net::SocketAddr;
+#[cfg(feature = "sidekiq")]
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, OnceLock};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
 
 #[cfg(not(test))]
 type Inner = AppContextInner;
@@ -39,7 +54,23 @@ impl AppContext {
         #[cfg(not(test))]
         let context = {
             #[cfg(feature = "db-sql")]
-            let db = sea_orm::Database::connect(A::db_connection_options(&config)?).await?;
+            let db = crate::db::connect(
+                A::db_connection_options(&config)?,
+                &config.database.on_connect_sql,
+            )
+            .await?;
+            #[cfg(feature = "db-sql")]
+            let db_read_replica = crate::db::connect_read_replica(
+                A::db_read_replica_connection_options(&config)?,
+                &config.database.on_connect_sql,
+            )
+            .await?;
+            #[cfg(feature = "db-sql")]
+            let (db, db_read_replica) = {
+                let db = Arc::new(db);
+                let db_read_replica = db_read_replica.map(Arc::new).unwrap_or_else(|| db.clone());
+                (db, db_read_replica)
+            };
 
             #[cfg(feature = "sidekiq")]
             let (redis_enqueue, redis_fetch) = {
@@ -78,12 +109,91 @@ impl AppContext {
                 config,
                 metadata,
                 health_checks: OnceLock::new(),
+                #[cfg(feature = "http")]
+                http_bound_addr: OnceLock::new(),
+                #[cfg(all(feature = "http", feature = "jwt"))]
+                jwt_key_provider: OnceLock::new(),
+                #[cfg(all(feature = "http", feature = "jwt"))]
+                jwt_claims_validator: OnceLock::new(),
                 #[cfg(feature = "db-sql")]
                 db,
+                #[cfg(feature = "db-sql")]
+                db_read_replica,
                 #[cfg(feature = "sidekiq")]
                 redis_enqueue,
                 #[cfg(feature = "sidekiq")]
                 redis_fetch,
+                #[cfg(feature = "sidekiq")]
+                sidekiq_fetch_paused: AtomicBool::new(false),
+                #[cfg(feature = "sidekiq")]
+                sidekiq_dyn_enqueuer: Default::default(),
+                ready: watch::channel(true).0,
+                cancellation_token: CancellationToken::new(),
+                task_tracker: TaskTracker::new(),
+            };
+            AppContext {
+                inner: Arc::new(inner),
+            }
+        };
+
+        Ok(context)
+    }
+
+    /// Create an [AppContext] that uses the provided [DatabaseConnection], e.g. a connection
+    /// created from a sea-orm [MockDatabase][sea_orm::MockDatabase]. Intended to be used in tests;
+    /// see [crate::testing::context].
+    #[cfg(feature = "testing-mocks")]
+    pub(crate) fn with_db(
+        config: AppConfig,
+        metadata: AppMetadata,
+        db: DatabaseConnection,
+    ) -> RoadsterResult<Self> {
+        #[cfg(test)]
+        let context = {
+            let db = Arc::new(db);
+            let mut inner = Self::test_inner(Some(config), Some(metadata), None)?;
+            inner.expect_db().return_const(db.clone());
+            inner.expect_db_read_replica().return_const(db);
+            AppContext {
+                inner: Arc::new(inner),
+            }
+        };
+
+        #[cfg(not(test))]
+        let context = {
+            #[cfg(feature = "sidekiq")]
+            let redis_enqueue = {
+                let redis = sidekiq::RedisConnectionManager::new(
+                    config.service.sidekiq.custom.redis.uri.to_string(),
+                )?;
+                bb8::Pool::builder().build_unchecked(redis)
+            };
+            // `DatabaseConnection` doesn't implement `Clone` when sea-orm's `mock` feature is
+            // enabled, so the same connection is shared via an `Arc` instead.
+            let db = Arc::new(db);
+            let inner = AppContextInner {
+                config,
+                metadata,
+                health_checks: OnceLock::new(),
+                #[cfg(feature = "http")]
+                http_bound_addr: OnceLock::new(),
+                #[cfg(all(feature = "http", feature = "jwt"))]
+                jwt_key_provider: OnceLock::new(),
+                #[cfg(all(feature = "http", feature = "jwt"))]
+                jwt_claims_validator: OnceLock::new(),
+                db_read_replica: db.clone(),
+                db,
+                #[cfg(feature = "sidekiq")]
+                redis_enqueue,
+                #[cfg(feature = "sidekiq")]
+                redis_fetch: None,
+                #[cfg(feature = "sidekiq")]
+                sidekiq_fetch_paused: AtomicBool::new(false),
+                #[cfg(feature = "sidekiq")]
+                sidekiq_dyn_enqueuer: Default::default(),
+                ready: watch::channel(true).0,
+                cancellation_token: CancellationToken::new(),
+                task_tracker: TaskTracker::new(),
             };
             AppContext {
                 inner: Arc::new(inner),
@@ -97,9 +207,22 @@ impl AppContext {
     pub(crate) fn test(
         config: Option<AppConfig>,
         metadata: Option<AppMetadata>,
-        #[cfg(not(feature = "sidekiq"))] _redis: Option<()>,
+        #[cfg(not(feature = "sidekiq"))] redis: Option<()>,
         #[cfg(feature = "sidekiq")] redis: Option<sidekiq::RedisPool>,
     ) -> RoadsterResult<Self> {
+        let inner = Self::test_inner(config, metadata, redis)?;
+        Ok(AppContext {
+            inner: Arc::new(inner),
+        })
+    }
+
+    #[cfg(test)]
+    fn test_inner(
+        config: Option<AppConfig>,
+        metadata: Option<AppMetadata>,
+        #[cfg(not(feature = "sidekiq"))] _redis: Option<()>,
+        #[cfg(feature = "sidekiq")] redis: Option<sidekiq::RedisPool>,
+    ) -> RoadsterResult<MockAppContextInner> {
         let mut inner = MockAppContextInner::default();
         inner
             .expect_config()
@@ -116,9 +239,102 @@ impl AppContext {
         } else {
             inner.expect_redis_fetch().return_const(None);
         }
-        Ok(AppContext {
-            inner: Arc::new(inner),
-        })
+
+        #[cfg(feature = "http")]
+        {
+            let http_bound_addr = Arc::new(OnceLock::new());
+            let addr = http_bound_addr.clone();
+            inner
+                .expect_http_bound_addr()
+                .returning(move || addr.get().copied());
+            inner.expect_set_http_bound_addr().returning(move |addr| {
+                http_bound_addr
+                    .set(addr)
+                    .map_err(|_| anyhow!("Unable to set http bound address"))?;
+                Ok(())
+            });
+        }
+
+        #[cfg(all(feature = "http", feature = "jwt"))]
+        {
+            let jwt_key_provider: Arc<OnceLock<Arc<dyn JwtKeyProvider>>> =
+                Arc::new(OnceLock::new());
+            let key_provider = jwt_key_provider.clone();
+            inner
+                .expect_jwt_key_provider()
+                .returning(move || key_provider.get().cloned());
+            inner
+                .expect_set_jwt_key_provider()
+                .returning(move |key_provider| {
+                    jwt_key_provider
+                        .set(key_provider)
+                        .map_err(|_| anyhow!("Unable to set JWT key provider"))?;
+                    Ok(())
+                });
+
+            let jwt_claims_validator: Arc<OnceLock<Arc<dyn ClaimsValidator>>> =
+                Arc::new(OnceLock::new());
+            let claims_validator = jwt_claims_validator.clone();
+            inner
+                .expect_jwt_claims_validator()
+                .returning(move || claims_validator.get().cloned());
+            inner
+                .expect_set_jwt_claims_validator()
+                .returning(move |claims_validator| {
+                    jwt_claims_validator
+                        .set(claims_validator)
+                        .map_err(|_| anyhow!("Unable to set JWT claims validator"))?;
+                    Ok(())
+                });
+        }
+
+        #[cfg(feature = "sidekiq")]
+        {
+            let sidekiq_fetch_paused = Arc::new(AtomicBool::new(false));
+            let paused = sidekiq_fetch_paused.clone();
+            inner
+                .expect_sidekiq_fetch_paused()
+                .returning(move || paused.load(Ordering::SeqCst));
+            inner
+                .expect_set_sidekiq_fetch_paused()
+                .returning(move |paused| sidekiq_fetch_paused.store(paused, Ordering::SeqCst));
+            inner
+                .expect_sidekiq_dyn_enqueuer()
+                .return_const(Arc::new(DynEnqueuer::default()));
+        }
+
+        let health_checks: Arc<OnceLock<HealthCheckRegistry>> = Arc::new(OnceLock::new());
+        let checks = health_checks.clone();
+        inner.expect_health_checks().returning(move || {
+            checks
+                .get()
+                .map(|health_checks| health_checks.checks())
+                .unwrap_or_default()
+        });
+        inner.expect_set_health_checks().returning(move |registry| {
+            health_checks
+                .set(registry)
+                .map_err(|_| anyhow!("Unable to set health check registry"))?;
+            Ok(())
+        });
+
+        let ready = Arc::new(watch::channel(true).0);
+        let is_ready = ready.clone();
+        inner.expect_ready().returning(move || *is_ready.borrow());
+        let subscribe_ready = ready.clone();
+        inner
+            .expect_subscribe_ready()
+            .returning(move || subscribe_ready.subscribe());
+        inner
+            .expect_set_ready()
+            .returning(move |value| set_ready(&ready, value));
+
+        inner
+            .expect_cancellation_token()
+            .return_const(CancellationToken::new());
+        inner.expect_task_tracker().return_const(TaskTracker::new());
+
+        Ok(inner)
     }
 
     pub fn config(&self) -> &AppConfig {
@@ -140,11 +356,61 @@ impl AppContext {
         self.inner.set_health_checks(health_checks)
     }
 
+    /// The address the HTTP service is bound to. This is only available once the HTTP service
+    /// has started. This is useful to discover the port that was assigned if the service was
+    /// configured with port `0`.
+    #[cfg(feature = "http")]
+    pub fn http_bound_addr(&self) -> Option<SocketAddr> {
+        self.inner.http_bound_addr()
+    }
+
+    #[cfg(feature = "http")]
+    pub(crate) fn set_http_bound_addr(&self, addr: SocketAddr) -> RoadsterResult<()> {
+        self.inner.set_http_bound_addr(addr)
+    }
+
+    /// The [JwtKeyProvider] used to get the keys to decode JWTs, if one was provided via
+    /// [App::jwt_key_provider].
+    #[cfg(all(feature = "http", feature = "jwt"))]
+    pub fn jwt_key_provider(&self) -> Option<Arc<dyn JwtKeyProvider>> {
+        self.inner.jwt_key_provider()
+    }
+
+    #[cfg(all(feature = "http", feature = "jwt"))]
+    pub(crate) fn set_jwt_key_provider(
+        &self,
+        key_provider: Arc<dyn JwtKeyProvider>,
+    ) -> RoadsterResult<()> {
+        self.inner.set_jwt_key_provider(key_provider)
+    }
+
+    /// The [ClaimsValidator] used to validate the claims of decoded JWTs, if one was provided
+    /// via [App::jwt_claims_validator].
+    #[cfg(all(feature = "http", feature = "jwt"))]
+    pub fn jwt_claims_validator(&self) -> Option<Arc<dyn ClaimsValidator>> {
+        self.inner.jwt_claims_validator()
+    }
+
+    #[cfg(all(feature = "http", feature = "jwt"))]
+    pub(crate) fn set_jwt_claims_validator(
+        &self,
+        claims_validator: Arc<dyn ClaimsValidator>,
+    ) -> RoadsterResult<()> {
+        self.inner.set_jwt_claims_validator(claims_validator)
+    }
+
     #[cfg(feature = "db-sql")]
     pub fn db(&self) -> &DatabaseConnection {
         self.inner.db()
     }
 
+    /// The connection to the database's read replica. If no read replica is configured, this
+    /// is the same connection as [AppContext::db].
+    #[cfg(feature = "db-sql")]
+    pub fn db_read_replica(&self) -> &DatabaseConnection {
+        self.inner.db_read_replica()
+    }
+
     #[cfg(feature = "sidekiq")]
     pub fn redis_enqueue(&self) -> &sidekiq::RedisPool {
         self.inner.redis_enqueue()
@@ -154,14 +420,123 @@ impl AppContext {
     pub fn redis_fetch(&self) -> &Option<sidekiq::RedisPool> {
         self.inner.redis_fetch()
     }
+
+    /// Whether the Sidekiq processor is currently paused. See [Self::set_sidekiq_fetch_paused].
+    #[cfg(feature = "sidekiq")]
+    pub fn sidekiq_fetch_paused(&self) -> bool {
+        self.inner.sidekiq_fetch_paused()
+    }
+
+    /// Pause (or resume) processing of Sidekiq jobs without restarting the app. This can be
+    /// useful for incident response.
+    ///
+    /// Note that this does not stop the Sidekiq processor from fetching jobs from Redis. Instead,
+    /// while paused, each Sidekiq worker task holds the next job it fetches without running it
+    /// until processing is resumed. Because the worker task is blocked on the held job, it
+    /// doesn't fetch any other jobs in the meantime, so at most one job per worker task is
+    /// removed from Redis while processing is paused. Jobs that are already running when the
+    /// pause is set are allowed to complete.
+    ///
+    /// A held job is enqueued again (instead of being run) if the app starts shutting down, or
+    /// if the job is held for longer than the worker's
+    /// [max duration][crate::service::worker::sidekiq::app_worker::AppWorkerConfig::max_duration]
+    /// (if the worker's timeout is enabled).
+    #[cfg(feature = "sidekiq")]
+    pub fn set_sidekiq_fetch_paused(&self, paused: bool) {
+        self.inner.set_sidekiq_fetch_paused(paused)
+    }
+
+    /// Get the [DynEnqueuer], which allows enqueuing jobs for the app's registered Sidekiq
+    /// workers by name with JSON args.
+    #[cfg(feature = "sidekiq")]
+    pub fn sidekiq_dyn_enqueuer(&self) -> Arc<DynEnqueuer> {
+        self.inner.sidekiq_dyn_enqueuer()
+    }
+
+    /// Whether the app is ready to handle requests. This is `true` until the app receives a
+    /// shutdown signal, at which point it's set to `false` for the duration of the
+    /// [pre-shutdown-delay][crate::config::app_config::App::pre_shutdown_delay] before the app
+    /// starts shutting down. While the app is not ready, the app's health checks report it as
+    /// unhealthy, which allows load balancers to stop routing requests to the app.
+    pub fn ready(&self) -> bool {
+        self.inner.ready()
+    }
+
+    /// Set whether the app is ready to handle requests. See [Self::ready].
+    pub fn set_ready(&self, ready: bool) {
+        self.inner.set_ready(ready)
+    }
+
+    /// Get a [watch::Receiver] that is notified when the value of [Self::ready] changes, e.g. to
+    /// report the app's new status to a health service without waiting for its next poll.
+    pub fn subscribe_ready(&self) -> watch::Receiver<bool> {
+        self.inner.subscribe_ready()
+    }
+
+    /// Get the [CancellationToken] that is cancelled when the app starts shutting down.
+    /// Long-running tasks can use this to stop gracefully when the app is shutting down.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.inner.cancellation_token()
+    }
+
+    /// Spawn a background task that's tied to the app's lifecycle. When the app shuts down, it
+    /// will wait for the task to complete before closing the app's resources (e.g., the DB
+    /// connection pool). The task is responsible for stopping when the app shuts down, e.g. by
+    /// waiting on the [Self::cancellation_token]; use [Self::spawn_cancellable] to have the task
+    /// stopped automatically instead.
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.inner.task_tracker().spawn(future)
+    }
+
+    /// Same as [Self::spawn], except the task will be stopped (dropped) when the app starts
+    /// shutting down. Resolves to `None` if the task was stopped before it completed.
+    pub fn spawn_cancellable<F>(&self, future: F) -> JoinHandle<Option<F::Output>>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let cancellation_token = self.cancellation_token();
+        self.spawn(async move {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => None,
+                output = future => Some(output),
+            }
+        })
+    }
+
+    /// The number of tasks spawned via [Self::spawn] or [Self::spawn_cancellable] that have not
+    /// completed yet.
+    pub(crate) fn background_task_count(&self) -> usize {
+        self.inner.task_tracker().len()
+    }
+
+    /// Wait for all of the tasks spawned via [Self::spawn] or [Self::spawn_cancellable] to
+    /// complete. No new tasks can be spawned after this is called.
+    pub(crate) async fn wait_for_tasks(&self) {
+        let task_tracker = self.inner.task_tracker();
+        task_tracker.close();
+        task_tracker.wait().await;
+    }
 }
 
 struct AppContextInner {
     config: AppConfig,
     metadata: AppMetadata,
     health_checks: OnceLock<HealthCheckRegistry>,
+    #[cfg(feature = "http")]
+    http_bound_addr: OnceLock<SocketAddr>,
+    #[cfg(all(feature = "http", feature = "jwt"))]
+    jwt_key_provider: OnceLock<Arc<dyn JwtKeyProvider>>,
+    #[cfg(all(feature = "http", feature = "jwt"))]
+    jwt_claims_validator: OnceLock<Arc<dyn ClaimsValidator>>,
+    #[cfg(feature = "db-sql")]
+    db: Arc<DatabaseConnection>,
     #[cfg(feature = "db-sql")]
-    db: DatabaseConnection,
+    db_read_replica: Arc<DatabaseConnection>,
     #[cfg(feature = "sidekiq")]
     redis_enqueue: sidekiq::RedisPool,
     /// The Redis connection pool used by [sidekiq::Processor] to fetch Sidekiq jobs from Redis.
@@ -169,6 +544,14 @@ struct AppContextInner {
     /// config is set to zero, in which case the [sidekiq::Processor] would also not be started.
     #[cfg(feature = "sidekiq")]
     redis_fetch: Option<sidekiq::RedisPool>,
+    #[cfg(feature = "sidekiq")]
+    sidekiq_fetch_paused: AtomicBool,
+    #[cfg(feature = "sidekiq")]
+    sidekiq_dyn_enqueuer: Arc<DynEnqueuer>,
+    ready: watch::Sender<bool>,
+    cancellation_token: CancellationToken,
+    /// Tracks the tasks spawned via [AppContext::spawn].
+    task_tracker: TaskTracker,
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -197,11 +580,61 @@ impl AppContextInner {
         Ok(())
     }
 
+    #[cfg(feature = "http")]
+    fn http_bound_addr(&self) -> Option<SocketAddr> {
+        self.http_bound_addr.get().copied()
+    }
+
+    #[cfg(feature = "http")]
+    fn set_http_bound_addr(&self, addr: SocketAddr) -> RoadsterResult<()> {
+        self.http_bound_addr
+            .set(addr)
+            .map_err(|_| anyhow!("Unable to set http bound address"))?;
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "http", feature = "jwt"))]
+    fn jwt_key_provider(&self) -> Option<Arc<dyn JwtKeyProvider>> {
+        self.jwt_key_provider.get().cloned()
+    }
+
+    #[cfg(all(feature = "http", feature = "jwt"))]
+    fn set_jwt_key_provider(&self, key_provider: Arc<dyn JwtKeyProvider>) -> RoadsterResult<()> {
+        self.jwt_key_provider
+            .set(key_provider)
+            .map_err(|_| anyhow!("Unable to set JWT key provider"))?;
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "http", feature = "jwt"))]
+    fn jwt_claims_validator(&self) -> Option<Arc<dyn ClaimsValidator>> {
+        self.jwt_claims_validator.get().cloned()
+    }
+
+    #[cfg(all(feature = "http", feature = "jwt"))]
+    fn set_jwt_claims_validator(
+        &self,
+        claims_validator: Arc<dyn ClaimsValidator>,
+    ) -> RoadsterResult<()> {
+        self.jwt_claims_validator
+            .set(claims_validator)
+            .map_err(|_| anyhow!("Unable to set JWT claims validator"))?;
+
+        Ok(())
+    }
+
     #[cfg(feature = "db-sql")]
-    fn db(&self) -> &DatabaseConnection {
+    fn db(&self) -> &Arc<DatabaseConnection> {
         &self.db
     }
 
+    #[cfg(feature = "db-sql")]
+    fn db_read_replica(&self) -> &Arc<DatabaseConnection> {
+        &self.db_read_replica
+    }
+
     #[cfg(feature = "sidekiq")]
     fn redis_enqueue(&self) -> &sidekiq::RedisPool {
         &self.redis_enqueue
@@ -211,4 +644,84 @@ impl AppContextInner {
     fn redis_fetch(&self) -> &Option<sidekiq::RedisPool> {
         &self.redis_fetch
     }
+
+    #[cfg(feature = "sidekiq")]
+    fn sidekiq_fetch_paused(&self) -> bool {
+        self.sidekiq_fetch_paused.load(Ordering::SeqCst)
+    }
+
+    #[cfg(feature = "sidekiq")]
+    fn set_sidekiq_fetch_paused(&self, paused: bool) {
+        self.sidekiq_fetch_paused.store(paused, Ordering::SeqCst)
+    }
+
+    #[cfg(feature = "sidekiq")]
+    fn sidekiq_dyn_enqueuer(&self) -> Arc<DynEnqueuer> {
+        self.sidekiq_dyn_enqueuer.clone()
+    }
+
+    fn ready(&self) -> bool {
+        *self.ready.borrow()
+    }
+
+    fn set_ready(&self, ready: bool) {
+        set_ready(&self.ready, ready)
+    }
+
+    fn subscribe_ready(&self) -> watch::Receiver<bool> {
+        self.ready.subscribe()
+    }
+
+    fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation_token.clone()
+    }
+
+    fn task_tracker(&self) -> TaskTracker {
+        self.task_tracker.clone()
+    }
+}
+
+/// Update the `ready` value, only notifying the receivers if the value changed.
+fn set_ready(sender: &watch::Sender<bool>, ready: bool) {
+    sender.send_if_modified(|current| {
+        let modified = *current != ready;
+        *current = ready;
+        modified
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn spawn_tied_to_shutdown() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let completed = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let cancellation_token = context.cancellation_token();
+            let completed = completed.clone();
+            context.spawn(async move {
+                cancellation_token.cancelled().await;
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                completed.store(true, Ordering::SeqCst);
+            })
+        };
+        let cancellable_handle = context.spawn_cancellable(std::future::pending::<()>());
+
+        // Act
+        context.cancellation_token().cancel();
+        tokio::time::timeout(Duration::from_secs(5), context.wait_for_tasks())
+            .await
+            .unwrap();
+
+        // Assert
+        assert!(completed.load(Ordering::SeqCst));
+        assert!(handle.is_finished());
+        assert!(cancellable_handle.await.unwrap().is_none());
+    }
 }
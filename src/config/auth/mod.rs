@@ -3,6 +3,7 @@ use serde_derive::{Deserialize, Serialize};
 use validator::Validate;
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct Auth {
@@ -11,16 +12,24 @@ pub struct Auth {
 }
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct Jwt {
     pub secret: String,
+    /// The amount of leeway (in seconds) to allow when validating the `exp` and `nbf` claims of
+    /// a JWT, to account for clock skew between the token issuer and the app. If not provided,
+    /// the default from the [jsonwebtoken](https://docs.rs/jsonwebtoken) crate is used
+    /// (currently 60 seconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leeway_seconds: Option<u64>,
     #[serde(default)]
     #[validate(nested)]
     pub claims: JwtClaims,
 }
 
 #[derive(Debug, Clone, Default, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct JwtClaims {
@@ -30,6 +39,27 @@ pub struct JwtClaims {
     /// Claim names to require, in addition to the default-required `exp` claim.
     #[serde(default)]
     pub required_claims: Vec<String>,
+    /// How to parse the `sub` claim into the
+    /// [Jwt::subject][crate::middleware::http::auth::jwt::Jwt::subject].
+    #[serde(default)]
+    pub subject_coercion: SubjectCoercion,
+}
+
+/// Controls which types the `sub` claim of a JWT is coerced into when it's parsed as a
+/// [Subject][crate::middleware::http::auth::jwt::Subject].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum SubjectCoercion {
+    /// Try to parse the `sub` as a URI, UUID, or integer, in that order, falling back to a string.
+    #[default]
+    Full,
+    /// Try to parse the `sub` as a URI or UUID, falling back to a string. Useful if the `sub` may
+    /// contain integer-like strings that need to be preserved as-is, e.g. zero-padded IDs.
+    NoInt,
+    /// Always treat the `sub` as a string.
+    Disabled,
 }
 
 #[cfg(test)]
@@ -77,6 +107,21 @@ mod tests {
         required-claims = ["baz"]
         "#
     )]
+    #[case(
+        r#"
+        [jwt]
+        secret = "foo"
+        leeway-seconds = 10
+        "#
+    )]
+    #[case(
+        r#"
+        [jwt]
+        secret = "foo"
+        [jwt.claims]
+        subject-coercion = "no-int"
+        "#
+    )]
     #[cfg_attr(coverage_nightly, coverage(off))]
     fn auth(_case: TestCase, #[case] config: &str) {
         let auth: Auth = toml::from_str(config).unwrap();
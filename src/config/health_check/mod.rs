@@ -2,9 +2,17 @@ use crate::app::context::AppContext;
 use crate::config::app_config::CustomConfig;
 use crate::util::serde_util::default_true;
 use axum::extract::FromRef;
+#[cfg(feature = "system-health-check")]
+use byte_unit::Byte;
+#[cfg(feature = "system-health-check")]
+use byte_unit::Unit::{GB, MB};
 use config::{FileFormat, FileSourceString};
 use serde_derive::{Deserialize, Serialize};
+use serde_with::serde_as;
 use std::collections::BTreeMap;
+#[cfg(feature = "system-health-check")]
+use std::path::PathBuf;
+use std::time::Duration;
 use validator::Validate;
 
 pub fn default_config() -> config::File<FileSourceString, FileFormat> {
@@ -12,6 +20,7 @@ pub fn default_config() -> config::File<FileSourceString, FileFormat> {
 }
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct HealthCheck {
@@ -21,6 +30,12 @@ pub struct HealthCheck {
     pub database: HealthCheckConfig<()>,
     #[cfg(feature = "sidekiq")]
     pub sidekiq: HealthCheckConfig<()>,
+    #[cfg(feature = "system-health-check")]
+    #[serde(default)]
+    pub disk_space: HealthCheckConfig<DiskSpaceHealthCheckConfig>,
+    #[cfg(feature = "system-health-check")]
+    #[serde(default)]
+    pub memory: HealthCheckConfig<MemoryHealthCheckConfig>,
     /// Allows providing configs for custom health checks. Any configs that aren't pre-defined above
     /// will be collected here.
     ///
@@ -50,7 +65,9 @@ pub struct HealthCheck {
     pub custom: BTreeMap<String, HealthCheckConfig<CustomConfig>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde_as]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct CommonConfig {
@@ -60,6 +77,19 @@ pub struct CommonConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub enable: Option<bool>,
+    /// If provided, the health check's reported status only changes after the check returns the
+    /// opposite status the configured number of consecutive times. See
+    /// [HealthCheck::circuit_breaker][crate::health_check::HealthCheck::circuit_breaker].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// If provided, the health check is run in the background on this interval (in seconds), and
+    /// the most recent result is returned instead of running the check each time. See
+    /// [HealthCheck::cache_interval][crate::health_check::HealthCheck::cache_interval].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<serde_with::DurationSeconds>")]
+    #[cfg_attr(feature = "config-schema", schemars(with = "Option<u64>"))]
+    pub cache_interval: Option<Duration>,
 }
 
 impl CommonConfig {
@@ -77,7 +107,30 @@ impl CommonConfig {
     }
 }
 
+/// Thresholds used to smooth out transient failures (or successes) of a flapping health check.
+///
+/// # Examples
+///
+/// ```toml
+/// [health-check.database.circuit-breaker]
+/// failure-threshold = 3
+/// success-threshold = 2
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct CircuitBreaker {
+    /// The number of consecutive failed checks required before a healthy check is reported as
+    /// unhealthy.
+    pub failure_threshold: u32,
+    /// The number of consecutive successful checks required before an unhealthy check is
+    /// reported as healthy again.
+    pub success_threshold: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct HealthCheckConfig<T> {
@@ -87,6 +140,50 @@ pub struct HealthCheckConfig<T> {
     pub custom: T,
 }
 
+#[cfg(feature = "system-health-check")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", default)]
+#[non_exhaustive]
+pub struct DiskSpaceHealthCheckConfig {
+    /// The paths to monitor. The free space of the disk that contains each path is checked.
+    /// Defaults to the app's current working directory.
+    pub paths: Vec<PathBuf>,
+    /// The minimum amount of free space required on each disk for the check to pass.
+    #[cfg_attr(feature = "config-schema", schemars(with = "String"))]
+    pub min_free: Byte,
+}
+
+#[cfg(feature = "system-health-check")]
+impl Default for DiskSpaceHealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            paths: vec![PathBuf::from(".")],
+            min_free: Byte::from_u64_with_unit(1, GB).unwrap(),
+        }
+    }
+}
+
+#[cfg(feature = "system-health-check")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", default)]
+#[non_exhaustive]
+pub struct MemoryHealthCheckConfig {
+    /// The minimum amount of available memory required for the check to pass.
+    #[cfg_attr(feature = "config-schema", schemars(with = "String"))]
+    pub min_free: Byte,
+}
+
+#[cfg(feature = "system-health-check")]
+impl Default for MemoryHealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            min_free: Byte::from_u64_with_unit(100, MB).unwrap(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,7 +209,11 @@ mod tests {
 
         let context = AppContext::test(Some(config), None, None).unwrap();
 
-        let common_config = CommonConfig { enable };
+        let common_config = CommonConfig {
+            enable,
+            circuit_breaker: None,
+            cache_interval: None,
+        };
 
         // Act/Assert
         assert_eq!(common_config.enabled(&context), expected_enabled);
@@ -4,5 +4,60 @@ pub mod auth;
 pub mod database;
 pub mod environment;
 pub mod health_check;
+pub mod runtime;
+pub mod secrets;
 pub mod service;
 pub mod tracing;
+#[cfg(feature = "config-watch")]
+pub mod watch;
+
+/// Generate a [JSON Schema](https://json-schema.org/) for the app's [AppConfig][app_config::AppConfig].
+/// The schema can be used by editors to provide completion and validation when editing the
+/// app's config files.
+///
+/// Note: the schema only includes the fields for the features that are enabled. Additionally,
+/// custom config values are allowed anywhere custom config is supported, so the schema can not
+/// detect typos in those sections of the config.
+#[cfg(feature = "config-schema")]
+pub fn json_schema() -> serde_json::Value {
+    let schema = schemars::gen::SchemaSettings::draft07()
+        .with(|settings| settings.inline_subschemas = true)
+        .into_generator()
+        .into_root_schema_for::<app_config::AppConfig>();
+    // Serializing a `RootSchema` into a `Value` should never fail.
+    serde_json::to_value(schema).expect("Unable to serialize the config JSON schema")
+}
+
+#[cfg(all(test, feature = "config-schema"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "http")]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn json_schema_http_port() {
+        let schema = json_schema();
+
+        let port = &schema["properties"]["service"]["properties"]["http"]["properties"]["port"];
+        assert_eq!(port["type"], "integer");
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn json_schema_jwt_secret() {
+        let schema = json_schema();
+
+        let secret = &schema["properties"]["auth"]["properties"]["jwt"]["properties"]["secret"];
+        assert_eq!(secret["type"], "string");
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn json_schema_field_docs() {
+        let schema = json_schema();
+
+        let shutdown_on_error =
+            &schema["properties"]["app"]["properties"]["shutdown-on-error"]["description"];
+        assert!(shutdown_on_error.is_string());
+    }
+}
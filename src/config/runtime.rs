@@ -0,0 +1,82 @@
+use serde_derive::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Config for the Tokio runtime created by [crate::app::run_with_runtime_config]. This config is
+/// not used if the app creates its own runtime, e.g. with `#[tokio::main]`. Any values that are
+/// not provided will use Tokio's defaults.
+#[derive(Debug, Clone, Default, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct Runtime {
+    /// The number of worker threads the runtime will use. Defaults to the number of CPU cores
+    /// available to the app.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub worker_threads: Option<usize>,
+
+    /// The name to use for the threads spawned by the runtime.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_name: Option<String>,
+
+    /// The stack size (in bytes) of the threads spawned by the runtime.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub thread_stack_size: Option<usize>,
+
+    /// The maximum number of threads spawned by the runtime for blocking operations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_blocking_threads: Option<usize>,
+}
+
+impl Runtime {
+    /// Build a multi-threaded Tokio runtime using the values from this config.
+    pub fn build(&self) -> std::io::Result<tokio::runtime::Runtime> {
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        builder.enable_all();
+        if let Some(worker_threads) = self.worker_threads {
+            builder.worker_threads(worker_threads);
+        }
+        if let Some(thread_name) = self.thread_name.as_ref() {
+            builder.thread_name(thread_name);
+        }
+        if let Some(thread_stack_size) = self.thread_stack_size {
+            builder.thread_stack_size(thread_stack_size);
+        }
+        if let Some(max_blocking_threads) = self.max_blocking_threads {
+            builder.max_blocking_threads(max_blocking_threads);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn build() {
+        // Arrange
+        let config = Runtime {
+            worker_threads: Some(3),
+            thread_name: Some("roadster-test".to_string()),
+            thread_stack_size: None,
+            max_blocking_threads: Some(10),
+        };
+
+        // Act
+        let runtime = config.build().unwrap();
+
+        // Assert
+        assert_eq!(runtime.metrics().num_workers(), 3);
+        let thread_name = runtime
+            .block_on(async {
+                tokio::spawn(async { std::thread::current().name().map(|name| name.to_string()) })
+                    .await
+            })
+            .unwrap();
+        assert_eq!(thread_name.as_deref(), Some("roadster-test"));
+    }
+}
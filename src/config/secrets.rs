@@ -0,0 +1,240 @@
+use config::{ConfigError, Map, Source, Value, ValueKind};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use typed_builder::TypedBuilder;
+
+/// How [FileSecretsSource] should behave if a configured secret file does not exist.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum MissingFileBehavior {
+    /// Return an error if a configured secret file does not exist.
+    #[default]
+    Error,
+    /// Skip any configured secret files that do not exist.
+    Skip,
+}
+
+/// A [config::Source] that reads config values from files. This is useful for secrets that are
+/// mounted as files, e.g. Docker or Kubernetes secrets.
+///
+/// Secrets can be provided either as a mapping of config key to file path, or as a directory
+/// where each file name is the config key. Nested keys are separated by `.`, e.g.
+/// `database.uri`. The contents of each file are used as the value of the config key, with
+/// any trailing newlines removed. Empty files are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use roadster::config::secrets::{FileSecretsSource, MissingFileBehavior};
+/// use std::collections::BTreeMap;
+/// use std::path::PathBuf;
+///
+/// let source = FileSecretsSource::builder()
+///     .files(BTreeMap::from([(
+///         "auth.jwt.secret".to_string(),
+///         PathBuf::from("/run/secrets/jwt_secret"),
+///     )]))
+///     .directory("/run/secrets/roadster")
+///     .missing_file_behavior(MissingFileBehavior::Skip)
+///     .build();
+/// ```
+#[derive(Debug, Clone, TypedBuilder)]
+#[non_exhaustive]
+pub struct FileSecretsSource {
+    /// A mapping of config key to the path of the file containing the key's value.
+    #[builder(default)]
+    files: BTreeMap<String, PathBuf>,
+    /// A directory containing secret files, where each file name is the config key.
+    #[builder(default, setter(strip_option, into))]
+    directory: Option<PathBuf>,
+    #[builder(default)]
+    missing_file_behavior: MissingFileBehavior,
+}
+
+impl FileSecretsSource {
+    fn read_secret(&self, path: &Path) -> Result<Option<String>, ConfigError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err)
+                if err.kind() == ErrorKind::NotFound
+                    && self.missing_file_behavior == MissingFileBehavior::Skip =>
+            {
+                return Ok(None);
+            }
+            Err(err) => {
+                return Err(ConfigError::Message(format!(
+                    "Unable to read secret file `{}`: {err}",
+                    path.display()
+                )));
+            }
+        };
+        let contents = contents.trim_end_matches(['\n', '\r']);
+        if contents.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(contents.to_string()))
+    }
+
+    fn secret_paths(&self) -> Result<Vec<(String, PathBuf)>, ConfigError> {
+        let mut paths = Vec::new();
+
+        if let Some(directory) = self.directory.as_ref() {
+            let entries = match fs::read_dir(directory) {
+                Ok(entries) => entries,
+                Err(err)
+                    if err.kind() == ErrorKind::NotFound
+                        && self.missing_file_behavior == MissingFileBehavior::Skip =>
+                {
+                    return Ok(self.files.clone().into_iter().collect());
+                }
+                Err(err) => {
+                    return Err(ConfigError::Message(format!(
+                        "Unable to read secrets directory `{}`: {err}",
+                        directory.display()
+                    )));
+                }
+            };
+            for entry in entries {
+                let entry = entry.map_err(|err| ConfigError::Foreign(Box::new(err)))?;
+                if !entry.path().is_file() {
+                    continue;
+                }
+                let key = entry.file_name().to_string_lossy().to_string();
+                paths.push((key, entry.path()));
+            }
+            paths.sort();
+        }
+
+        // Explicitly configured files take precedence over files from the directory.
+        paths.extend(self.files.clone());
+
+        Ok(paths)
+    }
+}
+
+impl Source for FileSecretsSource {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let mut map = Map::new();
+        for (key, path) in self.secret_paths()? {
+            if let Some(secret) = self.read_secret(&path)? {
+                let origin = path.display().to_string();
+                map.insert(key, Value::new(Some(&origin), ValueKind::String(secret)));
+            }
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_util::TempDir;
+    use config::Config;
+
+    fn build(source: FileSecretsSource) -> Result<Config, ConfigError> {
+        Config::builder().add_source(source).build()
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn files() {
+        // Arrange
+        let dir = TempDir::new();
+        let path = dir.write("jwt_secret", "foo\n");
+        let source = FileSecretsSource::builder()
+            .files(BTreeMap::from([("auth.jwt.secret".to_string(), path)]))
+            .build();
+
+        // Act
+        let config = build(source).unwrap();
+
+        // Assert
+        assert_eq!(config.get_string("auth.jwt.secret").unwrap(), "foo");
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn directory() {
+        // Arrange
+        let dir = TempDir::new();
+        dir.write("database.uri", "postgres://localhost:5432/example\r\n");
+        dir.write("auth.jwt.secret", "foo");
+        let source = FileSecretsSource::builder()
+            .directory(dir.path().to_path_buf())
+            .build();
+
+        // Act
+        let config = build(source).unwrap();
+
+        // Assert
+        assert_eq!(
+            config.get_string("database.uri").unwrap(),
+            "postgres://localhost:5432/example"
+        );
+        assert_eq!(config.get_string("auth.jwt.secret").unwrap(), "foo");
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn empty_file() {
+        // Arrange
+        let dir = TempDir::new();
+        let path = dir.write("jwt_secret", "\n");
+        let source = FileSecretsSource::builder()
+            .files(BTreeMap::from([("auth.jwt.secret".to_string(), path)]))
+            .build();
+
+        // Act
+        let config = build(source).unwrap();
+
+        // Assert
+        assert!(config.get_string("auth.jwt.secret").is_err());
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn missing_file_error() {
+        // Arrange
+        let dir = TempDir::new();
+        let source = FileSecretsSource::builder()
+            .files(BTreeMap::from([(
+                "auth.jwt.secret".to_string(),
+                dir.path().join("missing"),
+            )]))
+            .build();
+
+        // Act
+        let config = build(source);
+
+        // Assert
+        assert!(config.is_err());
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn missing_file_skip() {
+        // Arrange
+        let dir = TempDir::new();
+        let source = FileSecretsSource::builder()
+            .files(BTreeMap::from([(
+                "auth.jwt.secret".to_string(),
+                dir.path().join("missing"),
+            )]))
+            .missing_file_behavior(MissingFileBehavior::Skip)
+            .build();
+
+        // Act
+        let config = build(source).unwrap();
+
+        // Assert
+        assert!(config.get_string("auth.jwt.secret").is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use crate::config::service::common::address::Address;
 use config::{FileFormat, FileSourceString};
 use serde_derive::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::time::Duration;
 use validator::Validate;
 
 pub fn default_config() -> config::File<FileSourceString, FileFormat> {
@@ -8,10 +10,57 @@ pub fn default_config() -> config::File<FileSourceString, FileFormat> {
 }
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct GrpcServiceConfig {
     #[serde(flatten)]
     #[validate(nested)]
     pub address: Address,
+
+    /// Config for the standard gRPC health service (`grpc.health.v1.Health`).
+    #[serde(default)]
+    pub health_service: GrpcHealthServiceConfig,
+
+    /// Config for the standard gRPC reflection service (`grpc.reflection.v1alpha.ServerReflection`).
+    #[serde(default)]
+    pub reflection_service: GrpcReflectionServiceConfig,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", default)]
+#[non_exhaustive]
+pub struct GrpcHealthServiceConfig {
+    /// Whether to add the health service to the gRPC server. The status of the server (the
+    /// `""` service) is `SERVING` if all of the app's
+    /// [HealthCheck][crate::health_check::HealthCheck]s pass, and `NOT_SERVING` otherwise.
+    pub enable: bool,
+
+    /// How often to run the app's health checks to update the status reported by the health
+    /// service. This is also used as the maximum duration of each run of the health checks.
+    #[serde_as(as = "serde_with::DurationMilliSeconds")]
+    #[cfg_attr(feature = "config-schema", schemars(with = "u64"))]
+    pub interval: Duration,
+}
+
+impl Default for GrpcHealthServiceConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            interval: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", default)]
+#[non_exhaustive]
+pub struct GrpcReflectionServiceConfig {
+    /// Whether to add the reflection service to the gRPC server. The app's services need to be
+    /// registered with the reflection service via
+    /// [GrpcService::register_encoded_file_descriptor_set][crate::service::grpc::service::GrpcService::register_encoded_file_descriptor_set].
+    pub enable: bool,
 }
@@ -1,11 +1,15 @@
 use crate::app::context::AppContext;
 use crate::util::serde_util::default_true;
 use axum::extract::FromRef;
+use ipnet::IpNet;
+use serde::Deserializer;
 use serde_derive::{Deserialize, Serialize};
+use std::net::IpAddr;
 use validator::Validate;
 use validator::ValidationError;
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[validate(schema(function = "validate_default_routes"))]
 #[non_exhaustive]
@@ -15,7 +19,7 @@ pub struct DefaultRoutes {
 
     pub ping: DefaultRouteConfig,
 
-    pub health: DefaultRouteConfig,
+    pub health: HealthRouteConfig,
 
     #[cfg(feature = "open-api")]
     pub api_schema: DefaultRouteConfig,
@@ -54,6 +58,7 @@ fn validate_default_routes(
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct DefaultRouteConfig {
@@ -80,8 +85,64 @@ impl DefaultRouteConfig {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct HealthRouteConfig {
+    #[serde(flatten)]
+    pub common: DefaultRouteConfig,
+
+    /// Restrict which clients are allowed to access the health route. The health route can
+    /// expose details about the app's resources, so it may be desirable to restrict it in
+    /// some environments.
+    #[serde(default)]
+    pub access: HealthAccess,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum HealthAccess {
+    /// Any client can access the health route.
+    #[default]
+    Public,
+    /// Only requests with a valid JWT can access the health route.
+    #[cfg(feature = "jwt")]
+    RequireJwt,
+    /// Only requests from the listed IP addresses or CIDR ranges (e.g. `10.0.0.0/8`) can access
+    /// the health route. A plain IP address (e.g. `127.0.0.1`) only allows that address. Requests
+    /// from any other IP address will receive a `403 Forbidden` response.
+    IpAllowlist {
+        #[serde(deserialize_with = "deserialize_ip_nets")]
+        #[cfg_attr(feature = "config-schema", schemars(with = "Vec<String>"))]
+        allow: Vec<IpNet>,
+    },
+}
+
+/// Deserialize a list of CIDR ranges, where plain IP addresses are treated as a range that only
+/// contains the address (i.e., a `/32` range for IPv4 or a `/128` range for IPv6).
+fn deserialize_ip_nets<'de, D>(deserializer: D) -> Result<Vec<IpNet>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    <Vec<String> as serde::Deserialize>::deserialize(deserializer)?
+        .iter()
+        .map(|value| parse_ip_net(value).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+fn parse_ip_net(value: &str) -> Result<IpNet, String> {
+    value
+        .parse::<IpNet>()
+        .or_else(|_| value.parse::<IpAddr>().map(IpNet::from))
+        .map_err(|_| format!("Invalid IP address or CIDR range `{value}`"))
+}
+
 #[cfg(test)]
 mod tests {
+    use super::HealthAccess;
     use crate::config::app_config::AppConfig;
     use crate::config::service::http::*;
     use rstest::rstest;
@@ -150,4 +211,48 @@ mod tests {
         // Assert
         assert_eq!(result.is_err(), validation_error);
     }
+
+    #[rstest]
+    #[case("127.0.0.1", "127.0.0.1/32")]
+    #[case("10.0.0.0/8", "10.0.0.0/8")]
+    #[case("::1", "::1/128")]
+    #[case("fd00::/8", "fd00::/8")]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn parse_ip_net(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(
+            super::parse_ip_net(value).unwrap(),
+            expected.parse::<ipnet::IpNet>().unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn parse_ip_net_invalid() {
+        assert!(super::parse_ip_net("foo").is_err());
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn deserialize_health_access_ip_allowlist() {
+        // Act
+        let access: HealthAccess = toml::from_str(
+            r#"
+            type = "ip-allowlist"
+            allow = ["127.0.0.1", "10.0.0.0/8"]
+            "#,
+        )
+        .unwrap();
+
+        // Assert
+        let HealthAccess::IpAllowlist { allow } = access else {
+            panic!("Expected an IP allowlist");
+        };
+        assert_eq!(
+            allow,
+            vec![
+                "127.0.0.1/32".parse::<ipnet::IpNet>().unwrap(),
+                "10.0.0.0/8".parse::<ipnet::IpNet>().unwrap()
+            ]
+        );
+    }
 }
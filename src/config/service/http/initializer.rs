@@ -1,6 +1,7 @@
 use crate::app::context::AppContext;
 use crate::config::app_config::CustomConfig;
 use crate::service::http::initializer::normalize_path::NormalizePathConfig;
+use crate::service::http::initializer::static_files::StaticFilesConfig;
 use crate::util::serde_util::default_true;
 use axum::extract::FromRef;
 use serde_derive::{Deserialize, Serialize};
@@ -11,6 +12,7 @@ pub const PRIORITY_FIRST: i32 = -10_000;
 pub const PRIORITY_LAST: i32 = 10_000;
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct Initializer {
@@ -18,6 +20,7 @@ pub struct Initializer {
     pub default_enable: bool,
 
     pub normalize_path: InitializerConfig<NormalizePathConfig>,
+    pub static_files: InitializerConfig<StaticFilesConfig>,
     /// Allows providing configs for custom initializers. Any configs that aren't pre-defined above
     /// will be collected here.
     ///
@@ -49,6 +52,7 @@ pub struct Initializer {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct CommonConfig {
@@ -80,6 +84,7 @@ impl CommonConfig {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct InitializerConfig<T> {
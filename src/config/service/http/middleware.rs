@@ -5,6 +5,8 @@ use crate::service::http::middleware::compression::{
     RequestDecompressionConfig, ResponseCompressionConfig,
 };
 use crate::service::http::middleware::cors::CorsConfig;
+#[cfg(feature = "http-etag")]
+use crate::service::http::middleware::etag::ETagConfig;
 use crate::service::http::middleware::request_id::{PropagateRequestIdConfig, SetRequestIdConfig};
 use crate::service::http::middleware::sensitive_headers::{
     SensitiveRequestHeadersConfig, SensitiveResponseHeadersConfig,
@@ -22,6 +24,7 @@ pub const PRIORITY_FIRST: i32 = -10_000;
 pub const PRIORITY_LAST: i32 = 10_000;
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct Middleware {
@@ -50,6 +53,9 @@ pub struct Middleware {
 
     pub cors: MiddlewareConfig<CorsConfig>,
 
+    #[cfg(feature = "http-etag")]
+    pub etag: MiddlewareConfig<ETagConfig>,
+
     /// Allows providing configs for custom middleware. Any configs that aren't pre-defined above
     /// will be collected here.
     ///
@@ -81,6 +87,7 @@ pub struct Middleware {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct CommonConfig {
@@ -112,6 +119,7 @@ impl CommonConfig {
 }
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct MiddlewareConfig<T> {
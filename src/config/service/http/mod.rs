@@ -1,26 +1,78 @@
 use crate::config::service::common::address::Address;
 use crate::config::service::http::initializer::Initializer;
 use crate::config::service::http::middleware::Middleware;
+#[cfg(feature = "http-tls")]
+use crate::config::service::http::tls::Tls;
 use config::{FileFormat, FileSourceString};
 use default_routes::DefaultRoutes;
 use serde_derive::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use std::time::Duration;
+use tracing::Level;
 use validator::Validate;
 
 pub mod default_routes;
 pub mod initializer;
 pub mod middleware;
+#[cfg(feature = "http-tls")]
+pub mod tls;
 
 pub fn default_config() -> config::File<FileSourceString, FileFormat> {
     config::File::from_str(include_str!("default.toml"), FileFormat::Toml)
 }
 
+#[cfg(feature = "http-etag")]
+pub fn default_etag_config() -> config::File<FileSourceString, FileFormat> {
+    config::File::from_str(include_str!("default_etag.toml"), FileFormat::Toml)
+}
+
+#[serde_as]
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct HttpServiceConfig {
     #[serde(flatten)]
     #[validate(nested)]
     pub address: Address,
+    /// Whether to accept HTTP/2 connections in addition to HTTP/1. HTTP/2 connections can be
+    /// made in cleartext (h2c) using "prior knowledge".
+    #[serde(default)]
+    pub http2_enabled: bool,
+    /// The interval at which to send HTTP/2 keep-alive pings. If not provided, keep-alive
+    /// pings are disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<serde_with::DurationSeconds>")]
+    #[cfg_attr(feature = "config-schema", schemars(with = "Option<u64>"))]
+    pub keep_alive_interval: Option<Duration>,
+    /// The maximum number of concurrent streams allowed for each HTTP/2 connection. If not
+    /// provided, the `hyper` default is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_streams: Option<u32>,
+    /// The maximum size in bytes of a request's headers (including the request line for
+    /// HTTP/1). Requests with larger headers are rejected with `431 Request Header Fields Too
+    /// Large`. Must be at least `8192`. If not provided, the `hyper` default is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 8192))]
+    pub max_header_bytes: Option<usize>,
+    /// The maximum number of headers allowed in an HTTP/1 request. Requests with more headers are
+    /// rejected with `431 Request Header Fields Too Large`. If not provided, the `hyper` default
+    /// is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_headers: Option<usize>,
+    /// If provided, the HTTP service will use TLS.
+    #[cfg(feature = "http-tls")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub tls: Option<Tls>,
+    /// The levels at which to log [HttpError][crate::error::api::http::HttpError]s when they
+    /// are converted to responses.
+    #[serde(default)]
+    pub error_log_level: ErrorLogLevel,
+    /// The format to render [HttpError][crate::error::api::http::HttpError]s in when they are
+    /// converted to responses.
+    #[serde(default)]
+    pub error_format: ErrorFormat,
     #[validate(nested)]
     pub middleware: Middleware,
     #[validate(nested)]
@@ -28,3 +80,46 @@ pub struct HttpServiceConfig {
     #[validate(nested)]
     pub default_routes: DefaultRoutes,
 }
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", default)]
+#[non_exhaustive]
+pub struct ErrorLogLevel {
+    /// The level at which to log client errors (`4xx` status codes). These are generally caused
+    /// by user mistakes, so they are logged at `WARN` by default in order to avoid alerting on
+    /// them.
+    #[serde_as(as = "DisplayFromStr")]
+    #[cfg_attr(feature = "config-schema", schemars(with = "String"))]
+    pub client_error: Level,
+
+    /// The level at which to log server errors (`5xx` status codes).
+    #[serde_as(as = "DisplayFromStr")]
+    #[cfg_attr(feature = "config-schema", schemars(with = "String"))]
+    pub server_error: Level,
+}
+
+/// The format of the response body for [HttpError][crate::error::api::http::HttpError]s.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum ErrorFormat {
+    /// A JSON object with the error's `error` and `details` fields.
+    #[default]
+    Json,
+    /// RFC 7807 problem details, with the `application/problem+json` content type. The path of
+    /// the request is included as the problem's `instance`.
+    /// See: <https://www.rfc-editor.org/rfc/rfc7807>
+    ProblemJson,
+}
+
+impl Default for ErrorLogLevel {
+    fn default() -> Self {
+        Self {
+            client_error: Level::WARN,
+            server_error: Level::ERROR,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use serde_derive::{Deserialize, Serialize};
+use std::path::PathBuf;
+use validator::Validate;
+
+/// TLS config for the HTTP service. If provided, the HTTP service will terminate TLS using the
+/// provided certificate and private key.
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct Tls {
+    /// Path to the PEM-encoded certificate chain.
+    pub cert_path: PathBuf,
+    /// Path to the PEM-encoded private key.
+    pub key_path: PathBuf,
+}
@@ -17,6 +17,7 @@ use serde_derive::{Deserialize, Serialize};
 use validator::Validate;
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct Service {
@@ -37,6 +38,7 @@ pub struct Service {
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct CommonConfig {
@@ -55,6 +57,7 @@ impl CommonConfig {
 }
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct ServiceConfig<T: Validate> {
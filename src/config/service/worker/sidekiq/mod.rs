@@ -1,3 +1,4 @@
+use crate::config::environment::Environment;
 use crate::service::worker::sidekiq::app_worker::AppWorkerConfig;
 use config::{FileFormat, FileSourceString};
 use serde_derive::{Deserialize, Serialize};
@@ -9,7 +10,20 @@ pub fn default_config() -> config::File<FileSourceString, FileFormat> {
     config::File::from_str(include_str!("default.toml"), FileFormat::Toml)
 }
 
+/// Environment-specific defaults that are applied on top of [default_config]. Returns `None` if
+/// there are no defaults specific to the given [Environment].
+pub fn default_config_per_env(
+    environment: &Environment,
+) -> Option<config::File<FileSourceString, FileFormat>> {
+    let config = match environment {
+        Environment::Test => include_str!("default_test.toml"),
+        _ => return None,
+    };
+    Some(config::File::from_str(config, FileFormat::Toml))
+}
+
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct SidekiqServiceConfig {
@@ -24,6 +38,15 @@ pub struct SidekiqServiceConfig {
     #[serde(default = "SidekiqServiceConfig::default_num_workers")]
     pub num_workers: u32,
 
+    /// The maximum number of jobs that can be processed at the same time across all workers. If
+    /// this is lower than [SidekiqServiceConfig::num_workers], some worker tasks will wait for a
+    /// job to complete before processing the job they fetched. This is useful to avoid
+    /// overwhelming a downstream resource while still keeping worker tasks ready. If not
+    /// provided, the number of in-flight jobs is only limited by `num-workers`.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub max_in_flight: Option<usize>,
+
     /// The names of the worker queues to handle.
     // Todo: Allow overriding this via CLI args?
     #[serde(default)]
@@ -50,6 +73,7 @@ impl SidekiqServiceConfig {
 }
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct Periodic {
@@ -65,6 +89,7 @@ impl Default for Periodic {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, EnumString, IntoStaticStr)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[strum(serialize_all = "kebab-case")]
 #[non_exhaustive]
@@ -80,6 +105,7 @@ pub enum StaleCleanUpBehavior {
 }
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct Redis {
@@ -96,6 +122,7 @@ pub struct Redis {
 }
 
 #[derive(Debug, Default, Validate, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(default, rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct ConnectionPool {
@@ -172,6 +199,14 @@ mod deserialize_tests {
         stale-cleanup = "auto-clean-stale"
         "#
     )]
+    #[case(
+        r#"
+        num-workers = 4
+        max-in-flight = 2
+        [redis]
+        uri = "redis://localhost:6379"
+        "#
+    )]
     #[cfg_attr(coverage_nightly, coverage(off))]
     fn sidekiq(_case: TestCase, #[case] config: &str) {
         let sidekiq: SidekiqServiceConfig = toml::from_str(config).unwrap();
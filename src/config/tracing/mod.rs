@@ -3,6 +3,9 @@ use crate::util::serde_util::default_true;
 use config::{FileFormat, FileSourceString};
 use serde_derive::{Deserialize, Serialize};
 #[cfg(feature = "otel")]
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+#[cfg(feature = "otel")]
 use url::Url;
 use validator::Validate;
 
@@ -11,6 +14,7 @@ pub fn default_config() -> config::File<FileSourceString, FileFormat> {
 }
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct Tracing {
@@ -29,6 +33,81 @@ pub struct Tracing {
     /// URI of the OTLP exporter where traces/metrics/logs will be sent.
     #[cfg(feature = "otel")]
     pub otlp_endpoint: Option<Url>,
+
+    /// Additional attributes to add to the OpenTelemetry `Resource`, e.g.
+    /// `deployment.environment` or `service.namespace`. The reserved `service.name` and
+    /// `service.version` attributes can not be set here; use the `service-name` config or
+    /// [AppMetadata][crate::app::metadata::AppMetadata] instead.
+    #[cfg(feature = "otel")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub resource_attributes: BTreeMap<String, String>,
+
+    /// If provided, logs will be written to a rolling file in addition to stdout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub file: Option<FileTracing>,
+}
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct FileTracing {
+    /// The directory to write the log files to.
+    pub dir: PathBuf,
+
+    /// The prefix of the log files' names. The date/time of the file's rotation period is
+    /// appended to the prefix, e.g. `app.log.2024-06-01` when rotating daily.
+    #[serde(default = "FileTracing::default_file_name_prefix")]
+    pub file_name_prefix: String,
+
+    /// How often to start a new log file.
+    #[serde(default)]
+    pub rotation: FileRotation,
+
+    /// The maximum number of log files to keep. When a new file is created, the oldest files are
+    /// deleted to stay within the limit. If not provided, log files are never deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_files: Option<usize>,
+
+    /// The format of the logs written to the file. This is independent of the format of the
+    /// logs written to stdout.
+    #[serde(default)]
+    pub format: LogFormat,
+}
+
+impl FileTracing {
+    fn default_file_name_prefix() -> String {
+        "app.log".to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum FileRotation {
+    Minutely,
+    Hourly,
+    #[default]
+    Daily,
+    /// Write all logs to a single file.
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum LogFormat {
+    /// The default human-readable format, one line per event.
+    #[default]
+    Full,
+    /// Same as `full`, but more compact.
+    Compact,
+    /// Newline-delimited JSON, which is easier to ingest into log processing tools.
+    Json,
 }
 
 // To simplify testing, these are only run when all of the config fields are available
@@ -69,6 +148,24 @@ mod deserialize_tests {
         otlp-endpoint = "https://example.com:1234"
         "#
     )]
+    #[case(
+        r#"
+        level = "debug"
+        [resource-attributes]
+        "deployment.environment" = "prod"
+        "service.namespace" = "foo"
+        "#
+    )]
+    #[case(
+        r#"
+        level = "debug"
+        [file]
+        dir = "logs"
+        rotation = "hourly"
+        max-files = 24
+        format = "json"
+        "#
+    )]
     #[cfg_attr(coverage_nightly, coverage(off))]
     fn sidekiq(_case: TestCase, #[case] config: &str) {
         let tracing: Tracing = toml::from_str(config).unwrap();
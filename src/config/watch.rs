@@ -0,0 +1,275 @@
+use crate::app::context::AppContext;
+use crate::app::App;
+use crate::config::app_config::{AppConfig, AppConfigOptions, CONFIG_DIR};
+use crate::config::environment::Environment;
+use crate::error::RoadsterResult;
+use crate::tracing::set_trace_level;
+use axum::extract::FromRef;
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+
+type LoadFn = Box<dyn Fn() -> RoadsterResult<AppConfig> + Send + Sync>;
+
+/// The settings that can be applied while the app is running. Changes to any other settings
+/// require restarting the app.
+const RUNTIME_SETTINGS: &[&str] = &["tracing.level"];
+
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Watches the app's config files for changes and re-loads the [AppConfig] when any of them
+/// change. This is intended to be used during local development to avoid needing to restart
+/// the app after every config change.
+///
+/// Only a subset of the config can be applied while the app is running. Currently, this is
+/// only the trace filter (`tracing.level`), which is rebuilt the same way as in
+/// [init_tracing][crate::tracing::init_tracing] (e.g., directives from the `RUST_LOG` env var
+/// still take precedence). Changes to any other settings are logged, but require restarting the
+/// app to take effect.
+pub struct ConfigWatcher {
+    paths: Vec<PathBuf>,
+    load: LoadFn,
+    config: AppConfig,
+    contents: Vec<Option<u64>>,
+}
+
+impl ConfigWatcher {
+    /// Create a new [ConfigWatcher] for the given `paths`. The `load` function is used to
+    /// re-load the [AppConfig] when any of the files change, and `config` is the config the
+    /// app is currently running with.
+    pub fn new<F>(config: AppConfig, paths: Vec<PathBuf>, load: F) -> Self
+    where
+        F: Fn() -> RoadsterResult<AppConfig> + Send + Sync + 'static,
+    {
+        let contents = contents_hashes(&paths);
+        Self {
+            paths,
+            load: Box::new(load),
+            config,
+            contents,
+        }
+    }
+
+    /// The most recently loaded [AppConfig].
+    pub fn config(&self) -> &AppConfig {
+        &self.config
+    }
+
+    /// Check whether any of the watched files changed since the previous check. If so, the
+    /// config is re-loaded and the settings that can be changed at runtime are applied.
+    ///
+    /// Returns the keys of the settings that changed (e.g., `tracing.level`), or an empty list
+    /// if none of the files changed.
+    pub fn check(&mut self) -> RoadsterResult<Vec<String>> {
+        let contents = contents_hashes(&self.paths);
+        if contents == self.contents {
+            return Ok(Vec::new());
+        }
+        self.contents = contents;
+
+        let config = (self.load)()?;
+        let mut changed = Vec::new();
+        changed_keys(
+            "",
+            &serde_json::to_value(&self.config)?,
+            &serde_json::to_value(&config)?,
+            &mut changed,
+        );
+        for key in changed.iter() {
+            if RUNTIME_SETTINGS.contains(&key.as_str()) {
+                info!(%key, "Config changed, applying the new value");
+            } else {
+                warn!(%key, "Config changed, restart the app to apply the new value");
+            }
+        }
+
+        if self.config.tracing.level != config.tracing.level {
+            if let Err(err) = set_trace_level(&config.tracing.level) {
+                warn!(%err, "Unable to apply the new trace filter");
+            }
+        }
+
+        self.config = config;
+        Ok(changed)
+    }
+
+    /// Check the watched files for changes on the given `interval`. This runs until the returned
+    /// future is dropped, so it should be spawned with
+    /// [AppContext::spawn_cancellable][crate::app::context::AppContext::spawn_cancellable].
+    pub async fn run(mut self, interval: Duration) {
+        let mut timer = tokio::time::interval(interval);
+        timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            timer.tick().await;
+            if let Err(err) = self.check() {
+                warn!(%err, "Unable to reload the config");
+            }
+        }
+    }
+}
+
+/// Watch the app's config files for changes if the app is running in the
+/// [Environment::Development] environment.
+pub(crate) fn watch_app_config<A, S>(context: &AppContext)
+where
+    S: Clone + Send + Sync + 'static,
+    AppContext: FromRef<S>,
+    A: App<S> + 'static,
+{
+    let environment = context.config().environment.clone();
+    if environment != Environment::Development {
+        return;
+    }
+    info!("Watching config files for changes");
+    let watcher = ConfigWatcher::new(
+        context.config().clone(),
+        config_file_paths(Path::new(CONFIG_DIR), &environment, &A::config_profiles()),
+        move || {
+            AppConfig::new_with_options(
+                AppConfigOptions::builder()
+                    .environment(Some(environment.clone()))
+                    .sources(A::config_sources()?)
+                    .profiles(A::config_profiles())
+                    .env_var_prefix(A::config_env_var_prefix())
+                    .build(),
+            )
+        },
+    );
+    context.spawn_cancellable(watcher.run(WATCH_INTERVAL));
+}
+
+/// The config files that are loaded by [AppConfig::new_with_options] from the given
+/// [config_dir][AppConfigOptions::config_dir] for the given [Environment] and
+/// [profiles][AppConfigOptions::profiles].
+pub fn config_file_paths(
+    config_dir: &Path,
+    environment: &Environment,
+    profiles: &[String],
+) -> Vec<PathBuf> {
+    let environment: &str = environment.clone().into();
+    [
+        config_dir.join("default.toml"),
+        config_dir.join(format!("{environment}.toml")),
+    ]
+    .into_iter()
+    .chain(
+        profiles
+            .iter()
+            .map(|profile| config_dir.join(format!("{profile}.toml"))),
+    )
+    .collect()
+}
+
+/// A hash of the contents of each of the files. `None` if a file doesn't exist.
+///
+/// The contents are compared instead of the files' modified times and lengths because an edit
+/// that doesn't change the file's length may not change its modified time either if it happens
+/// within the file system's timestamp resolution. The config files are small, so reading them
+/// on each check is cheap.
+fn contents_hashes(paths: &[PathBuf]) -> Vec<Option<u64>> {
+    paths
+        .iter()
+        .map(|path| {
+            let contents = std::fs::read(path).ok()?;
+            let mut hasher = DefaultHasher::new();
+            contents.hash(&mut hasher);
+            Some(hasher.finish())
+        })
+        .collect()
+}
+
+/// Collect the (dot-separated) keys of the values that differ between `previous` and `current`.
+fn changed_keys(prefix: &str, previous: &Value, current: &Value, changed: &mut Vec<String>) {
+    match (previous, current) {
+        (Value::Object(previous), Value::Object(current)) => {
+            let keys = previous
+                .keys()
+                .chain(current.keys().filter(|key| !previous.contains_key(*key)));
+            for key in keys {
+                let key_path = if prefix.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                changed_keys(
+                    &key_path,
+                    previous.get(key).unwrap_or(&Value::Null),
+                    current.get(key).unwrap_or(&Value::Null),
+                    changed,
+                );
+            }
+        }
+        (previous, current) if previous != current => changed.push(prefix.to_string()),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_util::TempDir;
+    use rstest::rstest;
+    use serde_json::json;
+
+    const CONFIG: &str = r#"
+        environment = "test"
+
+        [app]
+        name = "Test"
+
+        [tracing]
+        level = "debug"
+
+        [auth.jwt]
+        secret = "secret-test"
+    "#;
+
+    #[rstest]
+    #[case("info")]
+    // Same length as the original value, so only the file's contents change
+    #[case::same_length("trace")]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn check(#[case] level: &str) {
+        // Arrange
+        let dir = TempDir::new();
+        let path = dir.write("config.toml", CONFIG);
+        let load = {
+            let path = path.clone();
+            move || AppConfig::test(Some(&std::fs::read_to_string(&path)?))
+        };
+        let mut watcher = ConfigWatcher::new(load().unwrap(), vec![path.clone()], load);
+
+        // Act
+        let unchanged = watcher.check().unwrap();
+        std::fs::write(
+            &path,
+            CONFIG.replace(r#"level = "debug""#, &format!(r#"level = "{level}""#)),
+        )
+        .unwrap();
+        let changed = watcher.check().unwrap();
+
+        // Assert
+        assert!(unchanged.is_empty());
+        assert_eq!(changed, vec!["tracing.level".to_string()]);
+        assert_eq!(watcher.config().tracing.level, level);
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn changed_keys() {
+        // Arrange
+        let previous = json!({"a": {"b": 1, "c": [1, 2]}, "d": "foo"});
+        let current = json!({"a": {"b": 2, "c": [1, 2]}, "e": true});
+
+        // Act
+        let mut changed = Vec::new();
+        super::changed_keys("", &previous, &current, &mut changed);
+
+        // Assert
+        assert_eq!(changed, vec!["a.b", "d", "e"]);
+    }
+}
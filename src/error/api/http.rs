@@ -1,9 +1,16 @@
+use crate::config::service::http::ErrorLogLevel;
 use crate::error::Error;
-use axum::http::StatusCode;
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::header::{CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, ETAG};
+use axum::http::{HeaderValue, StatusCode};
+use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use serde_derive::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use std::sync::OnceLock;
+use tracing::{debug, error, info, trace, warn, Level};
 
 /// Error type representing an HTTP API error. This is generally expected to be returned explicitly
 /// by your application logic.
@@ -135,6 +142,122 @@ impl Display for HttpError {
     }
 }
 
+/// The class of an [HttpError]'s status code. Used to determine the level at which the error is
+/// logged when it's converted to a response; see [ErrorLogLevel].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum StatusClass {
+    /// `4xx` status codes.
+    ClientError,
+    /// `5xx` status codes.
+    ServerError,
+    /// Any other status code.
+    Other,
+}
+
+impl From<StatusCode> for StatusClass {
+    fn from(value: StatusCode) -> Self {
+        if value.is_client_error() {
+            StatusClass::ClientError
+        } else if value.is_server_error() {
+            StatusClass::ServerError
+        } else {
+            StatusClass::Other
+        }
+    }
+}
+
+/// RFC 7807 problem details for an [HttpError]. This is added to the extensions of the
+/// response created from an [HttpError], and is rendered as the response body instead of the
+/// [HttpError] if the app is configured to use the
+/// [ProblemJson][crate::config::service::http::ErrorFormat::ProblemJson] error format.
+/// See: <https://www.rfc-editor.org/rfc/rfc7807>
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ProblemDetails {
+    /// A URI reference that identifies the problem type. Always `about:blank`, which indicates
+    /// that the problem has no additional semantics beyond the HTTP status code.
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// A short summary of the problem type. Because the type is `about:blank`, this is the
+    /// HTTP status code's canonical reason phrase.
+    pub title: String,
+    /// The HTTP status code.
+    pub status: u16,
+    /// An explanation specific to this occurrence of the problem, populated from the
+    /// [HttpError]'s `error` and `details` fields.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// The path of the request that caused the problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+}
+
+impl From<&HttpError> for ProblemDetails {
+    fn from(value: &HttpError) -> Self {
+        let detail = match (value.error.as_ref(), value.details.as_ref()) {
+            (Some(error), Some(details)) => Some(format!("{error}: {details}")),
+            (error, details) => error.or(details).cloned(),
+        };
+        Self {
+            problem_type: "about:blank".to_string(),
+            title: value
+                .status
+                .canonical_reason()
+                .unwrap_or_default()
+                .to_string(),
+            status: value.status.as_u16(),
+            detail,
+            instance: None,
+        }
+    }
+}
+
+/// Middleware function that replaces the body of responses created from an [HttpError] with
+/// the error's [ProblemDetails], including the request's path as the problem's `instance`.
+///
+/// This is installed outside all other middleware, so the response it receives may already have
+/// been modified by other middleware, e.g. compressed. The headers that describe the original
+/// body (`Content-Encoding`, `Content-Length`, and `ETag`) are removed because they don't apply
+/// to the new (uncompressed) body.
+pub(crate) async fn render_problem_details(request: Request, next: Next) -> Response {
+    let instance = request.uri().path().to_string();
+    let response = next.run(request).await;
+    let Some(problem) = response.extensions().get::<ProblemDetails>() else {
+        return response;
+    };
+    let problem = ProblemDetails {
+        instance: Some(instance),
+        ..problem.clone()
+    };
+    let body = match serde_json::to_vec(&problem) {
+        Ok(body) => body,
+        Err(err) => {
+            error!(%err, "Unable to serialize problem details");
+            return response;
+        }
+    };
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(CONTENT_ENCODING);
+    parts.headers.remove(CONTENT_LENGTH);
+    parts.headers.remove(ETAG);
+    parts.headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static("application/problem+json"),
+    );
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// The levels to log [HttpError]s at. This is set from the app's config when the HTTP service is
+/// built; the defaults from [ErrorLogLevel::default] are used if it was not set.
+static ERROR_LOG_LEVEL: OnceLock<ErrorLogLevel> = OnceLock::new();
+
+pub(crate) fn set_error_log_level(error_log_level: ErrorLogLevel) {
+    // Ignore the error if the level was already set, e.g. if multiple HTTP services are built
+    // in the same process.
+    let _ = ERROR_LOG_LEVEL.set(error_log_level);
+}
+
 impl HttpError {
     pub fn new(status: StatusCode) -> Self {
         Self {
@@ -150,6 +273,32 @@ impl HttpError {
         self.into()
     }
 
+    /// The [StatusClass] of the error's status code.
+    pub fn status_class(&self) -> StatusClass {
+        self.status.into()
+    }
+
+    /// Log the error at the level configured for its [StatusClass].
+    fn log(&self) {
+        let error_log_level = ERROR_LOG_LEVEL.get_or_init(Default::default);
+        let level = match self.status_class() {
+            StatusClass::ClientError => error_log_level.client_error,
+            StatusClass::ServerError => error_log_level.server_error,
+            StatusClass::Other => return,
+        };
+        let status = self.status.as_u16();
+        let error = self.error.as_deref();
+        let source = self.source.as_ref().map(|source| source.to_string());
+        let source = source.as_deref();
+        match level {
+            Level::ERROR => error!(status, error, source, "HTTP error"),
+            Level::WARN => warn!(status, error, source, "HTTP error"),
+            Level::INFO => info!(status, error, source, "HTTP error"),
+            Level::DEBUG => debug!(status, error, source, "HTTP error"),
+            _ => trace!(status, error, source, "HTTP error"),
+        }
+    }
+
     pub fn error(self, error: impl ToString) -> Self {
         Self {
             error: Some(error.to_string()),
@@ -198,12 +347,22 @@ impl HttpError {
         Self::new(StatusCode::GONE)
     }
 
+    /// Helper method to create an error with status code [StatusCode::UNPROCESSABLE_ENTITY]
+    pub fn unprocessable_entity() -> Self {
+        Self::new(StatusCode::UNPROCESSABLE_ENTITY)
+    }
+
     // Common 5xx errors
 
     /// Helper method to create an error with status code [StatusCode::INTERNAL_SERVER_ERROR]
     pub fn internal_server_error() -> Self {
         Self::new(StatusCode::INTERNAL_SERVER_ERROR)
     }
+
+    /// Helper method to create an error with status code [StatusCode::GATEWAY_TIMEOUT]
+    pub fn gateway_timeout() -> Self {
+        Self::new(StatusCode::GATEWAY_TIMEOUT)
+    }
 }
 
 impl From<StatusCode> for HttpError {
@@ -220,9 +379,97 @@ impl From<StatusCode> for Error {
 
 impl IntoResponse for HttpError {
     fn into_response(self) -> Response {
+        self.log();
         let status = self.status;
+        let problem = ProblemDetails::from(&self);
         let mut res = Json(self).into_response();
         *res.status_mut() = status;
+        res.extensions_mut().insert(problem);
         res
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::tracing::capture_events;
+    use axum::body::to_bytes;
+    use axum::routing::get;
+    use axum::Router;
+    use rstest::rstest;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    #[rstest]
+    #[case(StatusCode::OK, StatusClass::Other)]
+    #[case(StatusCode::BAD_REQUEST, StatusClass::ClientError)]
+    #[case(StatusCode::NOT_FOUND, StatusClass::ClientError)]
+    #[case(StatusCode::INTERNAL_SERVER_ERROR, StatusClass::ServerError)]
+    #[case(StatusCode::GATEWAY_TIMEOUT, StatusClass::ServerError)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn status_class(#[case] status: StatusCode, #[case] expected: StatusClass) {
+        assert_eq!(HttpError::new(status).status_class(), expected);
+    }
+
+    #[rstest]
+    #[case(HttpError::bad_request(), Some(Level::WARN))]
+    #[case(HttpError::internal_server_error(), Some(Level::ERROR))]
+    #[case(HttpError::new(StatusCode::OK), None)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn into_response_log_level(#[case] err: HttpError, #[case] expected_level: Option<Level>) {
+        // Arrange
+        let (events, _guard) = capture_events();
+
+        // Act
+        let _response = err.error("Something went wrong").into_response();
+
+        // Assert
+        let events = events.with_message("HTTP error");
+        assert_eq!(events.first().map(|event| event.level), expected_level);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn render_problem_details() {
+        // Arrange
+        let router = Router::new()
+            .route(
+                "/foo",
+                get(|| async {
+                    HttpError::unprocessable_entity()
+                        .error("Invalid request")
+                        .details("Field 'A' is missing")
+                }),
+            )
+            .layer(axum::middleware::from_fn(super::render_problem_details));
+
+        // Act
+        let response = router
+            .oneshot(
+                axum::http::Request::get("/foo")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/problem+json"
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "type": "about:blank",
+                "title": "Unprocessable Entity",
+                "status": 422,
+                "detail": "Invalid request: Field 'A' is missing",
+                "instance": "/foo",
+            })
+        );
+    }
+}
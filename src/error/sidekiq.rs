@@ -12,10 +12,57 @@ pub enum SidekiqError {
     #[error(transparent)]
     Bb8(#[from] bb8::RunError<sidekiq::RedisError>),
 
+    #[error(transparent)]
+    Enqueue(#[from] EnqueueError),
+
     #[error(transparent)]
     Other(#[from] Box<dyn std::error::Error + Send + Sync>),
 }
 
+/// Error returned when a job could not be enqueued, e.g. via
+/// [AppWorker::enqueue][crate::service::worker::sidekiq::app_worker::AppWorker::enqueue].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum EnqueueError {
+    /// The queue backend (e.g., Redis) returned an error. If `transient` is `true`, the error was
+    /// caused by a connection or pool issue that may resolve on its own, so the caller may want
+    /// to try again later (e.g., respond with `503 Service Unavailable`).
+    #[error("Unable to enqueue job (transient: {transient}): {source}")]
+    Backend {
+        transient: bool,
+        #[source]
+        source: sidekiq::Error,
+    },
+}
+
+impl From<sidekiq::Error> for EnqueueError {
+    fn from(value: sidekiq::Error) -> Self {
+        let transient = match &value {
+            sidekiq::Error::BB8(bb8::RunError::TimedOut) => true,
+            sidekiq::Error::BB8(bb8::RunError::User(err)) => transient_redis_error(err),
+            sidekiq::Error::Redis(err) => transient_redis_error(err),
+            _ => false,
+        };
+        Self::Backend {
+            transient,
+            source: value,
+        }
+    }
+}
+
+fn transient_redis_error(err: &sidekiq::RedisError) -> bool {
+    err.is_timeout()
+        || err.is_io_error()
+        || err.is_connection_dropped()
+        || err.is_connection_refusal()
+}
+
+impl From<EnqueueError> for Error {
+    fn from(value: EnqueueError) -> Self {
+        Self::Sidekiq(SidekiqError::from(value))
+    }
+}
+
 impl From<sidekiq::Error> for Error {
     fn from(value: sidekiq::Error) -> Self {
         Self::Sidekiq(SidekiqError::from(value))
@@ -6,6 +6,9 @@ pub enum TonicError {
     #[error(transparent)]
     Transport(#[from] tonic::transport::Error),
 
+    #[error(transparent)]
+    Reflection(#[from] tonic_reflection::server::Error),
+
     #[error(transparent)]
     Other(#[from] Box<dyn std::error::Error + Send + Sync>),
 }
@@ -15,3 +18,9 @@ impl From<tonic::transport::Error> for Error {
         Self::Tonic(TonicError::from(value))
     }
 }
+
+impl From<tonic_reflection::server::Error> for Error {
+    fn from(value: tonic_reflection::server::Error) -> Self {
+        Self::Tonic(TonicError::from(value))
+    }
+}
@@ -7,6 +7,10 @@ pub enum TracingError {
     #[error(transparent)]
     Init(#[from] TracingInitError),
 
+    /// An error that occurs when reloading the trace filter at runtime.
+    #[error(transparent)]
+    Reload(#[from] tracing_subscriber::reload::Error),
+
     #[error(transparent)]
     Other(#[from] Box<dyn std::error::Error + Send + Sync>),
 }
@@ -35,6 +39,12 @@ pub enum TracingInitError {
     Init(#[from] tracing_subscriber::util::TryInitError),
 }
 
+impl From<tracing_subscriber::reload::Error> for Error {
+    fn from(value: tracing_subscriber::reload::Error) -> Self {
+        Self::Tracing(TracingError::from(value))
+    }
+}
+
 #[cfg(feature = "otel")]
 impl From<opentelemetry::trace::TraceError> for Error {
     fn from(value: opentelemetry::trace::TraceError) -> Self {
@@ -0,0 +1,184 @@
+use crate::app::context::AppContext;
+use crate::error::RoadsterResult;
+use crate::health_check::{CheckResponse, ErrorData, HealthCheck, Status};
+use async_trait::async_trait;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::debug;
+
+/// Wrapper around a [HealthCheck] that returned a [HealthCheck::cache_interval]. The wrapped check
+/// is run in the background on the configured interval, and the most recent result is returned
+/// from [HealthCheck::check] as long as it's not older than [HealthCheck::cache_max_age]. If
+/// there is no result yet, or the result is too old, the wrapped check is run directly instead.
+pub(crate) struct CachedHealthCheck {
+    inner: Arc<dyn HealthCheck>,
+    interval: Duration,
+    max_age: Duration,
+    cached: RwLock<Option<(Instant, CheckResponse)>>,
+}
+
+impl CachedHealthCheck {
+    pub(crate) fn new(inner: Arc<dyn HealthCheck>, interval: Duration) -> Self {
+        let max_age = inner.cache_max_age().unwrap_or(interval * 2);
+        Self {
+            inner,
+            interval,
+            max_age,
+            cached: RwLock::new(None),
+        }
+    }
+
+    /// Run the wrapped check on the configured interval until the app starts shutting down.
+    pub(crate) fn run_in_background(self: &Arc<Self>, context: &AppContext) {
+        let check = self.clone();
+        context.spawn_cancellable(async move {
+            let mut interval = tokio::time::interval(check.interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                check.refresh().await;
+            }
+        });
+    }
+
+    async fn refresh(&self) {
+        let name = self.inner.name();
+        debug!(name=%name, "Running cached check");
+        let timer = Instant::now();
+        let response = match self.inner.check().await {
+            Ok(response) => response,
+            Err(err) => CheckResponse::builder()
+                .status(Status::Err(
+                    ErrorData::builder()
+                        .msg(format!(
+                            "An error occurred while running health check `{name}`: {err}"
+                        ))
+                        .build(),
+                ))
+                .latency(timer.elapsed())
+                .build(),
+        };
+        if let Ok(mut cached) = self.cached.write() {
+            *cached = Some((Instant::now(), response));
+        }
+    }
+
+    fn cached(&self) -> Option<CheckResponse> {
+        let cached = self.cached.read().ok()?;
+        cached
+            .as_ref()
+            .filter(|(updated_at, _)| updated_at.elapsed() <= self.max_age)
+            .map(|(_, response)| response.clone())
+    }
+}
+
+#[async_trait]
+impl HealthCheck for CachedHealthCheck {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn enabled(&self) -> bool {
+        self.inner.enabled()
+    }
+
+    fn cache_interval(&self) -> Option<Duration> {
+        Some(self.interval)
+    }
+
+    fn cache_max_age(&self) -> Option<Duration> {
+        Some(self.max_age)
+    }
+
+    async fn check(&self) -> RoadsterResult<CheckResponse> {
+        if let Some(response) = self.cached() {
+            return Ok(response);
+        }
+        self.inner.check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct CountingCheck {
+        count: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl HealthCheck for CountingCheck {
+        fn name(&self) -> String {
+            "counting".to_string()
+        }
+
+        fn enabled(&self) -> bool {
+            true
+        }
+
+        async fn check(&self) -> RoadsterResult<CheckResponse> {
+            let count = self.count.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(CheckResponse::builder()
+                .status(Status::Ok)
+                .latency(Duration::from_millis(count as u64))
+                .build())
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn cached_check() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let count = Arc::new(AtomicU32::new(0));
+        let check = Arc::new(CachedHealthCheck::new(
+            Arc::new(CountingCheck {
+                count: count.clone(),
+            }),
+            Duration::from_millis(200),
+        ));
+
+        // Act
+        check.run_in_background(&context);
+        // Let the background task run the check for the first time
+        tokio::task::yield_now().await;
+        tokio::time::advance(Duration::from_millis(50)).await;
+        let first = check.check().await.unwrap();
+        let second = check.check().await.unwrap();
+        // Advance past the next interval and let the background task refresh the cached result
+        tokio::time::advance(Duration::from_millis(200)).await;
+        tokio::task::yield_now().await;
+        let third = check.check().await.unwrap();
+
+        // Assert
+        // The first result was served from the cache, and the check only ran on its interval
+        assert_eq!(first.latency, 1);
+        assert_eq!(second.latency, 1);
+        assert_eq!(third.latency, 2);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        context.cancellation_token().cancel();
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn cached_check_not_started() {
+        // Arrange
+        let count = Arc::new(AtomicU32::new(0));
+        let check = CachedHealthCheck::new(
+            Arc::new(CountingCheck {
+                count: count.clone(),
+            }),
+            Duration::from_secs(60),
+        );
+
+        // Act
+        check.check().await.unwrap();
+        check.check().await.unwrap();
+
+        // Assert
+        // Without a cached result, the check is run directly each time
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+}
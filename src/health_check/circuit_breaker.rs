@@ -0,0 +1,178 @@
+use crate::config::health_check::CircuitBreaker;
+use crate::error::RoadsterResult;
+use crate::health_check::{CheckResponse, ErrorData, HealthCheck, Status};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Wrapper around a [HealthCheck] that returned a [HealthCheck::circuit_breaker]. The reported
+/// status of the wrapped check only changes after it returns the opposite status for the
+/// configured number of consecutive runs, which smooths out transient blips of a flapping
+/// resource. Until then, the most recent response with the previously reported status is
+/// returned instead.
+pub(crate) struct CircuitBreakerHealthCheck {
+    inner: Arc<dyn HealthCheck>,
+    policy: CircuitBreaker,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Whether the check is currently reported as healthy. `None` until the check runs for the
+    /// first time, in which case the first result is reported as-is.
+    healthy: Option<bool>,
+    successes: u32,
+    failures: u32,
+    /// The most recent response that matches the currently reported status.
+    last: Option<CheckResponse>,
+}
+
+impl CircuitBreakerHealthCheck {
+    pub(crate) fn new(inner: Arc<dyn HealthCheck>, policy: CircuitBreaker) -> Self {
+        Self {
+            inner,
+            policy,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Record the `response` of the wrapped check and return the response to report.
+    fn record(&self, response: CheckResponse) -> CheckResponse {
+        let Ok(mut state) = self.state.lock() else {
+            return response;
+        };
+        let ok = matches!(response.status, Status::Ok);
+        if ok {
+            state.successes += 1;
+            state.failures = 0;
+        } else {
+            state.failures += 1;
+            state.successes = 0;
+        }
+
+        let healthy = match state.healthy {
+            None => ok,
+            Some(true) if state.failures >= self.policy.failure_threshold.max(1) => false,
+            Some(false) if state.successes >= self.policy.success_threshold.max(1) => true,
+            Some(healthy) => healthy,
+        };
+        if state.healthy.is_some_and(|previous| previous != healthy) {
+            info!(name=%self.inner.name(), %healthy, "Health check status changed");
+        }
+        state.healthy = Some(healthy);
+
+        if ok == healthy {
+            state.last = Some(response.clone());
+            response
+        } else {
+            state.last.clone().unwrap_or(response)
+        }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for CircuitBreakerHealthCheck {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn enabled(&self) -> bool {
+        self.inner.enabled()
+    }
+
+    fn cache_interval(&self) -> Option<Duration> {
+        self.inner.cache_interval()
+    }
+
+    fn cache_max_age(&self) -> Option<Duration> {
+        self.inner.cache_max_age()
+    }
+
+    fn circuit_breaker(&self) -> Option<CircuitBreaker> {
+        Some(self.policy.clone())
+    }
+
+    async fn check(&self) -> RoadsterResult<CheckResponse> {
+        let timer = Instant::now();
+        let response = match self.inner.check().await {
+            Ok(response) => response,
+            Err(err) => CheckResponse::builder()
+                .status(Status::Err(
+                    ErrorData::builder()
+                        .msg(format!(
+                            "An error occurred while running health check `{}`: {err}",
+                            self.inner.name()
+                        ))
+                        .build(),
+                ))
+                .latency(timer.elapsed())
+                .build(),
+        };
+        Ok(self.record(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    struct SequenceCheck {
+        results: Mutex<VecDeque<bool>>,
+    }
+
+    #[async_trait]
+    impl HealthCheck for SequenceCheck {
+        fn name(&self) -> String {
+            "sequence".to_string()
+        }
+
+        fn enabled(&self) -> bool {
+            true
+        }
+
+        async fn check(&self) -> RoadsterResult<CheckResponse> {
+            let ok = self.results.lock().unwrap().pop_front().unwrap();
+            let status = if ok {
+                Status::Ok
+            } else {
+                Status::Err(ErrorData::builder().build())
+            };
+            Ok(CheckResponse::builder()
+                .status(status)
+                .latency(Duration::from_millis(1))
+                .build())
+        }
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn circuit_breaker() {
+        // Arrange
+        let results = [true, false, true, false, false, true, false, true, true];
+        let check = CircuitBreakerHealthCheck::new(
+            Arc::new(SequenceCheck {
+                results: Mutex::new(results.into_iter().collect()),
+            }),
+            CircuitBreaker {
+                failure_threshold: 2,
+                success_threshold: 2,
+            },
+        );
+
+        // Act
+        let mut reported = Vec::new();
+        for _ in results {
+            let response = check.check().await.unwrap();
+            reported.push(matches!(response.status, Status::Ok));
+        }
+
+        // Assert
+        // The reported status only flips after 2 consecutive results with the opposite status
+        assert_eq!(
+            reported,
+            vec![true, true, true, true, false, false, false, false, true]
+        );
+    }
+}
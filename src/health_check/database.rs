@@ -1,8 +1,10 @@
 use crate::api::core::health::db_health;
 use crate::app::context::AppContext;
+use crate::config::health_check::CircuitBreaker;
 use crate::error::RoadsterResult;
 use crate::health_check::{CheckResponse, HealthCheck};
 use async_trait::async_trait;
+use std::time::Duration;
 use tracing::instrument;
 
 pub struct DatabaseHealthCheck {
@@ -19,6 +21,25 @@ impl HealthCheck for DatabaseHealthCheck {
         enabled(&self.context)
     }
 
+    fn cache_interval(&self) -> Option<Duration> {
+        self.context
+            .config()
+            .health_check
+            .database
+            .common
+            .cache_interval
+    }
+
+    fn circuit_breaker(&self) -> Option<CircuitBreaker> {
+        self.context
+            .config()
+            .health_check
+            .database
+            .common
+            .circuit_breaker
+            .clone()
+    }
+
     #[instrument(skip_all)]
     async fn check(&self) -> RoadsterResult<CheckResponse> {
         Ok(db_health(&self.context, None).await)
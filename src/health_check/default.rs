@@ -1,6 +1,10 @@
 use crate::app::context::AppContext;
 #[cfg(feature = "db-sql")]
 use crate::health_check::database::DatabaseHealthCheck;
+#[cfg(feature = "system-health-check")]
+use crate::health_check::disk_space::DiskSpaceHealthCheck;
+#[cfg(feature = "system-health-check")]
+use crate::health_check::memory::MemoryHealthCheck;
 #[cfg(feature = "sidekiq")]
 use crate::health_check::sidekiq_enqueue::SidekiqEnqueueHealthCheck;
 #[cfg(feature = "sidekiq")]
@@ -25,6 +29,14 @@ pub fn default_health_checks(
         Arc::new(SidekiqFetchHealthCheck {
             context: context.clone(),
         }),
+        #[cfg(feature = "system-health-check")]
+        Arc::new(DiskSpaceHealthCheck {
+            context: context.clone(),
+        }),
+        #[cfg(feature = "system-health-check")]
+        Arc::new(MemoryHealthCheck {
+            context: context.clone(),
+        }),
     ];
     health_checks
         .into_iter()
@@ -0,0 +1,204 @@
+use crate::app::context::AppContext;
+use crate::config::health_check::{CircuitBreaker, DiskSpaceHealthCheckConfig};
+use crate::error::RoadsterResult;
+use crate::health_check::{CheckResponse, ErrorData, HealthCheck, Status};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use itertools::Itertools;
+use serde_derive::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use sysinfo::Disks;
+use tracing::instrument;
+
+/// Health check that fails if any of the configured paths are on a disk that has less than the
+/// configured minimum amount of free space.
+pub struct DiskSpaceHealthCheck {
+    pub(crate) context: AppContext,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiskSpaceData {
+    /// The minimum amount of free space required on each disk, in bytes.
+    min_free: u64,
+    disks: Vec<DiskSpace>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiskSpace {
+    path: PathBuf,
+    /// The amount of free space on the disk that contains `path`, in bytes.
+    free: u64,
+}
+
+#[async_trait]
+impl HealthCheck for DiskSpaceHealthCheck {
+    fn name(&self) -> String {
+        "disk-space".to_string()
+    }
+
+    fn enabled(&self) -> bool {
+        enabled(&self.context)
+    }
+
+    fn cache_interval(&self) -> Option<Duration> {
+        self.context
+            .config()
+            .health_check
+            .disk_space
+            .common
+            .cache_interval
+    }
+
+    fn circuit_breaker(&self) -> Option<CircuitBreaker> {
+        self.context
+            .config()
+            .health_check
+            .disk_space
+            .common
+            .circuit_breaker
+            .clone()
+    }
+
+    #[instrument(skip_all)]
+    async fn check(&self) -> RoadsterResult<CheckResponse> {
+        let config = &self.context.config().health_check.disk_space.custom;
+        Ok(disk_space_health(config))
+    }
+}
+
+fn enabled(context: &AppContext) -> bool {
+    context
+        .config()
+        .health_check
+        .disk_space
+        .common
+        .enabled(context)
+}
+
+fn disk_space_health(config: &DiskSpaceHealthCheckConfig) -> CheckResponse {
+    let timer = Instant::now();
+    let min_free = config.min_free.as_u64();
+    let disks = Disks::new_with_refreshed_list();
+
+    let (disks, errors): (Vec<DiskSpace>, Vec<anyhow::Error>) = config
+        .paths
+        .iter()
+        .map(|path| -> anyhow::Result<DiskSpace> {
+            let free = free_space(&disks, path)?;
+            Ok(DiskSpace {
+                path: path.clone(),
+                free,
+            })
+        })
+        .partition_result();
+
+    let low_disks = disks
+        .iter()
+        .filter(|disk| disk.free < min_free)
+        .map(|disk| {
+            format!(
+                "Disk containing `{}` has {} bytes free, which is less than the minimum of {} bytes",
+                disk.path.display(),
+                disk.free,
+                min_free
+            )
+        });
+    let errors = errors
+        .into_iter()
+        .map(|err| err.to_string())
+        .chain(low_disks)
+        .collect_vec();
+
+    let status = if errors.is_empty() {
+        Status::Ok
+    } else {
+        Status::Err(ErrorData::builder().msg(errors.join("; ")).build())
+    };
+
+    CheckResponse::builder()
+        .status(status)
+        .latency(timer.elapsed())
+        .custom(DiskSpaceData { min_free, disks })
+        .build()
+}
+
+/// Get the free space of the disk that contains the given path, i.e., the disk with the longest
+/// mount point that is a prefix of the path.
+fn free_space(disks: &Disks, path: &Path) -> anyhow::Result<u64> {
+    let canonical_path = path
+        .canonicalize()
+        .map_err(|err| anyhow!("Unable to resolve path `{}`: {err}", path.display()))?;
+    disks
+        .list()
+        .iter()
+        .filter(|disk| canonical_path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+        .ok_or_else(|| anyhow!("Unable to find disk containing `{}`", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::app_config::AppConfig;
+    use byte_unit::Byte;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(false, Some(true), true)]
+    #[case(false, Some(false), false)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn enabled(
+        #[case] default_enable: bool,
+        #[case] enable: Option<bool>,
+        #[case] expected_enabled: bool,
+    ) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.health_check.default_enable = default_enable;
+        config.health_check.disk_space.common.enable = enable;
+
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        // Act/Assert
+        assert_eq!(super::enabled(&context), expected_enabled);
+    }
+
+    #[rstest]
+    #[case(0, true)]
+    #[case(u64::MAX, false)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn check(#[case] min_free: u64, #[case] expected_ok: bool) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.health_check.disk_space.custom.min_free = Byte::from_u64(min_free);
+
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let check = DiskSpaceHealthCheck { context };
+
+        // Act
+        let response = check.check().await.unwrap();
+
+        // Assert
+        assert_eq!(matches!(response.status, Status::Ok), expected_ok);
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn missing_path() {
+        // Arrange
+        let mut config = DiskSpaceHealthCheckConfig::default();
+        config.paths = vec![PathBuf::from("/roadster-missing-path")];
+        config.min_free = Byte::from_u64(0);
+
+        // Act
+        let response = disk_space_health(&config);
+
+        // Assert
+        assert!(matches!(response.status, Status::Err(_)));
+    }
+}
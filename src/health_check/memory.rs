@@ -0,0 +1,139 @@
+use crate::app::context::AppContext;
+use crate::config::health_check::{CircuitBreaker, MemoryHealthCheckConfig};
+use crate::error::RoadsterResult;
+use crate::health_check::{CheckResponse, ErrorData, HealthCheck, Status};
+use async_trait::async_trait;
+use serde_derive::Serialize;
+use std::time::{Duration, Instant};
+use sysinfo::System;
+use tracing::instrument;
+
+/// Health check that fails if the host has less than the configured minimum amount of available
+/// memory.
+pub struct MemoryHealthCheck {
+    pub(crate) context: AppContext,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MemoryData {
+    /// The minimum amount of available memory required, in bytes.
+    min_free: u64,
+    /// The amount of memory available on the host, in bytes.
+    free: u64,
+}
+
+#[async_trait]
+impl HealthCheck for MemoryHealthCheck {
+    fn name(&self) -> String {
+        "memory".to_string()
+    }
+
+    fn enabled(&self) -> bool {
+        enabled(&self.context)
+    }
+
+    fn cache_interval(&self) -> Option<Duration> {
+        self.context
+            .config()
+            .health_check
+            .memory
+            .common
+            .cache_interval
+    }
+
+    fn circuit_breaker(&self) -> Option<CircuitBreaker> {
+        self.context
+            .config()
+            .health_check
+            .memory
+            .common
+            .circuit_breaker
+            .clone()
+    }
+
+    #[instrument(skip_all)]
+    async fn check(&self) -> RoadsterResult<CheckResponse> {
+        let config = &self.context.config().health_check.memory.custom;
+        Ok(memory_health(config))
+    }
+}
+
+fn enabled(context: &AppContext) -> bool {
+    context.config().health_check.memory.common.enabled(context)
+}
+
+fn memory_health(config: &MemoryHealthCheckConfig) -> CheckResponse {
+    let timer = Instant::now();
+    let min_free = config.min_free.as_u64();
+
+    let mut system = System::new();
+    system.refresh_memory();
+    let free = system.available_memory();
+
+    let status = if free < min_free {
+        Status::Err(
+            ErrorData::builder()
+                .msg(format!(
+                    "Host has {free} bytes of memory available, which is less than the minimum of {min_free} bytes"
+                ))
+                .build(),
+        )
+    } else {
+        Status::Ok
+    };
+
+    CheckResponse::builder()
+        .status(status)
+        .latency(timer.elapsed())
+        .custom(MemoryData { min_free, free })
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::app_config::AppConfig;
+    use byte_unit::Byte;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(false, Some(true), true)]
+    #[case(false, Some(false), false)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn enabled(
+        #[case] default_enable: bool,
+        #[case] enable: Option<bool>,
+        #[case] expected_enabled: bool,
+    ) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.health_check.default_enable = default_enable;
+        config.health_check.memory.common.enable = enable;
+
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        // Act/Assert
+        assert_eq!(super::enabled(&context), expected_enabled);
+    }
+
+    #[rstest]
+    #[case(0, true)]
+    #[case(u64::MAX, false)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn check(#[case] min_free: u64, #[case] expected_ok: bool) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.health_check.memory.custom.min_free = Byte::from_u64(min_free);
+
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let check = MemoryHealthCheck { context };
+
+        // Act
+        let response = check.check().await.unwrap();
+
+        // Assert
+        assert_eq!(matches!(response.status, Status::Ok), expected_ok);
+    }
+}
@@ -1,12 +1,19 @@
+pub(crate) mod cached;
+pub(crate) mod circuit_breaker;
 #[cfg(feature = "db-sql")]
 pub mod database;
 pub mod default;
+#[cfg(feature = "system-health-check")]
+pub mod disk_space;
+#[cfg(feature = "system-health-check")]
+pub mod memory;
 pub mod registry;
 #[cfg(feature = "sidekiq")]
 pub mod sidekiq_enqueue;
 #[cfg(feature = "sidekiq")]
 pub mod sidekiq_fetch;
 
+use crate::config::health_check::CircuitBreaker;
 use crate::error::RoadsterResult;
 use async_trait::async_trait;
 #[cfg(feature = "open-api")]
@@ -14,6 +21,7 @@ use schemars::JsonSchema;
 use serde_derive::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_with::{serde_as, skip_serializing_none};
+use std::time::Duration;
 use typed_builder::TypedBuilder;
 
 #[serde_as]
@@ -82,6 +90,39 @@ pub trait HealthCheck: Send + Sync {
     /// and directly call `HealthCheck#check` even if `HealthCheck#enabled` returns `false`.
     fn enabled(&self) -> bool;
 
+    /// If provided, Roadster will run the health check in the background on this interval, and
+    /// the most recent result will be returned when the health check is run from the `_health`
+    /// endpoint (or anywhere else the app's health checks are run). This is useful for expensive
+    /// checks, e.g. to avoid a DB round-trip every time a load balancer checks the app's health.
+    ///
+    /// Note: The background runs start when the app's services start. Until the first result is
+    /// available, the health check is run directly.
+    ///
+    /// For Roadster's default health checks, this can be set via config, e.g.
+    /// `health-check.database.cache-interval = 10` (in seconds).
+    fn cache_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// The maximum age of a cached result (see [HealthCheck::cache_interval]) before the health
+    /// check is run directly instead of returning the cached result. Defaults to twice the
+    /// `cache_interval`. Not used if [HealthCheck::cache_interval] is `None`.
+    fn cache_max_age(&self) -> Option<Duration> {
+        None
+    }
+
+    /// If provided, the reported status of the health check only changes after the check returns
+    /// the opposite status for [CircuitBreaker::failure_threshold] (healthy to unhealthy) or
+    /// [CircuitBreaker::success_threshold] (unhealthy to healthy) consecutive runs. Until then, the
+    /// most recent result with the previous status is returned. This is useful to avoid a single
+    /// transient failure of a flapping resource taking the app out of a load balancer's rotation.
+    ///
+    /// Note: If [HealthCheck::cache_interval] is also provided, the thresholds apply to the
+    /// background runs of the health check.
+    fn circuit_breaker(&self) -> Option<CircuitBreaker> {
+        None
+    }
+
     /// Run the health check.
     async fn check(&self) -> RoadsterResult<CheckResponse>;
 }
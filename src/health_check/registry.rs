@@ -1,5 +1,7 @@
 use crate::app::context::AppContext;
 use crate::error::RoadsterResult;
+use crate::health_check::cached::CachedHealthCheck;
+use crate::health_check::circuit_breaker::CircuitBreakerHealthCheck;
 use crate::health_check::default::default_health_checks;
 use crate::health_check::HealthCheck;
 use anyhow::anyhow;
@@ -15,13 +17,20 @@ use tracing::info;
 ///    and the health CLI command.
 pub struct HealthCheckRegistry {
     health_checks: BTreeMap<String, Arc<dyn HealthCheck>>,
+    cached_checks: BTreeMap<String, Arc<CachedHealthCheck>>,
 }
 
 impl HealthCheckRegistry {
     pub(crate) fn new(context: &AppContext) -> Self {
-        Self {
-            health_checks: default_health_checks(context),
+        let mut registry = Self {
+            health_checks: Default::default(),
+            cached_checks: Default::default(),
+        };
+        for (name, check) in default_health_checks(context) {
+            let check = registry.wrap(&name, check);
+            registry.health_checks.insert(name, check);
         }
+        registry
     }
 
     pub fn register<H>(&mut self, health_check: H) -> RoadsterResult<()>
@@ -37,9 +46,11 @@ impl HealthCheckRegistry {
 
         info!(name=%name, "Registering health check");
 
+        let health_check = self.wrap(&name, Arc::new(health_check));
+
         if self
             .health_checks
-            .insert(name.clone(), Arc::new(health_check))
+            .insert(name.clone(), health_check)
             .is_some()
         {
             return Err(anyhow!("Health check `{}` was already registered", name).into());
@@ -47,9 +58,54 @@ impl HealthCheckRegistry {
         Ok(())
     }
 
+    /// Remove a [HealthCheck] from the registry. This is mainly useful to remove one of the
+    /// default health checks that Roadster registers automatically, e.g., to replace it with a
+    /// custom health check with the same name. Returns the removed [HealthCheck], if any.
+    ///
+    /// Note: default health checks can also be disabled via config, e.g.
+    /// `health-check.database.enable = false`.
+    pub fn remove(&mut self, name: &str) -> Option<Arc<dyn HealthCheck>> {
+        let health_check = self.health_checks.remove(name);
+        self.cached_checks.remove(name);
+        if health_check.is_some() {
+            info!(name=%name, "Removed health check");
+        }
+        health_check
+    }
+
     pub fn checks(&self) -> Vec<Arc<dyn HealthCheck>> {
         self.health_checks.values().cloned().collect()
     }
+
+    /// The registered [HealthCheck]s that provided a [HealthCheck::cache_interval] and need to be
+    /// run in the background.
+    pub(crate) fn cached_checks(&self) -> Vec<Arc<CachedHealthCheck>> {
+        self.cached_checks.values().cloned().collect()
+    }
+
+    /// Wrap the [HealthCheck] in a [CircuitBreakerHealthCheck] and/or a [CachedHealthCheck]
+    /// depending on the policies it provided. Cached checks are also added to the cached checks
+    /// so they can be run in the background.
+    fn wrap(&mut self, name: &str, health_check: Arc<dyn HealthCheck>) -> Arc<dyn HealthCheck> {
+        let health_check = with_circuit_breaker(health_check);
+        if let Some(interval) = health_check.cache_interval() {
+            let cached = Arc::new(CachedHealthCheck::new(health_check, interval));
+            self.cached_checks.insert(name.to_string(), cached.clone());
+            cached
+        } else {
+            health_check
+        }
+    }
+}
+
+/// Wrap the [HealthCheck] in a [CircuitBreakerHealthCheck] if it provided a
+/// [HealthCheck::circuit_breaker].
+fn with_circuit_breaker(health_check: Arc<dyn HealthCheck>) -> Arc<dyn HealthCheck> {
+    if let Some(policy) = health_check.circuit_breaker() {
+        Arc::new(CircuitBreakerHealthCheck::new(health_check, policy))
+    } else {
+        health_check
+    }
 }
 
 #[cfg(test)]
@@ -58,6 +114,7 @@ mod tests {
     use crate::config::app_config::AppConfig;
     use crate::health_check::MockHealthCheck;
     use rstest::rstest;
+    use std::time::Duration;
 
     #[rstest]
     #[case(true, 1)]
@@ -72,6 +129,8 @@ mod tests {
         let mut check: MockHealthCheck = MockHealthCheck::default();
         check.expect_enabled().return_const(check_enabled);
         check.expect_name().return_const("test".to_string());
+        check.expect_cache_interval().return_const(None);
+        check.expect_circuit_breaker().return_const(None);
 
         // Act
         let mut subject: HealthCheckRegistry = HealthCheckRegistry::new(&context);
@@ -84,4 +143,119 @@ mod tests {
             check_enabled
         );
     }
+
+    #[rstest]
+    #[case(None, 0)]
+    #[case(Some(Duration::from_secs(10)), 1)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn register_cached_check(
+        #[case] cache_interval: Option<Duration>,
+        #[case] expected_count: usize,
+    ) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.health_check.default_enable = false;
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        let mut check: MockHealthCheck = MockHealthCheck::default();
+        check.expect_enabled().return_const(true);
+        check.expect_name().return_const("test".to_string());
+        check.expect_cache_interval().return_const(cache_interval);
+        check.expect_circuit_breaker().return_const(None);
+        check.expect_cache_max_age().return_const(None);
+
+        // Act
+        let mut subject: HealthCheckRegistry = HealthCheckRegistry::new(&context);
+        subject.register(check).unwrap();
+
+        // Assert
+        assert_eq!(subject.checks().len(), 1);
+        assert_eq!(subject.cached_checks().len(), expected_count);
+    }
+
+    #[rstest]
+    #[case(None, 0)]
+    #[case(Some(Duration::from_secs(10)), 1)]
+    #[cfg(feature = "system-health-check")]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn default_cached_check(
+        #[case] cache_interval: Option<Duration>,
+        #[case] expected_count: usize,
+    ) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.health_check.default_enable = false;
+        config.health_check.memory.common.enable = Some(true);
+        config.health_check.memory.common.cache_interval = cache_interval;
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        // Act
+        let subject: HealthCheckRegistry = HealthCheckRegistry::new(&context);
+
+        // Assert
+        assert_eq!(subject.checks().len(), 1);
+        assert_eq!(subject.cached_checks().len(), expected_count);
+    }
+
+    #[test]
+    #[cfg(feature = "db-sql")]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn remove_default_check() {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.health_check.default_enable = false;
+        config.health_check.database.common.enable = Some(true);
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let mut subject: HealthCheckRegistry = HealthCheckRegistry::new(&context);
+        assert!(subject.checks().iter().any(|check| check.name() == "db"));
+
+        // Act
+        let removed = subject.remove("db");
+
+        // Assert
+        assert!(removed.is_some());
+        assert!(subject.checks().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "db-sql")]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn remove_default_check_and_register_custom() {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.health_check.default_enable = false;
+        config.health_check.database.common.enable = Some(true);
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let mut subject: HealthCheckRegistry = HealthCheckRegistry::new(&context);
+
+        let mut check: MockHealthCheck = MockHealthCheck::default();
+        check.expect_enabled().return_const(true);
+        check.expect_name().return_const("db".to_string());
+        check.expect_cache_interval().return_const(None);
+        check.expect_circuit_breaker().return_const(None);
+
+        // Act
+        subject.remove("db");
+        subject.register(check).unwrap();
+
+        // Assert
+        assert_eq!(subject.checks().len(), 1);
+        assert!(subject.checks().iter().any(|check| check.name() == "db"));
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn remove_missing_check() {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.health_check.default_enable = false;
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let mut subject: HealthCheckRegistry = HealthCheckRegistry::new(&context);
+
+        // Act
+        let removed = subject.remove("missing");
+
+        // Assert
+        assert!(removed.is_none());
+    }
 }
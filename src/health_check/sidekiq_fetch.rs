@@ -1,9 +1,11 @@
 use crate::api::core::health::redis_health;
 use crate::app::context::AppContext;
+use crate::config::health_check::CircuitBreaker;
 use crate::error::RoadsterResult;
 use crate::health_check::{CheckResponse, HealthCheck};
 use anyhow::anyhow;
 use async_trait::async_trait;
+use std::time::Duration;
 use tracing::instrument;
 
 pub struct SidekiqFetchHealthCheck {
@@ -20,6 +22,25 @@ impl HealthCheck for SidekiqFetchHealthCheck {
         enabled(&self.context)
     }
 
+    fn cache_interval(&self) -> Option<Duration> {
+        self.context
+            .config()
+            .health_check
+            .sidekiq
+            .common
+            .cache_interval
+    }
+
+    fn circuit_breaker(&self) -> Option<CircuitBreaker> {
+        self.context
+            .config()
+            .health_check
+            .sidekiq
+            .common
+            .circuit_breaker
+            .clone()
+    }
+
     #[instrument(skip_all)]
     async fn check(&self) -> RoadsterResult<CheckResponse> {
         Ok(redis_health(
@@ -19,9 +19,13 @@
 pub mod api;
 pub mod app;
 pub mod config;
+#[cfg(feature = "db-sql")]
+pub mod db;
 pub mod error;
 pub mod health_check;
 pub mod middleware;
 pub mod service;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod tracing;
 pub mod util;
@@ -0,0 +1,48 @@
+use crate::error::RoadsterResult;
+use async_trait::async_trait;
+use axum::http::request::Parts;
+use serde_json::Value;
+
+/// Performs app-specific validation of a JWT's claims, in addition to the standard claim
+/// validation (`exp`, `aud`, required claims, etc). This allows enforcing authorization rules
+/// such as "the `role` claim must be one of an allowed set of roles" or "the `tenant` claim must
+/// match the request's path" in one place instead of in every handler.
+///
+/// The validator is run by the [Jwt][crate::middleware::http::auth::jwt::Jwt] extractor after the
+/// JWT is decoded. If the validator returns an error, the request is rejected with a
+/// `403 Forbidden` response.
+///
+/// The validator can be registered via
+/// [App::jwt_claims_validator][crate::app::App::jwt_claims_validator].
+///
+/// # Examples
+///
+/// ```rust
+/// use anyhow::anyhow;
+/// use async_trait::async_trait;
+/// use axum::http::request::Parts;
+/// use roadster::error::RoadsterResult;
+/// use roadster::middleware::http::auth::jwt::claims_validator::ClaimsValidator;
+/// use serde_json::Value;
+///
+/// struct AllowedRoles {
+///     roles: Vec<String>,
+/// }
+///
+/// #[async_trait]
+/// impl ClaimsValidator for AllowedRoles {
+///     async fn validate(&self, claims: &Value, _parts: &Parts) -> RoadsterResult<()> {
+///         let role = claims.get("role").and_then(|role| role.as_str());
+///         match role {
+///             Some(role) if self.roles.iter().any(|allowed| allowed == role) => Ok(()),
+///             _ => Err(anyhow!("Role `{role:?}` is not allowed").into()),
+///         }
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait ClaimsValidator: Send + Sync {
+    /// Validate the decoded `claims` of a JWT that was sent with the request with the given
+    /// [Parts]. Return an error to reject the request.
+    async fn validate(&self, claims: &Value, parts: &Parts) -> RoadsterResult<()>;
+}
@@ -0,0 +1,176 @@
+use crate::app::context::AppContext;
+use crate::error::api::http::HttpError;
+use crate::error::{Error, RoadsterResult};
+#[cfg(feature = "jwt-ietf")]
+use crate::middleware::http::auth::jwt::ietf::Claims;
+#[cfg(all(feature = "jwt-openid", not(feature = "jwt-ietf")))]
+use crate::middleware::http::auth::jwt::openid::Claims;
+use crate::middleware::http::auth::jwt::Jwt;
+#[cfg(feature = "open-api")]
+use aide::OperationInput;
+use async_trait::async_trait;
+use axum::extract::{FromRef, FromRequestParts, Request, State};
+use axum::http::request::Parts;
+use axum::middleware::Next;
+use axum::response::Response;
+#[cfg(not(any(feature = "jwt-ietf", feature = "jwt-openid")))]
+use serde_json::Value as Claims;
+
+/// Middleware that decodes the request's JWT once and inserts the [Jwt] into the request's
+/// extensions, where it can be read by the [CurrentUser] extractor. This avoids decoding the JWT
+/// again in each handler or middleware that needs it, and allows deciding which routes require
+/// authentication in one place (i.e., where the middleware is installed). Requests without a
+/// valid JWT are rejected.
+///
+/// # Examples
+///
+/// ```rust
+/// use axum::routing::get;
+/// use axum::Router;
+/// use roadster::app::context::AppContext;
+/// use roadster::middleware::http::auth::jwt::current_user::{jwt_extension, CurrentUser};
+///
+/// fn authed_routes(context: &AppContext) -> Router<AppContext> {
+///     Router::new()
+///         .route("/me", get(|_user: CurrentUser<serde_json::Value>| async {}))
+///         .route_layer(axum::middleware::from_fn_with_state(
+///             context.clone(),
+///             jwt_extension::<AppContext, serde_json::Value>,
+///         ))
+/// }
+/// ```
+pub async fn jwt_extension<S, C>(
+    State(state): State<S>,
+    request: Request,
+    next: Next,
+) -> RoadsterResult<Response>
+where
+    S: Clone + Send + Sync + 'static,
+    AppContext: FromRef<S>,
+    C: for<'de> serde::Deserialize<'de> + Clone + Send + Sync + 'static,
+{
+    let (mut parts, body) = request.into_parts();
+    let jwt = Jwt::<C>::from_request_parts(&mut parts, &state).await?;
+    parts.extensions.insert(jwt);
+    Ok(next.run(Request::from_parts(parts, body)).await)
+}
+
+/// Extractor for the [Jwt] that was decoded by the [jwt_extension] middleware. Unlike the [Jwt]
+/// extractor, this does not decode the JWT again. Returns an error if the [jwt_extension]
+/// middleware was not installed for the route.
+pub struct CurrentUser<C = Claims>(pub Jwt<C>)
+where
+    C: for<'de> serde::Deserialize<'de>;
+
+// Required in order to use `CurrentUser` in an Aide route.
+#[cfg(feature = "open-api")]
+impl OperationInput for CurrentUser {}
+
+#[async_trait]
+impl<S, C> FromRequestParts<S> for CurrentUser<C>
+where
+    S: Clone + Send + Sync + 'static,
+    C: for<'de> serde::Deserialize<'de> + Clone + Send + Sync + 'static,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let jwt = parts.extensions.get::<Jwt<C>>().cloned().ok_or_else(|| {
+            HttpError::internal_server_error().error(
+                "JWT not found in request extensions; is the `jwt_extension` middleware installed?",
+            )
+        })?;
+        Ok(CurrentUser(jwt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::http::auth::jwt::claims_validator::ClaimsValidator;
+    use axum::body::Body;
+    use axum::http::header::AUTHORIZATION;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::Router;
+    use jsonwebtoken::Header;
+    use rstest::rstest;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    struct CountingValidator(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl ClaimsValidator for CountingValidator {
+        async fn validate(
+            &self,
+            _claims: &serde_json::Value,
+            _parts: &Parts,
+        ) -> RoadsterResult<()> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[rstest]
+    #[case(true, StatusCode::OK, 1)]
+    #[case(false, StatusCode::INTERNAL_SERVER_ERROR, 0)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn current_user(
+        #[case] install_middleware: bool,
+        #[case] expected_status: StatusCode,
+        #[case] expected_decode_count: usize,
+    ) {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let decode_count = Arc::new(AtomicUsize::new(0));
+        context
+            .set_jwt_claims_validator(Arc::new(CountingValidator(decode_count.clone())))
+            .unwrap();
+        let router =
+            Router::new().route(
+                "/",
+                get(
+                    |first: CurrentUser<serde_json::Value>,
+                     second: CurrentUser<serde_json::Value>| async move {
+                        assert_eq!(first.0.claims, second.0.claims);
+                        first.0.claims["sub"].as_str().unwrap().to_string()
+                    },
+                ),
+            );
+        let router = if install_middleware {
+            router.route_layer(axum::middleware::from_fn_with_state(
+                context.clone(),
+                jwt_extension::<AppContext, serde_json::Value>,
+            ))
+        } else {
+            router
+        };
+        let router = router.with_state(context);
+        let exp = jsonwebtoken::get_current_timestamp() + 60;
+        let claims = serde_json::json!({ "exp": exp, "sub": "foo" });
+        let token = jsonwebtoken::encode(
+            &Header::default(),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret("secret-test".as_ref()),
+        )
+        .unwrap();
+
+        // Act
+        let response = router
+            .oneshot(
+                axum::http::Request::get("/")
+                    .header(AUTHORIZATION, format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), expected_status);
+        assert_eq!(decode_count.load(Ordering::SeqCst), expected_decode_count);
+    }
+}
@@ -73,7 +73,7 @@ mod tests {
         let jwt = build_token(false, None);
 
         let decoded: TokenData<Claims> =
-            decode_auth_token(&jwt.1, TEST_JWT_SECRET, AUDIENCE, REQUIRED_CLAIMS).unwrap();
+            decode_auth_token(&jwt.1, TEST_JWT_SECRET, AUDIENCE, REQUIRED_CLAIMS, None).unwrap();
 
         assert_eq!(decoded.claims.subject, jwt.0.subject);
     }
@@ -84,7 +84,7 @@ mod tests {
         let (_, jwt) = build_token(true, None);
 
         let decoded: RoadsterResult<TokenData<Claims>> =
-            decode_auth_token(&jwt, TEST_JWT_SECRET, AUDIENCE, REQUIRED_CLAIMS);
+            decode_auth_token(&jwt, TEST_JWT_SECRET, AUDIENCE, REQUIRED_CLAIMS, None);
 
         assert!(decoded.is_err());
     }
@@ -95,7 +95,7 @@ mod tests {
         let (_, jwt) = build_token(false, Some("different-audience".to_string()));
 
         let decoded: RoadsterResult<TokenData<Claims>> =
-            decode_auth_token(&jwt, TEST_JWT_SECRET, AUDIENCE, REQUIRED_CLAIMS);
+            decode_auth_token(&jwt, TEST_JWT_SECRET, AUDIENCE, REQUIRED_CLAIMS, None);
 
         assert!(decoded.is_err());
     }
@@ -0,0 +1,44 @@
+use crate::error::RoadsterResult;
+use async_trait::async_trait;
+use jsonwebtoken::{DecodingKey, Header};
+
+/// Provides the keys to use when decoding a JWT, in addition to the static
+/// [`auth.jwt.secret`][crate::config::auth::Jwt] config value. This allows rotating the keys used
+/// to sign JWTs, e.g. by fetching the current keys from a JWKS endpoint or a secrets manager.
+///
+/// The provider can be registered via [App::jwt_key_provider][crate::app::App::jwt_key_provider].
+///
+/// # Examples
+///
+/// ```rust
+/// use async_trait::async_trait;
+/// use jsonwebtoken::{DecodingKey, Header};
+/// use roadster::error::RoadsterResult;
+/// use roadster::middleware::http::auth::jwt::key_provider::JwtKeyProvider;
+/// use std::collections::BTreeMap;
+///
+/// struct StaticKeys {
+///     keys: BTreeMap<String, DecodingKey>,
+/// }
+///
+/// #[async_trait]
+/// impl JwtKeyProvider for StaticKeys {
+///     async fn decoding_keys(&self, header: &Header) -> RoadsterResult<Vec<DecodingKey>> {
+///         let keys = if let Some(kid) = header.kid.as_ref() {
+///             self.keys.get(kid).cloned().into_iter().collect()
+///         } else {
+///             self.keys.values().cloned().collect()
+///         };
+///         Ok(keys)
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait JwtKeyProvider: Send + Sync {
+    /// Provide the candidate keys that may have been used to sign the JWT with the given
+    /// [Header]. The header's `kid` (key ID) can be used to select the appropriate key(s).
+    ///
+    /// Each key is tried in order until one successfully decodes the JWT. If none of the keys
+    /// are able to decode the JWT, the static `auth.jwt.secret` will be used instead.
+    async fn decoding_keys(&self, header: &Header) -> RoadsterResult<Vec<DecodingKey>>;
+}
@@ -1,15 +1,20 @@
+pub mod claims_validator;
+pub mod current_user;
 #[cfg(feature = "jwt-ietf")]
 pub mod ietf;
+pub mod key_provider;
 #[cfg(feature = "jwt-openid")]
 pub mod openid;
 
 use crate::app::context::AppContext;
+use crate::config::auth::SubjectCoercion;
+use crate::error::api::http::HttpError;
 use crate::error::{Error, RoadsterResult};
 #[cfg(feature = "jwt-ietf")]
 use crate::middleware::http::auth::jwt::ietf::Claims;
 #[cfg(all(feature = "jwt-openid", not(feature = "jwt-ietf")))]
 use crate::middleware::http::auth::jwt::openid::Claims;
-use crate::util::serde_util::{deserialize_from_str, serialize_to_str};
+use crate::util::serde_util::serialize_to_str;
 #[cfg(feature = "open-api")]
 use aide::OperationInput;
 use async_trait::async_trait;
@@ -20,10 +25,12 @@ use axum_extra::headers::authorization::Bearer;
 use axum_extra::headers::Authorization;
 use axum_extra::TypedHeader;
 use itertools::Itertools;
-use jsonwebtoken::{decode, DecodingKey, Header, TokenData, Validation};
-use serde_derive::{Deserialize, Serialize};
+use jsonwebtoken::{decode, decode_header, DecodingKey, Header, TokenData, Validation};
+use serde::{Deserialize, Deserializer};
+use serde_derive::Serialize;
 #[cfg(not(any(feature = "jwt-ietf", feature = "jwt-openid")))]
 use serde_json::Value as Claims;
+use std::marker::PhantomData;
 use url::Url;
 use uuid::Uuid;
 
@@ -35,6 +42,7 @@ type BearerAuthHeader = TypedHeader<Authorization<Bearer>>;
 /// to the claims from `jwt-ietf`. If neither feature is enabled (but `jwt` is enabled), then
 /// the default will simply be a [serde_json::Value]. In all cases, the type can be overridden
 /// by the consumer.
+#[derive(Clone)]
 #[non_exhaustive]
 pub struct Jwt<C = Claims>
 where
@@ -42,6 +50,11 @@ where
 {
     pub header: Header,
     pub claims: C,
+    /// The token's `sub` claim, if any, parsed using the configured
+    /// [JwtClaims::subject_coercion][crate::config::auth::JwtClaims::subject_coercion]. Unlike
+    /// any [Subject] contained in the `claims`, which is always parsed using
+    /// [SubjectCoercion::Full], this preserves e.g. zero-padded string IDs if configured to do so.
+    pub subject: Option<Subject>,
 }
 
 // Required in order to use `Jwt` in an Aide route.
@@ -58,35 +71,162 @@ where
     type Rejection = Error;
 
     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
-        let auth_header = parts.extract::<BearerAuthHeader>().await?;
         let context = AppContext::from_ref(state);
-        let token: TokenData<C> = decode_auth_token(
-            auth_header.0.token(),
-            &context.config().auth.jwt.secret,
-            &context.config().auth.jwt.claims.audience,
-            &context.config().auth.jwt.claims.required_claims,
-        )?;
-        let token = Jwt {
-            header: token.header,
-            claims: token.claims,
-        };
-        Ok(token)
+        extract_jwt(parts, &context, &context.config().auth.jwt.claims.audience).await
     }
 }
 
+/// Declares the audience(s) that a JWT must be issued for in order to be accepted by
+/// the [JwtForAudience] extractor.
+///
+/// # Examples
+/// ```rust
+/// # use roadster::middleware::http::auth::jwt::JwtAudience;
+/// struct Admin;
+/// impl JwtAudience for Admin {
+///     const AUDIENCE: &'static [&'static str] = &["admin"];
+/// }
+/// ```
+pub trait JwtAudience {
+    const AUDIENCE: &'static [&'static str];
+}
+
+/// Same as [Jwt], except the token's audience is validated against the audience declared by
+/// the [JwtAudience] type `A` instead of the audience configured in
+/// [JwtClaims::audience][crate::config::auth::JwtClaims::audience]. This is useful when
+/// different groups of routes accept tokens issued for different audiences, e.g. `api` vs
+/// `admin`.
+#[non_exhaustive]
+pub struct JwtForAudience<A, C = Claims>
+where
+    A: JwtAudience,
+    C: for<'de> serde::Deserialize<'de>,
+{
+    pub header: Header,
+    pub claims: C,
+    /// See [Jwt::subject].
+    pub subject: Option<Subject>,
+    _audience: PhantomData<fn() -> A>,
+}
+
+// Required in order to use `JwtForAudience` in an Aide route.
+#[cfg(feature = "open-api")]
+impl<A> OperationInput for JwtForAudience<A> where A: JwtAudience {}
+
+#[async_trait]
+impl<S, A, C> FromRequestParts<S> for JwtForAudience<A, C>
+where
+    S: Clone + Send + Sync + 'static,
+    AppContext: FromRef<S>,
+    A: JwtAudience,
+    C: for<'de> serde::Deserialize<'de>,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let context = AppContext::from_ref(state);
+        let jwt: Jwt<C> = extract_jwt(parts, &context, A::AUDIENCE).await?;
+        Ok(Self {
+            header: jwt.header,
+            claims: jwt.claims,
+            subject: jwt.subject,
+            _audience: PhantomData,
+        })
+    }
+}
+
+/// Extract and validate the JWT from the request's `Authorization` header, requiring the token
+/// to be issued for one of the given `audience`s.
+async fn extract_jwt<T, C>(
+    parts: &mut Parts,
+    context: &AppContext,
+    audience: &[T],
+) -> RoadsterResult<Jwt<C>>
+where
+    T: ToString,
+    C: for<'de> serde::Deserialize<'de>,
+{
+    let auth_header = parts.extract::<BearerAuthHeader>().await?;
+    let keys = if let Some(key_provider) = context.jwt_key_provider() {
+        let header = decode_header(auth_header.0.token())?;
+        key_provider.decoding_keys(&header).await?
+    } else {
+        Vec::new()
+    };
+    let token: TokenData<serde_json::Value> = decode_auth_token_with_keys(
+        auth_header.0.token(),
+        &keys,
+        &context.config().auth.jwt.secret,
+        audience,
+        &context.config().auth.jwt.claims.required_claims,
+        context.config().auth.jwt.leeway_seconds,
+    )?;
+    if let Some(claims_validator) = context.jwt_claims_validator() {
+        claims_validator
+            .validate(&token.claims, parts)
+            .await
+            .map_err(|err| HttpError::forbidden().source(err))?;
+    }
+    let subject = Subject::from_claims(
+        &token.claims,
+        context.config().auth.jwt.claims.subject_coercion,
+    );
+    let claims: C =
+        serde_json::from_value(token.claims).map_err(jsonwebtoken::errors::Error::from)?;
+    Ok(Jwt {
+        header: token.header,
+        claims,
+        subject,
+    })
+}
+
+#[cfg(test)]
 fn decode_auth_token<T1, T2, C>(
     token: &str,
     jwt_secret: &str,
     audience: &[T1],
     required_claims: &[T2],
+    leeway_seconds: Option<u64>,
+) -> RoadsterResult<TokenData<C>>
+where
+    T1: ToString,
+    T2: ToString,
+    C: for<'de> serde::Deserialize<'de>,
+{
+    decode_auth_token_with_keys(
+        token,
+        &[],
+        jwt_secret,
+        audience,
+        required_claims,
+        leeway_seconds,
+    )
+}
+
+/// Decode the token using each of the provided `keys` in order, falling back to the static
+/// `jwt_secret` if none of the `keys` are able to decode the token.
+fn decode_auth_token_with_keys<T1, T2, C>(
+    token: &str,
+    keys: &[DecodingKey],
+    jwt_secret: &str,
+    audience: &[T1],
+    required_claims: &[T2],
+    leeway_seconds: Option<u64>,
 ) -> RoadsterResult<TokenData<C>>
 where
     T1: ToString,
     T2: ToString,
     C: for<'de> serde::Deserialize<'de>,
 {
-    let mut validation = Validation::default();
+    // Only allow the algorithm from the token's header. `jsonwebtoken` rejects the token if the
+    // algorithm doesn't match the type of the key, so this can't be used to, e.g., verify an
+    // RSA-signed token with an HMAC secret.
+    let header = decode_header(token)?;
+    let mut validation = Validation::new(header.alg);
     validation.set_audience(audience);
+    if let Some(leeway_seconds) = leeway_seconds {
+        validation.leeway = leeway_seconds;
+    }
     if !required_claims.is_empty() {
         // Todo: Is there a way to reduce the allocations used here?
         let required_claims = validation
@@ -97,6 +237,16 @@ where
             .collect_vec();
         validation.set_required_spec_claims(&required_claims);
     }
+    for key in keys {
+        match decode::<C>(token, key, &validation) {
+            Ok(token_data) => return Ok(token_data),
+            // The token was not signed with this key, so try the next one.
+            Err(err) if is_key_mismatch(&err) => continue,
+            // The token was signed with this key but is otherwise invalid (e.g., it's expired),
+            // so return the actual reason instead of trying the remaining keys.
+            Err(err) => return Err(err.into()),
+        }
+    }
     let token_data: TokenData<C> = decode(
         token,
         &DecodingKey::from_secret(jwt_secret.as_ref()),
@@ -105,36 +255,236 @@ where
     Ok(token_data)
 }
 
+/// Whether the error indicates that the token was not signed with the key that was used to
+/// decode it.
+fn is_key_mismatch(err: &jsonwebtoken::errors::Error) -> bool {
+    matches!(
+        err.kind(),
+        jsonwebtoken::errors::ErrorKind::InvalidSignature
+            | jsonwebtoken::errors::ErrorKind::InvalidAlgorithm
+    )
+}
+
 /// The subject of a JWT claim. Technically the IETF spec only specifies that this is a `StringOrURI`
 /// type, and the OpenID spec specifies String. However, since this is likely to contain a user ID,
 /// we will also try to deserialize directly into a UUID or Integer. Deserialization will fall back
 /// to a simple String if the value can not be parsed into a UUID or Integer (or URI).
+///
+/// [Subject]s are always deserialized using [SubjectCoercion::Full]. To preserve zero-padded
+/// string IDs such as `"0123"`, which would otherwise be parsed as an integer, use
+/// [Subject::parse] with a different [SubjectCoercion], or use [Jwt::subject], which is parsed
+/// using the configured
+/// [JwtClaims::subject_coercion][crate::config::auth::JwtClaims::subject_coercion].
 /// See: <https://www.rfc-editor.org/rfc/rfc7519.html#section-4.1.2>
 /// See: <https://openid.net/specs/openid-connect-core-1_0.html#IDToken>
 // Intentionally not annotated with `#[non_exhaustive]`
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum Subject {
     Uri(Url),
     Uuid(Uuid),
-    Int(
-        #[serde(
-            deserialize_with = "deserialize_from_str",
-            serialize_with = "serialize_to_str"
-        )]
-        u64,
-    ),
+    Int(#[serde(serialize_with = "serialize_to_str")] u64),
     String(String),
 }
 
+impl Subject {
+    /// Parse the `value` into a [Subject], only attempting the types allowed by the provided
+    /// [SubjectCoercion].
+    pub fn parse(value: String, coercion: SubjectCoercion) -> Self {
+        if coercion == SubjectCoercion::Disabled {
+            return Subject::String(value);
+        }
+        if let Ok(uri) = Url::parse(&value) {
+            return Subject::Uri(uri);
+        }
+        if let Ok(uuid) = Uuid::parse_str(&value) {
+            return Subject::Uuid(uuid);
+        }
+        if coercion == SubjectCoercion::Full {
+            if let Ok(int) = value.parse::<u64>() {
+                return Subject::Int(int);
+            }
+        }
+        Subject::String(value)
+    }
+
+    /// Parse the `sub` claim from the JWT's `claims`, if present, using the provided
+    /// [SubjectCoercion].
+    fn from_claims(claims: &serde_json::Value, coercion: SubjectCoercion) -> Option<Self> {
+        claims
+            .get("sub")
+            .and_then(|sub| sub.as_str())
+            .map(|sub| Subject::parse(sub.to_string(), coercion))
+    }
+}
+
+impl<'de> Deserialize<'de> for Subject {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Ok(Subject::parse(value, SubjectCoercion::Full))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::app_config::AppConfig;
+    use crate::error::auth::AuthError;
+    use crate::middleware::http::auth::jwt::claims_validator::ClaimsValidator;
     use crate::util::serde_util::Wrapper;
+    use axum::body::Body;
+    use axum::http::header::AUTHORIZATION;
+    use axum::http::{Request, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use jsonwebtoken::Algorithm;
+    use rstest::rstest;
     use serde_json::from_str;
     use std::str::FromStr;
+    use std::sync::Arc;
+    use tower::ServiceExt;
     use url::Url;
 
+    const TEST_SECRET: &str = "test-secret";
+
+    fn encode_token(exp_offset_seconds: i64) -> String {
+        encode_token_with_secret(exp_offset_seconds, TEST_SECRET)
+    }
+
+    fn encode_token_with_secret(exp_offset_seconds: i64, secret: &str) -> String {
+        encode_token_with_algorithm(exp_offset_seconds, secret, Algorithm::HS256)
+    }
+
+    fn encode_token_with_algorithm(
+        exp_offset_seconds: i64,
+        secret: &str,
+        algorithm: Algorithm,
+    ) -> String {
+        let exp = jsonwebtoken::get_current_timestamp() as i64 + exp_offset_seconds;
+        let claims = serde_json::json!({ "exp": exp });
+        jsonwebtoken::encode(
+            &Header::new(algorithm),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret(secret.as_ref()),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    #[case(60, Some(0), true)]
+    #[case(-30, Some(0), false)]
+    #[case(-30, Some(60), true)]
+    #[case(-120, Some(60), false)]
+    #[case(-30, None, true)]
+    #[case(-120, None, false)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn decode_auth_token_leeway(
+        #[case] exp_offset_seconds: i64,
+        #[case] leeway_seconds: Option<u64>,
+        #[case] expect_ok: bool,
+    ) {
+        // Arrange
+        let token = encode_token(exp_offset_seconds);
+
+        // Act
+        let result: RoadsterResult<TokenData<serde_json::Value>> = decode_auth_token(
+            &token,
+            TEST_SECRET,
+            &Vec::<String>::new(),
+            &Vec::<String>::new(),
+            leeway_seconds,
+        );
+
+        // Assert
+        assert_eq!(result.is_ok(), expect_ok);
+    }
+
+    #[rstest]
+    #[case("key-1", true)]
+    #[case("key-2", true)]
+    #[case(TEST_SECRET, true)]
+    #[case("unknown-key", false)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn decode_auth_token_with_keys(#[case] signing_secret: &str, #[case] expect_ok: bool) {
+        // Arrange
+        let token = encode_token_with_secret(60, signing_secret);
+        let keys = vec![
+            DecodingKey::from_secret("key-1".as_ref()),
+            DecodingKey::from_secret("key-2".as_ref()),
+        ];
+
+        // Act
+        let result: RoadsterResult<TokenData<serde_json::Value>> =
+            super::decode_auth_token_with_keys(
+                &token,
+                &keys,
+                TEST_SECRET,
+                &Vec::<String>::new(),
+                &Vec::<String>::new(),
+                None,
+            );
+
+        // Assert
+        assert_eq!(result.is_ok(), expect_ok);
+    }
+
+    #[rstest]
+    #[case::hs256(Algorithm::HS256)]
+    #[case::hs384(Algorithm::HS384)]
+    #[case::hs512(Algorithm::HS512)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn decode_auth_token_with_keys_algorithm(#[case] algorithm: Algorithm) {
+        // Arrange
+        let token = encode_token_with_algorithm(60, "key-2", algorithm);
+        let keys = vec![
+            DecodingKey::from_secret("key-1".as_ref()),
+            DecodingKey::from_secret("key-2".as_ref()),
+        ];
+
+        // Act
+        let result: RoadsterResult<TokenData<serde_json::Value>> =
+            super::decode_auth_token_with_keys(
+                &token,
+                &keys,
+                TEST_SECRET,
+                &Vec::<String>::new(),
+                &Vec::<String>::new(),
+                None,
+            );
+
+        // Assert
+        assert_eq!(result.unwrap().header.alg, algorithm);
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn decode_auth_token_with_keys_expired() {
+        // Arrange
+        let token = encode_token_with_secret(-120, "key-1");
+        let keys = vec![DecodingKey::from_secret("key-1".as_ref())];
+
+        // Act
+        let result: RoadsterResult<TokenData<serde_json::Value>> =
+            super::decode_auth_token_with_keys(
+                &token,
+                &keys,
+                TEST_SECRET,
+                &Vec::<String>::new(),
+                &Vec::<String>::new(),
+                Some(0),
+            );
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(Error::Auth(AuthError::Jwt(ref err)))
+                if *err.kind() == jsonwebtoken::errors::ErrorKind::ExpiredSignature
+        ));
+    }
+
     #[test]
     #[cfg_attr(coverage_nightly, coverage(off))]
     fn deserialize_subject_as_uri() {
@@ -178,4 +528,176 @@ mod tests {
         let value: Wrapper<Subject> = from_str(r#"{"inner": "invalid-uri"}"#).unwrap();
         assert_eq!(value.inner, Subject::String("invalid-uri".to_string()));
     }
+
+    #[rstest]
+    #[case("0123", SubjectCoercion::Full, Subject::Int(123))]
+    #[case("0123", SubjectCoercion::NoInt, Subject::String("0123".to_string()))]
+    #[case("0123", SubjectCoercion::Disabled, Subject::String("0123".to_string()))]
+    #[case(
+        "https://example.com",
+        SubjectCoercion::NoInt,
+        Subject::Uri(Url::from_str("https://example.com").unwrap())
+    )]
+    #[case(
+        "https://example.com",
+        SubjectCoercion::Disabled,
+        Subject::String("https://example.com".to_string())
+    )]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn subject_parse(
+        #[case] value: &str,
+        #[case] coercion: SubjectCoercion,
+        #[case] expected: Subject,
+    ) {
+        assert_eq!(Subject::parse(value.to_string(), coercion), expected);
+    }
+
+    #[rstest]
+    #[case(SubjectCoercion::Full, Subject::Int(123))]
+    #[case(SubjectCoercion::NoInt, Subject::String("0123".to_string()))]
+    #[case(SubjectCoercion::Disabled, Subject::String("0123".to_string()))]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn jwt_subject_coercion(#[case] coercion: SubjectCoercion, #[case] expected: Subject) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.auth.jwt.claims.subject_coercion = coercion;
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let router = Router::new()
+            .route(
+                "/",
+                get(|jwt: Jwt<serde_json::Value>| async move {
+                    serde_json::to_string(&jwt.subject).unwrap()
+                }),
+            )
+            .with_state(context);
+        let exp = jsonwebtoken::get_current_timestamp() + 60;
+        let claims = serde_json::json!({ "exp": exp, "sub": "0123" });
+        let token = jsonwebtoken::encode(
+            &Header::default(),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret("secret-test".as_ref()),
+        )
+        .unwrap();
+
+        // Act
+        let response = router
+            .oneshot(
+                Request::get("/")
+                    .header(AUTHORIZATION, format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, serde_json::to_string(&Some(expected)).unwrap());
+    }
+
+    struct BannedRoleValidator;
+
+    #[async_trait]
+    impl ClaimsValidator for BannedRoleValidator {
+        async fn validate(&self, claims: &serde_json::Value, _parts: &Parts) -> RoadsterResult<()> {
+            if claims.get("role").and_then(|role| role.as_str()) == Some("banned") {
+                return Err(anyhow::anyhow!("Role is not allowed").into());
+            }
+            Ok(())
+        }
+    }
+
+    #[rstest]
+    #[case("admin", StatusCode::OK)]
+    #[case("banned", StatusCode::FORBIDDEN)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn jwt_claims_validator(#[case] role: &str, #[case] expected_status: StatusCode) {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        context
+            .set_jwt_claims_validator(Arc::new(BannedRoleValidator))
+            .unwrap();
+        let router = Router::new()
+            .route("/", get(|_jwt: Jwt<serde_json::Value>| async {}))
+            .with_state(context);
+        let exp = jsonwebtoken::get_current_timestamp() + 60;
+        let claims = serde_json::json!({ "exp": exp, "role": role });
+        let token = jsonwebtoken::encode(
+            &Header::default(),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret("secret-test".as_ref()),
+        )
+        .unwrap();
+
+        // Act
+        let response = router
+            .oneshot(
+                Request::get("/")
+                    .header(AUTHORIZATION, format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), expected_status);
+    }
+
+    struct ApiAudience;
+    impl JwtAudience for ApiAudience {
+        const AUDIENCE: &'static [&'static str] = &["api"];
+    }
+
+    struct AdminAudience;
+    impl JwtAudience for AdminAudience {
+        const AUDIENCE: &'static [&'static str] = &["admin"];
+    }
+
+    #[rstest]
+    #[case("/api", true)]
+    #[case("/admin", false)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn jwt_for_audience(#[case] path: &str, #[case] expect_ok: bool) {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let router = Router::new()
+            .route(
+                "/api",
+                get(|_jwt: JwtForAudience<ApiAudience, serde_json::Value>| async {}),
+            )
+            .route(
+                "/admin",
+                get(|_jwt: JwtForAudience<AdminAudience, serde_json::Value>| async {}),
+            )
+            .with_state(context);
+        let exp = jsonwebtoken::get_current_timestamp() + 60;
+        let claims = serde_json::json!({ "exp": exp, "aud": "api" });
+        let token = jsonwebtoken::encode(
+            &Header::default(),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret("secret-test".as_ref()),
+        )
+        .unwrap();
+
+        // Act
+        let response = router
+            .oneshot(
+                Request::get(path)
+                    .header(AUTHORIZATION, format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status().is_success(), expect_ok);
+    }
 }
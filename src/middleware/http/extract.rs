@@ -0,0 +1,248 @@
+use crate::error::api::http::HttpError;
+#[cfg(feature = "open-api")]
+use aide::gen::GenContext;
+#[cfg(feature = "open-api")]
+use aide::openapi::Operation;
+#[cfg(feature = "open-api")]
+use aide::OperationInput;
+use async_trait::async_trait;
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{FromRequestParts, MatchedPath, Query};
+use axum::http::request::Parts;
+use axum::response::{IntoResponse, Response};
+use thiserror::Error;
+use validator::{Validate, ValidationErrors};
+
+/// Extractor that deserializes the request's query string into `T` (similar to [Query]), and then
+/// validates it using [Validate::validate].
+///
+/// # Examples
+///
+/// ```rust
+/// use roadster::middleware::http::extract::ValidatedQuery;
+/// use serde_derive::Deserialize;
+/// use validator::Validate;
+///
+/// #[derive(Deserialize, Validate)]
+/// struct Pagination {
+///     #[validate(range(min = 1, max = 100))]
+///     page_size: u32,
+/// }
+///
+/// async fn list(ValidatedQuery(pagination): ValidatedQuery<Pagination>) -> String {
+///     format!("page_size: {}", pagination.page_size)
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedQuery<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: for<'de> serde::Deserialize<'de> + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ValidatedQueryRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state).await?;
+        value.validate()?;
+        Ok(ValidatedQuery(value))
+    }
+}
+
+// Required in order to use `ValidatedQuery` in an Aide route.
+#[cfg(feature = "open-api")]
+impl<T> OperationInput for ValidatedQuery<T>
+where
+    T: schemars::JsonSchema,
+{
+    fn operation_input(ctx: &mut GenContext, operation: &mut Operation) {
+        Query::<T>::operation_input(ctx, operation);
+    }
+}
+
+/// Rejection used for [ValidatedQuery].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ValidatedQueryRejection {
+    /// The query string could not be deserialized into the target type.
+    #[error(transparent)]
+    Query(#[from] QueryRejection),
+
+    /// The query string was deserialized, but the resulting value failed validation.
+    #[error(transparent)]
+    Validation(#[from] ValidationErrors),
+}
+
+impl IntoResponse for ValidatedQueryRejection {
+    fn into_response(self) -> Response {
+        match self {
+            ValidatedQueryRejection::Query(err) => HttpError::bad_request()
+                .error("Invalid query string")
+                .details(err.body_text())
+                .source(err)
+                .into_response(),
+            ValidatedQueryRejection::Validation(err) => {
+                // Serialize the field errors as JSON so clients can tell which fields failed
+                // validation and why.
+                let details = serde_json::to_string(&err).unwrap_or_else(|_| err.to_string());
+                HttpError::bad_request()
+                    .error("Invalid query parameters")
+                    .details(details)
+                    .source(err)
+                    .into_response()
+            }
+        }
+    }
+}
+
+/// Extractor that provides the route pattern that matched the request, e.g. `/users/:id`
+/// instead of the request's concrete path, e.g. `/users/123`. This is useful, for example, to
+/// use as a label for metrics without creating a separate label for every path param value.
+///
+/// This is a thin wrapper around [MatchedPath] that converts the path to a [String] and returns
+/// an [HttpError] if the matched path is not available, e.g. if the extractor is used in a
+/// fallback handler or a middleware that runs before routing.
+///
+/// # Examples
+///
+/// ```rust
+/// use roadster::middleware::http::extract::RoutePattern;
+///
+/// async fn get_user(RoutePattern(route): RoutePattern) -> String {
+///     format!("route: {route}")
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RoutePattern(pub String);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for RoutePattern
+where
+    S: Send + Sync,
+{
+    type Rejection = HttpError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<MatchedPath>()
+            .map(|path| RoutePattern(path.as_str().to_string()))
+            .ok_or_else(|| {
+                HttpError::internal_server_error().error("Matched route pattern is not available")
+            })
+    }
+}
+
+// Required in order to use `RoutePattern` in an Aide route.
+#[cfg(feature = "open-api")]
+impl OperationInput for RoutePattern {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use serde_derive::Deserialize;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    #[derive(Debug, Deserialize, Validate)]
+    struct TestQuery {
+        #[validate(range(min = 1, max = 10))]
+        foo: u32,
+    }
+
+    async fn extract(uri: &str) -> Result<ValidatedQuery<TestQuery>, ValidatedQueryRejection> {
+        let (mut parts, _) = Request::builder().uri(uri).body(()).unwrap().into_parts();
+        ValidatedQuery::<TestQuery>::from_request_parts(&mut parts, &()).await
+    }
+
+    async fn response_body(response: Response) -> Value {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn valid_query() {
+        let ValidatedQuery(query) = extract("/?foo=5").await.unwrap();
+
+        assert_eq!(query.foo, 5);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn deserialize_failure() {
+        let rejection = extract("/?foo=bar").await.unwrap_err();
+        assert!(matches!(rejection, ValidatedQueryRejection::Query(_)));
+
+        let response = rejection.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response_body(response).await;
+        assert_eq!(body["error"], "Invalid query string");
+        assert!(body["details"].is_string());
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn validation_failure() {
+        let rejection = extract("/?foo=100").await.unwrap_err();
+        assert!(matches!(rejection, ValidatedQueryRejection::Validation(_)));
+
+        let response = rejection.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response_body(response).await;
+        assert_eq!(body["error"], "Invalid query parameters");
+        let details: Value = serde_json::from_str(body["details"].as_str().unwrap()).unwrap();
+        assert_eq!(details["foo"][0]["code"], "range");
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn route_pattern() {
+        // Arrange
+        let router = Router::new().route(
+            "/users/:id",
+            get(|RoutePattern(route): RoutePattern| async move { route }),
+        );
+
+        // Act
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/users/123")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "/users/:id");
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn route_pattern_missing() {
+        // Arrange
+        let (mut parts, _) = Request::builder()
+            .uri("/users/123")
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        // Act
+        let rejection = RoutePattern::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err();
+
+        // Assert
+        assert_eq!(rejection.status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
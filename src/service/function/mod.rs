@@ -1 +1,2 @@
+pub mod scheduled;
 pub mod service;
@@ -0,0 +1,267 @@
+use crate::app::context::AppContext;
+use crate::app::App;
+use crate::error::RoadsterResult;
+use crate::service::AppService;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use axum::extract::FromRef;
+use serde_derive::{Deserialize, Serialize};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, instrument};
+use typed_builder::TypedBuilder;
+
+/// How a [ScheduledFunctionService] should behave if an invocation of its function takes longer
+/// than the service's interval. In either case, invocations of the function never overlap.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum OverlapBehavior {
+    /// Skip any ticks that were missed while the function was running. The next invocation
+    /// will happen at the next tick of the interval.
+    #[default]
+    Skip,
+    /// Queue any ticks that were missed while the function was running. The function will be
+    /// invoked for each missed tick as quickly as possible until it has caught up.
+    Queue,
+}
+
+impl From<OverlapBehavior> for MissedTickBehavior {
+    fn from(value: OverlapBehavior) -> Self {
+        match value {
+            OverlapBehavior::Skip => MissedTickBehavior::Skip,
+            OverlapBehavior::Queue => MissedTickBehavior::Burst,
+        }
+    }
+}
+
+/// A generic [AppService] that invokes an async function periodically at a fixed interval. This
+/// is useful for recurring tasks that don't need to be backed by a job queue, e.g. refreshing an
+/// in-memory cache.
+///
+/// The function is first invoked immediately when the service starts, and then once per
+/// `interval`. Any errors returned by the function are logged and do not stop the service. The
+/// service stops when the app's [CancellationToken] is cancelled; if the function is running at
+/// that time, it will be dropped without running to completion.
+///
+/// The `interval` must be greater than zero; otherwise, the app will fail to start.
+///
+/// # Examples
+///
+/// ```rust
+/// use roadster::app::context::AppContext;
+/// use roadster::app::App;
+/// use roadster::error::RoadsterResult;
+/// use roadster::service::function::scheduled::ScheduledFunctionService;
+/// use roadster::service::registry::ServiceRegistry;
+/// use std::time::Duration;
+///
+/// async fn refresh_cache(_state: AppContext) -> RoadsterResult<()> {
+///     // Task logic here
+///     Ok(())
+/// }
+///
+/// fn register<A>(registry: &mut ServiceRegistry<A, AppContext>) -> RoadsterResult<()>
+/// where
+///     A: App<AppContext> + 'static,
+/// {
+///     let service = ScheduledFunctionService::builder()
+///         .name("refresh-cache".to_string())
+///         .interval(Duration::from_secs(60))
+///         .function(refresh_cache)
+///         .build();
+///
+///     registry.register_service(service)?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(TypedBuilder)]
+pub struct ScheduledFunctionService<A, S, F, Fut>
+where
+    S: Clone + Send + Sync + 'static,
+    AppContext: FromRef<S>,
+    A: App<S> + 'static,
+    F: Send + Sync + Fn(S) -> Fut,
+    Fut: Send + Future<Output = RoadsterResult<()>>,
+{
+    name: String,
+    #[builder(default, setter(strip_option))]
+    enabled: Option<bool>,
+    interval: Duration,
+    #[builder(default)]
+    overlap_behavior: OverlapBehavior,
+    function: F,
+    #[builder(default, setter(skip))]
+    _app: PhantomData<A>,
+    #[builder(default, setter(skip))]
+    _state: PhantomData<S>,
+}
+
+impl<A, S, F, Fut> ScheduledFunctionService<A, S, F, Fut>
+where
+    S: Clone + Send + Sync + 'static,
+    AppContext: FromRef<S>,
+    A: App<S> + 'static,
+    F: Send + Sync + Fn(S) -> Fut,
+    Fut: Send + Future<Output = RoadsterResult<()>>,
+{
+    /// [tokio::time::interval] panics if the interval is zero, so return an error instead.
+    fn validate_interval(&self) -> RoadsterResult<()> {
+        if self.interval.is_zero() {
+            return Err(anyhow!(
+                "The interval for scheduled function service `{}` must be greater than zero",
+                self.name
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<A, S, F, Fut> AppService<A, S> for ScheduledFunctionService<A, S, F, Fut>
+where
+    S: Clone + Send + Sync + 'static,
+    AppContext: FromRef<S>,
+    A: App<S> + 'static,
+    F: Send + Sync + Fn(S) -> Fut,
+    Fut: Send + Future<Output = RoadsterResult<()>>,
+{
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn enabled(&self, state: &S) -> bool {
+        self.enabled
+            .unwrap_or(AppContext::from_ref(state).config().service.default_enable)
+    }
+
+    async fn before_run(&self, _state: &S) -> RoadsterResult<()> {
+        self.validate_interval()
+    }
+
+    #[instrument(skip_all, fields(service = %self.name))]
+    async fn run(
+        self: Box<Self>,
+        state: &S,
+        cancel_token: CancellationToken,
+    ) -> RoadsterResult<()> {
+        self.validate_interval()?;
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(self.overlap_behavior.into());
+
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                result = (self.function)(state.clone()) => {
+                    if let Err(err) = result {
+                        error!(%err, "Scheduled function failed");
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::MockApp;
+    use rstest::rstest;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    async fn wait_for_count(count: &AtomicUsize, expected: usize) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while count.load(Ordering::SeqCst) < expected {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn run(#[case] fail: bool) {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let count = Arc::new(AtomicUsize::new(0));
+        let service: ScheduledFunctionService<MockApp<AppContext>, _, _, _> = {
+            let count = count.clone();
+            ScheduledFunctionService::builder()
+                .name("test".to_string())
+                .interval(Duration::from_millis(10))
+                .function(move |_state: AppContext| {
+                    let count = count.clone();
+                    async move {
+                        count.fetch_add(1, Ordering::SeqCst);
+                        let result: RoadsterResult<()> = if fail {
+                            Err(anyhow!("failed").into())
+                        } else {
+                            Ok(())
+                        };
+                        result
+                    }
+                })
+                .build()
+        };
+        let cancel_token = CancellationToken::new();
+
+        // Act
+        let handle = {
+            let cancel_token = cancel_token.clone();
+            tokio::spawn(async move { Box::new(service).run(&context, cancel_token).await })
+        };
+        wait_for_count(&count, 3).await;
+        cancel_token.cancel();
+        let result = handle.await.unwrap();
+
+        // Assert
+        assert!(result.is_ok());
+        let count_after_cancel = count.load(Ordering::SeqCst);
+        assert!(count_after_cancel >= 3);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(count.load(Ordering::SeqCst), count_after_cancel);
+    }
+
+    #[rstest]
+    #[case(Duration::from_millis(10), true)]
+    #[case(Duration::ZERO, false)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn interval(#[case] interval: Duration, #[case] expect_ok: bool) {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let service: ScheduledFunctionService<MockApp<AppContext>, _, _, _> =
+            ScheduledFunctionService::builder()
+                .name("test".to_string())
+                .interval(interval)
+                .function(|_state: AppContext| async { RoadsterResult::Ok(()) })
+                .build();
+        let cancel_token = CancellationToken::new();
+        cancel_token.cancel();
+
+        // Act
+        let before_run = service.before_run(&context).await;
+        let run = Box::new(service).run(&context, cancel_token).await;
+
+        // Assert
+        assert_eq!(before_run.is_ok(), expect_ok);
+        assert_eq!(run.is_ok(), expect_ok);
+    }
+}
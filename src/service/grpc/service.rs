@@ -1,28 +1,53 @@
+use crate::api::core::health::health_check;
 use crate::app::context::AppContext;
 use crate::app::App;
 use crate::error::RoadsterResult;
+use crate::health_check::Status;
 use crate::service::AppService;
 use anyhow::anyhow;
 use async_trait::async_trait;
 use axum::extract::FromRef;
 use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
 use tokio_util::sync::CancellationToken;
 use tonic::transport::server::Router;
+use tonic_health::server::HealthReporter;
+use tonic_health::ServingStatus;
 use tracing::info;
 
+pub(crate) const NAME: &str = "grpc";
+
 /// Simple wrapper around a tonic [Router] to run a gRPC service.
+///
+/// The standard gRPC health and reflection services can be added to the [Router] automatically
+/// by enabling them in the app's config (`service.grpc.health-service` and
+/// `service.grpc.reflection-service`, respectively).
 // todo: enable adding middleware to the service?
 // todo: enable sharing middleware with the axum router?
 pub struct GrpcService {
     pub(crate) router: Mutex<Router>,
+    file_descriptor_sets: Vec<&'static [u8]>,
 }
 
 impl GrpcService {
     pub fn new(router: Router) -> Self {
         Self {
             router: Mutex::new(router),
+            file_descriptor_sets: Default::default(),
         }
     }
+
+    /// Register an encoded file descriptor set (e.g., as generated by `tonic-build`) with the
+    /// reflection service, if the reflection service is enabled. Only the services whose
+    /// file descriptor sets are registered can be discovered via the reflection service.
+    pub fn register_encoded_file_descriptor_set(
+        mut self,
+        encoded_file_descriptor_set: &'static [u8],
+    ) -> Self {
+        self.file_descriptor_sets.push(encoded_file_descriptor_set);
+        self
+    }
 }
 
 #[async_trait]
@@ -33,7 +58,7 @@ where
     A: App<S> + 'static,
 {
     fn name(&self) -> String {
-        "grpc".to_string()
+        NAME.to_string()
     }
 
     fn enabled(&self, state: &S) -> bool {
@@ -47,12 +72,43 @@ where
         cancel_token: CancellationToken,
     ) -> RoadsterResult<()> {
         let context = AppContext::from_ref(state);
-        let server_addr = context.config().service.grpc.custom.address.url();
+        let config = &context.config().service.grpc.custom;
+        let server_addr = config.address.url();
+
+        let mut router = self.router.into_inner().unwrap();
+
+        if config.health_service.enable {
+            info!("Adding gRPC health service");
+            let (reporter, health_service) = tonic_health::server::health_reporter();
+            context.spawn_cancellable(report_health(
+                state.clone(),
+                reporter,
+                config.health_service.interval,
+            ));
+            router = router.add_service(health_service);
+        }
+
+        if config.reflection_service.enable {
+            info!("Adding gRPC reflection service");
+            let reflection_service = self
+                .file_descriptor_sets
+                .iter()
+                .fold(
+                    tonic_reflection::server::Builder::configure()
+                        .register_encoded_file_descriptor_set(
+                            tonic_health::pb::FILE_DESCRIPTOR_SET,
+                        ),
+                    |builder, file_descriptor_set| {
+                        builder.register_encoded_file_descriptor_set(file_descriptor_set)
+                    },
+                )
+                .build()?;
+            router = router.add_service(reflection_service);
+        }
+
         info!("gRPC server will start at {server_addr}");
 
-        self.router
-            .into_inner()
-            .unwrap()
+        router
             .serve_with_shutdown(
                 server_addr
                     .parse()
@@ -63,3 +119,211 @@ where
         Ok(())
     }
 }
+
+/// Periodically run the app's health checks and report the result via the gRPC health service.
+/// The health checks are also run as soon as the app's [readiness][AppContext::ready] changes, so
+/// the app is reported as `NOT_SERVING` for the whole pre-shutdown delay.
+async fn report_health<S>(state: S, mut reporter: HealthReporter, interval: Duration)
+where
+    S: Clone + Send + Sync + 'static,
+    AppContext: FromRef<S>,
+{
+    let mut ready = AppContext::from_ref(&state).subscribe_ready();
+    let mut timer = tokio::time::interval(interval);
+    timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = timer.tick() => {},
+            Ok(()) = ready.changed() => {},
+        }
+        let status = match health_check(&state, Some(interval)).await {
+            Ok(response)
+                if response
+                    .resources
+                    .values()
+                    .all(|resource| matches!(resource.status, Status::Ok)) =>
+            {
+                ServingStatus::Serving
+            }
+            _ => ServingStatus::NotServing,
+        };
+        reporter.set_service_status("", status).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::MockApp;
+    use crate::config::app_config::AppConfig;
+    use futures::stream;
+    use std::net::TcpListener;
+    use tonic::transport::Channel;
+    use tonic_health::pb::health_check_response;
+    use tonic_health::pb::health_client::HealthClient;
+    use tonic_health::pb::HealthCheckRequest;
+    use tonic_reflection::pb::server_reflection_client::ServerReflectionClient;
+    use tonic_reflection::pb::server_reflection_request::MessageRequest;
+    use tonic_reflection::pb::server_reflection_response::MessageResponse;
+    use tonic_reflection::pb::ServerReflectionRequest;
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn health_and_reflection_services() {
+        // Arrange
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let mut config = AppConfig::test(None).unwrap();
+        config.service.grpc.custom.address.host = "127.0.0.1".to_string();
+        config.service.grpc.custom.address.port = port as u32;
+        config.service.grpc.custom.health_service.enable = true;
+        config.service.grpc.custom.reflection_service.enable = true;
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let service =
+            GrpcService::new(tonic::transport::Server::builder().add_routes(Default::default()));
+        let cancel_token = CancellationToken::new();
+
+        let handle = {
+            let context = context.clone();
+            let cancel_token = cancel_token.clone();
+            tokio::spawn(async move {
+                AppService::<MockApp<AppContext>, AppContext>::run(
+                    Box::new(service),
+                    &context,
+                    cancel_token,
+                )
+                .await
+            })
+        };
+        let mut channel = None;
+        for _ in 0..100 {
+            if let Ok(connected) = Channel::from_shared(format!("http://127.0.0.1:{port}"))
+                .unwrap()
+                .connect()
+                .await
+            {
+                channel = Some(connected);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let channel = channel.unwrap();
+
+        // Act
+        let health = HealthClient::new(channel.clone())
+            .check(HealthCheckRequest {
+                service: "".to_string(),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        let reflection = ServerReflectionClient::new(channel)
+            .server_reflection_info(stream::iter(vec![ServerReflectionRequest {
+                host: "".to_string(),
+                message_request: Some(MessageRequest::ListServices("".to_string())),
+            }]))
+            .await
+            .unwrap()
+            .into_inner()
+            .message()
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Assert
+        assert_eq!(
+            health.status,
+            health_check_response::ServingStatus::Serving as i32
+        );
+        let Some(MessageResponse::ListServicesResponse(services)) = reflection.message_response
+        else {
+            panic!("Unexpected reflection response: {reflection:?}");
+        };
+        assert!(services
+            .service
+            .iter()
+            .any(|service| service.name == "grpc.health.v1.Health"));
+
+        cancel_token.cancel();
+        context.cancellation_token().cancel();
+        handle.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn health_service_not_ready() {
+        // Arrange
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let mut config = AppConfig::test(None).unwrap();
+        config.service.grpc.custom.address.host = "127.0.0.1".to_string();
+        config.service.grpc.custom.address.port = port as u32;
+        config.service.grpc.custom.health_service.enable = true;
+        // Long enough that the status is only updated due to the readiness change
+        config.service.grpc.custom.health_service.interval = Duration::from_secs(3600);
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let service =
+            GrpcService::new(tonic::transport::Server::builder().add_routes(Default::default()));
+        let cancel_token = CancellationToken::new();
+
+        let handle = {
+            let context = context.clone();
+            let cancel_token = cancel_token.clone();
+            tokio::spawn(async move {
+                AppService::<MockApp<AppContext>, AppContext>::run(
+                    Box::new(service),
+                    &context,
+                    cancel_token,
+                )
+                .await
+            })
+        };
+        let mut channel = None;
+        for _ in 0..100 {
+            if let Ok(connected) = Channel::from_shared(format!("http://127.0.0.1:{port}"))
+                .unwrap()
+                .connect()
+                .await
+            {
+                channel = Some(connected);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let mut client = HealthClient::new(channel.unwrap());
+
+        // Act
+        context.set_ready(false);
+        let mut status = None;
+        for _ in 0..100 {
+            let response = client
+                .check(HealthCheckRequest {
+                    service: "".to_string(),
+                })
+                .await
+                .unwrap()
+                .into_inner();
+            status = Some(response.status);
+            if response.status == health_check_response::ServingStatus::NotServing as i32 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // Assert
+        assert_eq!(
+            status,
+            Some(health_check_response::ServingStatus::NotServing as i32)
+        );
+
+        cancel_token.cancel();
+        context.cancellation_token().cancel();
+        handle.await.unwrap().unwrap();
+    }
+}
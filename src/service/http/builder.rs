@@ -4,10 +4,13 @@ use crate::api::http::default_api_routes;
 use crate::api::http::default_routes;
 use crate::app::context::AppContext;
 use crate::app::App;
+use crate::config::service::http::ErrorFormat;
+use crate::error::api::http::{render_problem_details, set_error_log_level, HttpError};
 use crate::error::RoadsterResult;
 use crate::service::http::initializer::default::default_initializers;
 use crate::service::http::initializer::Initializer;
 use crate::service::http::middleware::default::default_middleware;
+use crate::service::http::middleware::tracing::TracingMiddleware;
 use crate::service::http::middleware::Middleware;
 use crate::service::http::service::{enabled, HttpService, NAME};
 use crate::service::AppServiceBuilder;
@@ -19,14 +22,22 @@ use aide::openapi::OpenApi;
 use aide::transform::TransformOpenApi;
 use anyhow::anyhow;
 use async_trait::async_trait;
-use axum::extract::FromRef;
+use axum::extract::{FromRef, Request};
+use axum::handler::Handler;
+use axum::http::{Extensions, HeaderMap};
+use axum::response::IntoResponse;
+use axum::routing::Route;
 #[cfg(feature = "open-api")]
 use axum::Extension;
 use axum::Router;
 use itertools::Itertools;
 use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::path::Path;
 #[cfg(feature = "open-api")]
 use std::sync::Arc;
+use tower::{Layer, Service};
+use tower_http::services::ServeFile;
 use tracing::info;
 
 pub struct HttpServiceBuilder<S>
@@ -40,8 +51,14 @@ where
     api_router: ApiRouter<S>,
     #[cfg(feature = "open-api")]
     api_docs: Box<dyn Fn(TransformOpenApi) -> TransformOpenApi + Send>,
+    router_providers: Vec<Box<dyn FnOnce(&S) -> Router<S> + Send>>,
+    #[cfg(feature = "open-api")]
+    api_router_providers: Vec<Box<dyn FnOnce(&S) -> ApiRouter<S> + Send>>,
     middleware: BTreeMap<String, Box<dyn Middleware<S>>>,
+    layers: Vec<Box<dyn FnOnce(Router) -> Router + Send>>,
     initializers: BTreeMap<String, Box<dyn Initializer<S>>>,
+    /// Whether a custom fallback was set. If not, [default_fallback] will be used.
+    custom_fallback: bool,
 }
 
 impl<S> HttpServiceBuilder<S>
@@ -69,8 +86,13 @@ where
             api_docs: Box::new(move |api| {
                 api.title(&app_name).description(&format!("# {}", app_name))
             }),
+            router_providers: Default::default(),
+            #[cfg(feature = "open-api")]
+            api_router_providers: Default::default(),
             middleware: default_middleware(state),
+            layers: Default::default(),
             initializers: default_initializers(state),
+            custom_fallback: false,
         }
     }
 
@@ -83,8 +105,13 @@ where
             api_router: ApiRouter::<S>::new(),
             #[cfg(feature = "open-api")]
             api_docs: Box::new(|op| op),
+            router_providers: Default::default(),
+            #[cfg(feature = "open-api")]
+            api_router_providers: Default::default(),
             middleware: Default::default(),
+            layers: Default::default(),
             initializers: Default::default(),
+            custom_fallback: false,
         }
     }
 
@@ -99,6 +126,29 @@ where
         self
     }
 
+    /// Register a function that provides additional routes. Unlike [Self::router], the function
+    /// is not called until the [HttpService] is built, and it's called with the app's final
+    /// state. This allows the routes to close over values from the state (e.g., an API client)
+    /// instead of extracting them from the state on each request.
+    pub fn router_provider<F>(mut self, provider: F) -> Self
+    where
+        F: FnOnce(&S) -> Router<S> + Send + 'static,
+    {
+        self.router_providers.push(Box::new(provider));
+        self
+    }
+
+    /// Same as [Self::router_provider], but for an [ApiRouter], so the provided routes are
+    /// included in the generated OpenAPI schema.
+    #[cfg(feature = "open-api")]
+    pub fn api_router_provider<F>(mut self, provider: F) -> Self
+    where
+        F: FnOnce(&S) -> ApiRouter<S> + Send + 'static,
+    {
+        self.api_router_providers.push(Box::new(provider));
+        self
+    }
+
     #[cfg(feature = "open-api")]
     pub fn api_docs(
         mut self,
@@ -108,6 +158,44 @@ where
         self
     }
 
+    /// Set the handler to use for requests that don't match any of the app's routes. If not
+    /// provided, a default handler will be used that returns a `404 Not Found` response with a
+    /// JSON [HttpError] body.
+    pub fn fallback<H, T>(mut self, handler: H) -> Self
+    where
+        H: Handler<T, S>,
+        T: 'static,
+    {
+        self.router = self.router.fallback(handler);
+        self.custom_fallback = true;
+        self
+    }
+
+    /// Serve the file at the given path for requests that don't match any of the app's routes.
+    /// This is useful for hosting a single page app (SPA), where all unmatched routes should
+    /// serve the app's `index.html` file.
+    pub fn fallback_serve_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.router = self.router.fallback_service(ServeFile::new(path));
+        self.custom_fallback = true;
+        self
+    }
+
+    /// Add the fields returned by the provided function to the span created by the
+    /// [TracingMiddleware] for each request. This replaces the default [TracingMiddleware],
+    /// if it's enabled. See [crate::service::http::middleware::tracing::ExtraSpanFields] for
+    /// more details.
+    pub fn tracing_extra_span_fields<F>(mut self, extra_span_fields: F) -> Self
+    where
+        F: Fn(&HeaderMap, &Extensions) -> Vec<(&'static str, String)> + Send + Sync + 'static,
+    {
+        let middleware = TracingMiddleware::with_extra_span_fields(extra_span_fields);
+        if Middleware::<S>::enabled(&middleware, &self.state) {
+            self.middleware
+                .insert(Middleware::<S>::name(&middleware), Box::new(middleware));
+        }
+        self
+    }
+
     pub fn initializer<T>(mut self, initializer: T) -> RoadsterResult<Self>
     where
         T: Initializer<S> + 'static,
@@ -143,6 +231,28 @@ where
         }
         Ok(self)
     }
+
+    /// Add an arbitrary [tower::Layer] to the router, e.g. an [axum::Extension] with a shared
+    /// client or a `tower-http` middleware. This is a lighter-weight alternative to implementing
+    /// the [Middleware] trait for layers that don't need to be configurable or toggled via the
+    /// app's config.
+    ///
+    /// Layers are installed after all of the [Middleware] (and before the
+    /// [Initializer::after_middleware] hooks), so they wrap the built-in middleware and run
+    /// before it when handling a request. If multiple layers are added, they're installed in the
+    /// order they were added, so the last layer added runs first.
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route> + Clone + Send + 'static,
+        L::Service: Service<Request> + Clone + Send + 'static,
+        <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        self.layers
+            .push(Box::new(move |router| router.layer(layer)));
+        self
+    }
 }
 
 #[async_trait]
@@ -161,13 +271,27 @@ where
     }
 
     async fn build(self, state: &S) -> RoadsterResult<HttpService> {
-        let router = self.router;
+        let context = AppContext::from_ref(state);
+        set_error_log_level(context.config().service.http.custom.error_log_level.clone());
+
+        let router = self
+            .router_providers
+            .into_iter()
+            .fold(self.router, |router, provider| {
+                router.merge(provider(state))
+            });
 
         #[cfg(feature = "open-api")]
         let (router, api) = {
             let mut api = OpenApi::default();
             let api_docs = self.api_docs;
-            let api_router = self.api_router.finish_api_with(&mut api, api_docs);
+            let api_router = self
+                .api_router_providers
+                .into_iter()
+                .fold(self.api_router, |router, provider| {
+                    router.merge(provider(state))
+                })
+                .finish_api_with(&mut api, api_docs);
             let router = router.merge(api_router);
             // Arc is very important here or we will face massive memory and performance issues
             let api = Arc::new(api);
@@ -175,6 +299,12 @@ where
             (router, api)
         };
 
+        let router = if self.custom_fallback {
+            router
+        } else {
+            router.fallback(default_fallback)
+        };
+
         let router = router.with_state::<()>(state.clone());
 
         let initializers = self
@@ -209,6 +339,11 @@ where
                 middleware.install(router, state)
             })?;
 
+        let router = self
+            .layers
+            .into_iter()
+            .fold(router, |router, layer| layer(router));
+
         let router = initializers
             .iter()
             .try_fold(router, |router, initializer| {
@@ -221,6 +356,15 @@ where
                 initializer.before_serve(router, state)
             })?;
 
+        // Installed last so it applies to errors returned by any route, middleware, or
+        // initializer.
+        let router =
+            if context.config().service.http.custom.error_format == ErrorFormat::ProblemJson {
+                router.layer(axum::middleware::from_fn(render_problem_details))
+            } else {
+                router
+            };
+
         let service = HttpService {
             router,
             #[cfg(feature = "open-api")]
@@ -231,12 +375,27 @@ where
     }
 }
 
+/// The default fallback handler, used for requests that don't match any of the app's routes.
+async fn default_fallback() -> HttpError {
+    HttpError::not_found().error("Not found")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::app::context::AppContext;
+    use crate::app::MockApp;
+    use crate::config::app_config::AppConfig;
     use crate::service::http::initializer::MockInitializer;
+    use crate::service::http::middleware::compression::ResponseCompressionMiddleware;
     use crate::service::http::middleware::MockMiddleware;
+    use axum::body::{to_bytes, Body};
+    use axum::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE};
+    use axum::http::{HeaderName, HeaderValue, Request, StatusCode};
+    use axum::response::Response;
+    use axum::routing::get;
+    use tower::ServiceExt;
+    use tower_http::set_header::SetResponseHeaderLayer;
 
     #[test]
     #[cfg_attr(coverage_nightly, coverage(off))]
@@ -347,4 +506,164 @@ mod tests {
         // Act
         builder.initializer(initializer).unwrap();
     }
+
+    async fn request(builder: HttpServiceBuilder<AppContext>, context: &AppContext) -> Response {
+        let service = AppServiceBuilder::<MockApp<AppContext>, AppContext, HttpService>::build(
+            builder, context,
+        )
+        .await
+        .unwrap();
+        service
+            .router
+            .oneshot(Request::get("/unknown").body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn fallback_default() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let builder = HttpServiceBuilder::<AppContext>::empty(&context);
+
+        // Act
+        let response = request(builder, &context).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "Not found");
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn fallback_custom() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let builder = HttpServiceBuilder::<AppContext>::empty(&context)
+            .fallback(|| async { (StatusCode::IM_A_TEAPOT, "custom") });
+
+        // Act
+        let response = request(builder, &context).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::IM_A_TEAPOT);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "custom");
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn router_provider() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let builder = HttpServiceBuilder::<AppContext>::empty(&context).router_provider(|state| {
+            let name = state.config().app.name.clone();
+            Router::new().route(
+                "/name",
+                get(move || {
+                    let name = name.clone();
+                    async move { name }
+                }),
+            )
+        });
+        let service = AppServiceBuilder::<MockApp<AppContext>, AppContext, HttpService>::build(
+            builder, &context,
+        )
+        .await
+        .unwrap();
+
+        // Act
+        let response = service
+            .router
+            .oneshot(Request::get("/name").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, context.config().app.name);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn problem_details_with_compression() {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.service.http.custom.error_format = ErrorFormat::ProblemJson;
+        config.service.http.custom.middleware.default_enable = false;
+        config
+            .service
+            .http
+            .custom
+            .middleware
+            .response_compression
+            .common
+            .enable = Some(true);
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let builder = HttpServiceBuilder::<AppContext>::empty(&context)
+            .router_provider(|_| {
+                Router::new().route(
+                    "/error",
+                    get(|| async {
+                        HttpError::unprocessable_entity()
+                            .error("Invalid request")
+                            .details("A long enough explanation that the response is compressed")
+                    }),
+                )
+            })
+            .middleware(ResponseCompressionMiddleware)
+            .unwrap();
+        let service = AppServiceBuilder::<MockApp<AppContext>, AppContext, HttpService>::build(
+            builder, &context,
+        )
+        .await
+        .unwrap();
+
+        // Act
+        let response = service
+            .router
+            .oneshot(
+                Request::get("/error")
+                    .header(ACCEPT_ENCODING, "gzip")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(!response.headers().contains_key(CONTENT_ENCODING));
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/problem+json"
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["status"], 422);
+        assert_eq!(body["instance"], "/error");
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn layer() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let builder = HttpServiceBuilder::<AppContext>::empty(&context).layer(
+            SetResponseHeaderLayer::overriding(
+                HeaderName::from_static("x-custom"),
+                HeaderValue::from_static("foo"),
+            ),
+        );
+
+        // Act
+        let response = request(builder, &context).await;
+
+        // Assert
+        assert_eq!(response.headers().get("x-custom").unwrap(), "foo");
+    }
 }
@@ -1,5 +1,6 @@
 use crate::app::context::AppContext;
 use crate::service::http::initializer::normalize_path::NormalizePathInitializer;
+use crate::service::http::initializer::static_files::StaticFilesInitializer;
 use crate::service::http::initializer::Initializer;
 use axum::extract::FromRef;
 use std::collections::BTreeMap;
@@ -9,7 +10,10 @@ where
     S: Clone + Send + Sync + 'static,
     AppContext: FromRef<S>,
 {
-    let initializers: Vec<Box<dyn Initializer<S>>> = vec![Box::new(NormalizePathInitializer)];
+    let initializers: Vec<Box<dyn Initializer<S>>> = vec![
+        Box::new(NormalizePathInitializer),
+        Box::new(StaticFilesInitializer),
+    ];
     initializers
         .into_iter()
         .filter(|initializer| initializer.enabled(state))
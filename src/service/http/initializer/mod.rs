@@ -1,5 +1,6 @@
 pub mod default;
 pub mod normalize_path;
+pub mod static_files;
 
 use crate::app::context::AppContext;
 use crate::error::RoadsterResult;
@@ -8,6 +8,7 @@ use tower::Layer;
 use tower_http::normalize_path::NormalizePathLayer;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct NormalizePathConfig {}
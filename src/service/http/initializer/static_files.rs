@@ -0,0 +1,318 @@
+use crate::app::context::AppContext;
+use crate::error::RoadsterResult;
+use crate::service::http::initializer::Initializer;
+use anyhow::anyhow;
+use axum::extract::FromRef;
+use axum::http::header::CACHE_CONTROL;
+use axum::http::HeaderValue;
+use axum::Router;
+use serde_derive::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tower_http::services::{ServeDir, ServeFile};
+use tower_http::set_header::SetResponseHeaderLayer;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", default)]
+#[non_exhaustive]
+pub struct StaticFilesConfig {
+    /// The URL path prefix to serve the static files from. Must start with `/`, and must not
+    /// contain path parameters or wildcards. If this is `/`, the static files are served for any
+    /// request that doesn't match another route (instead of the default `404 Not Found` fallback).
+    pub route: String,
+
+    /// The directory containing the static files.
+    pub dir: PathBuf,
+
+    /// The file to serve if the requested file does not exist, e.g. the `index.html` file of a
+    /// single page app (SPA). If not provided, a `404 Not Found` response is returned instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<PathBuf>,
+
+    /// The value of the `Cache-Control` header to add to the responses, e.g.
+    /// `public, max-age=3600`. If not provided, the header is not added.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<String>,
+}
+
+impl Default for StaticFilesConfig {
+    fn default() -> Self {
+        Self {
+            route: "/static".to_string(),
+            dir: PathBuf::from("static"),
+            fallback: None,
+            cache_control: None,
+        }
+    }
+}
+
+/// Serve the static files in the configured directory. Requests for paths outside of the
+/// directory (e.g., using `..` path segments) are rejected with a `404 Not Found` response.
+pub struct StaticFilesInitializer;
+
+impl<S> Initializer<S> for StaticFilesInitializer
+where
+    S: Clone + Send + Sync + 'static,
+    AppContext: FromRef<S>,
+{
+    fn name(&self) -> String {
+        "static-files".to_string()
+    }
+
+    fn enabled(&self, state: &S) -> bool {
+        AppContext::from_ref(state)
+            .config()
+            .service
+            .http
+            .custom
+            .initializer
+            .static_files
+            .common
+            .enabled(state)
+    }
+
+    fn priority(&self, state: &S) -> i32 {
+        AppContext::from_ref(state)
+            .config()
+            .service
+            .http
+            .custom
+            .initializer
+            .static_files
+            .common
+            .priority
+    }
+
+    fn after_router(&self, router: Router, state: &S) -> RoadsterResult<Router> {
+        let context = AppContext::from_ref(state);
+        let config = &context
+            .config()
+            .service
+            .http
+            .custom
+            .initializer
+            .static_files
+            .custom;
+
+        validate_route(&config.route)?;
+
+        let serve_dir = ServeDir::new(&config.dir);
+        let static_router = if let Some(fallback) = config.fallback.as_ref() {
+            Router::new().fallback_service(serve_dir.fallback(ServeFile::new(fallback)))
+        } else {
+            Router::new().fallback_service(serve_dir)
+        };
+
+        let static_router = if let Some(cache_control) = config.cache_control.as_ref() {
+            let cache_control = HeaderValue::from_str(cache_control).map_err(|err| {
+                anyhow!("Invalid static files cache-control header `{cache_control}`: {err}")
+            })?;
+            static_router.layer(SetResponseHeaderLayer::if_not_present(
+                CACHE_CONTROL,
+                cache_control,
+            ))
+        } else {
+            static_router
+        };
+
+        // Axum doesn't support nesting at the root path, so the static files are served as the
+        // router's fallback instead. This replaces any existing fallback, e.g. the default
+        // `404 Not Found` handler.
+        let router = if config.route == "/" {
+            router.fallback_service(static_router)
+        } else {
+            router.nest_service(&config.route, static_router)
+        };
+
+        Ok(router)
+    }
+}
+
+/// Check that the route is a static path that Axum can nest a service at. Axum panics when
+/// building the router if the path is invalid, so we check it up front in order to return an
+/// error with a more helpful message instead.
+fn validate_route(route: &str) -> RoadsterResult<()> {
+    if !route.starts_with('/') {
+        return Err(
+            anyhow!("Invalid static files route `{route}`: the route must start with `/`").into(),
+        );
+    }
+    if route.contains(['*', ':', '{', '}']) {
+        return Err(anyhow!(
+            "Invalid static files route `{route}`: the route must not contain path parameters or wildcards"
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::app_config::AppConfig;
+    use crate::util::test_util::TempDir;
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use axum::routing::get;
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    #[rstest]
+    #[case(true, None, true)]
+    #[case(true, Some(false), false)]
+    #[case(false, Some(true), true)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn static_files_enabled(
+        #[case] default_enable: bool,
+        #[case] enable: Option<bool>,
+        #[case] expected_enabled: bool,
+    ) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.service.http.custom.initializer.default_enable = default_enable;
+        config
+            .service
+            .http
+            .custom
+            .initializer
+            .static_files
+            .common
+            .enable = enable;
+
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        let initializer = StaticFilesInitializer;
+
+        // Act/Assert
+        assert_eq!(initializer.enabled(&context), expected_enabled);
+    }
+
+    #[rstest]
+    #[case("/static", true)]
+    #[case("/", true)]
+    #[case("/assets/static", true)]
+    #[case("", false)]
+    #[case("static", false)]
+    #[case("/static/*path", false)]
+    #[case("/static/:id", false)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn validate_route(#[case] route: &str, #[case] expected_valid: bool) {
+        assert_eq!(super::validate_route(route).is_ok(), expected_valid);
+    }
+
+    #[rstest]
+    #[case("/static/index.html", None, StatusCode::OK, Some("index"))]
+    #[case("/static/missing.html", None, StatusCode::NOT_FOUND, None)]
+    #[case(
+        "/static/missing.html",
+        Some("index.html"),
+        StatusCode::OK,
+        Some("index")
+    )]
+    #[case("/static/../secret.txt", None, StatusCode::NOT_FOUND, None)]
+    #[case("/static/%2e%2e/secret.txt", None, StatusCode::NOT_FOUND, None)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn static_files_after_router(
+        #[case] path: &str,
+        #[case] fallback: Option<&str>,
+        #[case] expected_status: StatusCode,
+        #[case] expected_body: Option<&str>,
+    ) {
+        // Arrange
+        let root = TempDir::new();
+        root.write("secret.txt", "secret");
+        let dir = root.path().join("static");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("index.html"), "index").unwrap();
+
+        let mut config = AppConfig::test(None).unwrap();
+        let static_files = &mut config.service.http.custom.initializer.static_files.custom;
+        static_files.dir.clone_from(&dir);
+        static_files.fallback = fallback.map(|fallback| dir.join(fallback));
+        static_files.cache_control = Some("public, max-age=60".to_string());
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let router = StaticFilesInitializer
+            .after_router(Router::new(), &context)
+            .unwrap();
+
+        // Act
+        let response = router
+            .oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), expected_status);
+        if let Some(expected_body) = expected_body {
+            assert_eq!(
+                response.headers().get(CACHE_CONTROL).unwrap(),
+                "public, max-age=60"
+            );
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert_eq!(body, expected_body);
+        }
+    }
+
+    #[rstest]
+    #[case("/index.html", StatusCode::OK, Some("index"))]
+    #[case("/missing.html", StatusCode::NOT_FOUND, None)]
+    #[case("/api/foo", StatusCode::OK, Some("foo"))]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn static_files_after_router_root(
+        #[case] path: &str,
+        #[case] expected_status: StatusCode,
+        #[case] expected_body: Option<&str>,
+    ) {
+        // Arrange
+        let dir = TempDir::new();
+        dir.write("index.html", "index");
+
+        let mut config = AppConfig::test(None).unwrap();
+        let static_files = &mut config.service.http.custom.initializer.static_files.custom;
+        static_files.route = "/".to_string();
+        static_files.dir = dir.path().to_path_buf();
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let router = Router::new().route("/api/foo", get(|| async { "foo" }));
+        let router = StaticFilesInitializer
+            .after_router(router, &context)
+            .unwrap();
+
+        // Act
+        let response = router
+            .oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        // Assert
+        // Other routes take precedence over the static files mounted at the root
+        assert_eq!(response.status(), expected_status);
+        if let Some(expected_body) = expected_body {
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert_eq!(body, expected_body);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn static_files_after_router_invalid_route() {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config
+            .service
+            .http
+            .custom
+            .initializer
+            .static_files
+            .custom
+            .route = "static".to_string();
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        // Act
+        let result = StaticFilesInitializer.after_router(Router::new(), &context);
+
+        // Assert
+        assert!(result.is_err());
+    }
+}
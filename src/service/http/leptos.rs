@@ -0,0 +1,162 @@
+use crate::app::context::AppContext;
+use crate::service::http::builder::HttpServiceBuilder;
+use crate::service::http::service::HttpService;
+use axum::body::Body;
+use axum::extract::{FromRef, State};
+use axum::http::{Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use leptos::{provide_context, IntoView, LeptosOptions};
+use leptos_axum::{generate_route_list, render_app_to_stream, LeptosRoutes};
+use tower::ServiceExt;
+use tower_http::services::ServeDir;
+use tracing::error;
+
+impl HttpService {
+    /// Create a new [HttpServiceBuilder] that serves a [Leptos](https://github.com/leptos-rs/leptos)
+    /// app using server side rendering (SSR). In addition to the routes added by
+    /// [HttpService::builder], this mounts the following:
+    ///
+    /// 1. The routes defined by the Leptos app's router, along with the app's server functions.
+    /// 2. A fallback that serves the static assets from the
+    ///    [site root][LeptosOptions::site_root], or renders the Leptos app if no static asset
+    ///    exists for the request's path (e.g., to render the app's "not found" page).
+    ///
+    /// The app state will be provided to the Leptos app (and its server functions) via
+    /// [provide_context], so it can be accessed using `leptos::use_context::<S>()`.
+    pub fn builder_leptos<S, F, IV>(
+        path_root: Option<&str>,
+        state: &S,
+        app_fn: F,
+    ) -> HttpServiceBuilder<S>
+    where
+        S: Clone + Send + Sync + 'static,
+        AppContext: FromRef<S>,
+        LeptosOptions: FromRef<S>,
+        F: Fn() -> IV + Clone + Send + 'static,
+        IV: IntoView + 'static,
+    {
+        HttpServiceBuilder::new(path_root, state)
+            .router(leptos_router(state, app_fn.clone()))
+            .fallback(
+                move |State(options): State<LeptosOptions>, request: Request<Body>| {
+                    file_and_error_handler(options, request, app_fn.clone())
+                },
+            )
+    }
+}
+
+fn leptos_router<S, F, IV>(state: &S, app_fn: F) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    LeptosOptions: FromRef<S>,
+    F: Fn() -> IV + Clone + Send + 'static,
+    IV: IntoView + 'static,
+{
+    let routes = generate_route_list(app_fn.clone());
+    let context_state = state.clone();
+    Router::<S>::new().leptos_routes_with_context(
+        state,
+        routes,
+        move || provide_context(context_state.clone()),
+        app_fn,
+    )
+}
+
+/// Serve the static file for the request's path from the Leptos site root if it exists, otherwise
+/// render the Leptos app.
+async fn file_and_error_handler<F, IV>(
+    options: LeptosOptions,
+    request: Request<Body>,
+    app_fn: F,
+) -> Response
+where
+    F: Fn() -> IV + Clone + Send + 'static,
+    IV: IntoView + 'static,
+{
+    let (parts, body) = request.into_parts();
+
+    // Only forward the `accept-encoding` header so `ServeDir` can serve pre-compressed files.
+    let mut static_parts = parts.clone();
+    static_parts.headers.clear();
+    if let Some(encodings) = parts.headers.get("accept-encoding") {
+        static_parts
+            .headers
+            .insert("accept-encoding", encodings.clone());
+    }
+
+    let static_file = ServeDir::new(&options.site_root)
+        .precompressed_gzip()
+        .precompressed_br()
+        .oneshot(Request::from_parts(static_parts, Body::empty()))
+        .await;
+
+    match static_file {
+        Ok(response) if response.status() == StatusCode::OK => response.into_response(),
+        Ok(_) => {
+            let handler = render_app_to_stream(options, app_fn);
+            handler(Request::from_parts(parts, body))
+                .await
+                .into_response()
+        }
+        Err(err) => {
+            error!(%err, "Error serving static file");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::task::LocalSet;
+
+    #[derive(Clone, FromRef)]
+    struct TestState {
+        context: AppContext,
+        leptos_options: LeptosOptions,
+    }
+
+    fn test_app() -> impl IntoView {
+        "Hello from Leptos"
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn leptos_root_route() {
+        // Leptos uses `spawn_local` to render the app, so the test needs to run in a `LocalSet`.
+        LocalSet::new()
+            .run_until(async {
+                // Arrange
+                let state = TestState {
+                    context: AppContext::test(None, None, None).unwrap(),
+                    leptos_options: LeptosOptions::builder()
+                        .output_name("test")
+                        .site_root("target/leptos-test-site")
+                        .build(),
+                };
+                let router = leptos_router(&state, test_app)
+                    .fallback(
+                        |State(options): State<LeptosOptions>, request: Request<Body>| {
+                            file_and_error_handler(options, request, test_app)
+                        },
+                    )
+                    .with_state(state);
+
+                // Act
+                let response = router
+                    .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+
+                // Assert
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                let body = String::from_utf8(body.to_vec()).unwrap();
+                assert!(body.contains("Hello from Leptos"));
+            })
+            .await;
+    }
+}
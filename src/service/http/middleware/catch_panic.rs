@@ -8,6 +8,7 @@ use tower_http::catch_panic::CatchPanicLayer;
 use validator::Validate;
 
 #[derive(Debug, Clone, Default, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct CatchPanicConfig {}
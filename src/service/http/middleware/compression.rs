@@ -10,11 +10,13 @@ use tower_http::decompression::RequestDecompressionLayer;
 use validator::Validate;
 
 #[derive(Debug, Clone, Default, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct ResponseCompressionConfig {}
 
 #[derive(Debug, Clone, Default, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct RequestDecompressionConfig {}
@@ -15,6 +15,7 @@ use validator::Validate;
 #[serde_as]
 #[skip_serializing_none]
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct CorsConfig {
@@ -34,6 +35,7 @@ pub struct CorsConfig {
     /// See <https://docs.rs/tower-http/latest/tower_http/cors/struct.CorsLayer.html#method.max_age>
     #[serde(default = "default_max_age")]
     #[serde_as(as = "serde_with::DurationMilliSeconds")]
+    #[cfg_attr(feature = "config-schema", schemars(with = "u64"))]
     pub max_age: Duration,
 
     /// See <https://docs.rs/tower-http/latest/tower_http/cors/struct.CorsLayer.html#method.allow_headers>
@@ -63,6 +65,7 @@ fn default_max_age() -> Duration {
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub enum CorsPreset {
@@ -76,6 +79,7 @@ pub enum CorsPreset {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "kebab-case")]
 #[non_exhaustive]
 pub enum CorsAllowHeaders {
@@ -86,6 +90,7 @@ pub enum CorsAllowHeaders {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "kebab-case")]
 #[non_exhaustive]
 pub enum CorsAllowMethods {
@@ -98,6 +103,7 @@ pub enum CorsAllowMethods {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "kebab-case")]
 #[non_exhaustive]
 pub enum CorsAllowOrigins {
@@ -110,6 +116,7 @@ pub enum CorsAllowOrigins {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "kebab-case")]
 #[non_exhaustive]
 pub enum CorsExposeHeaders {
@@ -2,6 +2,8 @@ use crate::app::context::AppContext;
 use crate::service::http::middleware::catch_panic::CatchPanicMiddleware;
 use crate::service::http::middleware::compression::RequestDecompressionMiddleware;
 use crate::service::http::middleware::cors::CorsMiddleware;
+#[cfg(feature = "http-etag")]
+use crate::service::http::middleware::etag::ETagMiddleware;
 use crate::service::http::middleware::request_id::{
     PropagateRequestIdMiddleware, SetRequestIdMiddleware,
 };
@@ -20,18 +22,21 @@ where
     S: Clone + Send + Sync + 'static,
     AppContext: FromRef<S>,
 {
-    let middleware: Vec<Box<dyn Middleware<S>>> = vec![
+    #[allow(unused_mut)] // Only mutated in some feature combinations
+    let mut middleware: Vec<Box<dyn Middleware<S>>> = vec![
         Box::new(SensitiveRequestHeadersMiddleware),
         Box::new(SensitiveResponseHeadersMiddleware),
         Box::new(SetRequestIdMiddleware),
         Box::new(PropagateRequestIdMiddleware),
-        Box::new(TracingMiddleware),
+        Box::new(TracingMiddleware::new()),
         Box::new(CatchPanicMiddleware),
         Box::new(RequestDecompressionMiddleware),
         Box::new(TimeoutMiddleware),
         Box::new(RequestBodyLimitMiddleware),
         Box::new(CorsMiddleware),
     ];
+    #[cfg(feature = "http-etag")]
+    middleware.push(Box::new(ETagMiddleware));
     middleware
         .into_iter()
         .filter(|middleware| middleware.enabled(state))
@@ -0,0 +1,384 @@
+use crate::app::context::AppContext;
+use crate::error::api::http::HttpError;
+use crate::error::RoadsterResult;
+use crate::service::http::middleware::Middleware;
+use axum::body::{to_bytes, Body, HttpBody};
+use axum::extract::{FromRef, Request, State};
+use axum::http::header::{CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use byte_unit::Byte;
+use byte_unit::Unit::MB;
+use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use validator::Validate;
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", default)]
+#[non_exhaustive]
+pub struct ETagConfig {
+    /// Whether to generate weak ETags (`W/"..."`) instead of strong ETags.
+    pub weak: bool,
+
+    /// The maximum size of a response body to generate an ETag for. ETags are not generated for
+    /// larger responses, or for responses whose size is not known in advance (e.g., streaming
+    /// responses).
+    #[cfg_attr(feature = "config-schema", schemars(with = "String"))]
+    pub max_size: Byte,
+
+    /// The response `Content-Type`s (without parameters such as `charset`) to generate ETags for.
+    pub content_types: Vec<String>,
+}
+
+impl Default for ETagConfig {
+    fn default() -> Self {
+        Self {
+            weak: false,
+            max_size: Byte::from_u64_with_unit(1, MB).unwrap(),
+            content_types: vec![
+                "application/json".to_string(),
+                "text/html".to_string(),
+                "text/plain".to_string(),
+            ],
+        }
+    }
+}
+
+impl ETagConfig {
+    fn allowed_content_type(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .map(|content_type| {
+                self.content_types
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(content_type.trim()))
+            })
+            .unwrap_or_default()
+    }
+
+    fn etag(&self, body: &[u8]) -> String {
+        let hash = Sha256::digest(body);
+        if self.weak {
+            format!(r#"W/"{hash:x}""#)
+        } else {
+            format!(r#""{hash:x}""#)
+        }
+    }
+}
+
+/// Middleware that generates an `ETag` header for `GET` and `HEAD` requests by hashing the
+/// response body, and responds with `304 Not Modified` if the request's `If-None-Match` header
+/// matches the `ETag`. If the handler already set an `ETag`, that value is used instead.
+///
+/// Note: This middleware buffers the response body in order to hash it. It should run before
+/// (i.e., have a lower [priority][Middleware::priority] than) any middleware that modifies the
+/// response body, e.g. response compression. Middleware with a lower priority handles the
+/// response last, so the ETag is then generated from the body that's actually sent to the client
+/// (e.g., the compressed body), and different encodings of a response get different ETags.
+pub struct ETagMiddleware;
+impl<S> Middleware<S> for ETagMiddleware
+where
+    S: Clone + Send + Sync + 'static,
+    AppContext: FromRef<S>,
+{
+    fn name(&self) -> String {
+        "etag".to_string()
+    }
+
+    fn enabled(&self, state: &S) -> bool {
+        AppContext::from_ref(state)
+            .config()
+            .service
+            .http
+            .custom
+            .middleware
+            .etag
+            .common
+            .enabled(state)
+    }
+
+    fn priority(&self, state: &S) -> i32 {
+        AppContext::from_ref(state)
+            .config()
+            .service
+            .http
+            .custom
+            .middleware
+            .etag
+            .common
+            .priority
+    }
+
+    fn install(&self, router: Router, state: &S) -> RoadsterResult<Router> {
+        let context = AppContext::from_ref(state);
+        let config = context
+            .config()
+            .service
+            .http
+            .custom
+            .middleware
+            .etag
+            .custom
+            .clone();
+
+        let router = router.layer(axum::middleware::from_fn_with_state(Arc::new(config), etag));
+
+        Ok(router)
+    }
+}
+
+async fn etag(State(config): State<Arc<ETagConfig>>, request: Request, next: Next) -> Response {
+    if request.method() != Method::GET && request.method() != Method::HEAD {
+        return next.run(request).await;
+    }
+
+    let if_none_match = request.headers().get(IF_NONE_MATCH).cloned();
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK || !config.allowed_content_type(response.headers()) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = if parts.headers.contains_key(ETAG) {
+        body
+    } else {
+        let max_size = config.max_size.as_u64();
+        match body.size_hint().upper() {
+            Some(size) if size <= max_size => {}
+            _ => return Response::from_parts(parts, body),
+        }
+        let bytes = match to_bytes(body, usize::try_from(max_size).unwrap_or(usize::MAX)).await {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                return HttpError::internal_server_error()
+                    .error("Unable to read response body")
+                    .source(err)
+                    .into_response()
+            }
+        };
+        match HeaderValue::try_from(config.etag(&bytes)) {
+            Ok(etag) => {
+                parts.headers.insert(ETAG, etag);
+            }
+            Err(err) => {
+                return HttpError::internal_server_error()
+                    .error("Unable to create ETag header")
+                    .source(err)
+                    .into_response()
+            }
+        }
+        Body::from(bytes)
+    };
+
+    let not_modified = if_none_match
+        .zip(parts.headers.get(ETAG))
+        .map(|(if_none_match, etag)| etag_matches(&if_none_match, etag))
+        .unwrap_or_default();
+    if not_modified {
+        parts.status = StatusCode::NOT_MODIFIED;
+        parts.headers.remove(CONTENT_LENGTH);
+        parts.headers.remove(CONTENT_TYPE);
+        return Response::from_parts(parts, Body::empty());
+    }
+
+    Response::from_parts(parts, body)
+}
+
+/// Check whether any of the entity tags in the `If-None-Match` header match the `ETag`. Uses the
+/// weak comparison, as required for `If-None-Match`.
+/// See: <https://www.rfc-editor.org/rfc/rfc9110#section-13.1.2>
+fn etag_matches(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let (Ok(if_none_match), Ok(etag)) = (if_none_match.to_str(), etag.to_str()) else {
+        return false;
+    };
+    let etag = opaque_tag(etag);
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || opaque_tag(tag) == etag)
+}
+
+fn opaque_tag(tag: &str) -> &str {
+    let tag = tag.trim();
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::app_config::AppConfig;
+    use axum::routing::{get, post};
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    #[rstest]
+    #[case(false, Some(true), true)]
+    #[case(false, Some(false), false)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn etag_enabled(
+        #[case] default_enable: bool,
+        #[case] enable: Option<bool>,
+        #[case] expected_enabled: bool,
+    ) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.service.http.custom.middleware.default_enable = default_enable;
+        config.service.http.custom.middleware.etag.common.enable = enable;
+
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        let middleware = ETagMiddleware;
+
+        // Act/Assert
+        assert_eq!(middleware.enabled(&context), expected_enabled);
+    }
+
+    #[rstest]
+    #[case(None, -10)]
+    #[case(Some(1234), 1234)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn etag_priority(#[case] override_priority: Option<i32>, #[case] expected_priority: i32) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        if let Some(priority) = override_priority {
+            config.service.http.custom.middleware.etag.common.priority = priority;
+        }
+
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        let middleware = ETagMiddleware;
+
+        // Act/Assert
+        assert_eq!(middleware.priority(&context), expected_priority);
+    }
+
+    #[rstest]
+    #[case(r#""foo""#, r#""foo""#, true)]
+    #[case(r#""foo""#, r#"W/"foo""#, true)]
+    #[case(r#"W/"foo""#, r#""foo""#, true)]
+    #[case(r#""bar", "foo""#, r#""foo""#, true)]
+    #[case("*", r#""foo""#, true)]
+    #[case(r#""bar""#, r#""foo""#, false)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn etag_matches(#[case] if_none_match: &str, #[case] etag: &str, #[case] expected: bool) {
+        assert_eq!(
+            super::etag_matches(
+                &HeaderValue::from_str(if_none_match).unwrap(),
+                &HeaderValue::from_str(etag).unwrap()
+            ),
+            expected
+        );
+    }
+
+    fn router(context: &AppContext) -> Router {
+        let router = Router::new()
+            .route("/json", get(|| async { axum::Json("foo") }))
+            .route("/binary", get(|| async { vec![0u8; 10] }))
+            .route("/post", post(|| async { axum::Json("foo") }));
+        ETagMiddleware.install(router, context).unwrap()
+    }
+
+    #[rstest]
+    #[case(Method::GET, "/json", true)]
+    #[case(Method::GET, "/binary", false)]
+    #[case(Method::POST, "/post", false)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn etag_install(#[case] method: Method, #[case] path: &str, #[case] expect_etag: bool) {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let router = router(&context);
+
+        // Act
+        let response = router
+            .oneshot(
+                axum::http::Request::builder()
+                    .method(method)
+                    .uri(path)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().contains_key(ETAG), expect_etag);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn etag_install_max_size() {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.service.http.custom.middleware.etag.custom.max_size = Byte::from_u64(1);
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let router = router(&context);
+
+        // Act
+        let response = router
+            .oneshot(
+                axum::http::Request::get("/json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(ETAG));
+    }
+
+    #[rstest]
+    #[case(true, StatusCode::NOT_MODIFIED)]
+    #[case(false, StatusCode::OK)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn etag_install_if_none_match(
+        #[case] matching: bool,
+        #[case] expected_status: StatusCode,
+    ) {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let router = router(&context);
+        let response = router
+            .clone()
+            .oneshot(
+                axum::http::Request::get("/json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let etag = response.headers().get(ETAG).unwrap().clone();
+        let if_none_match = if matching {
+            etag.clone()
+        } else {
+            HeaderValue::from_static(r#""other""#)
+        };
+
+        // Act
+        let response = router
+            .oneshot(
+                axum::http::Request::get("/json")
+                    .header(IF_NONE_MATCH, if_none_match)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), expected_status);
+        assert_eq!(response.headers().get(ETAG).unwrap(), etag);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body.is_empty(), matching);
+    }
+}
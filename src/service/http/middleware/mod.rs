@@ -2,6 +2,8 @@ pub mod catch_panic;
 pub mod compression;
 pub mod cors;
 pub mod default;
+#[cfg(feature = "http-etag")]
+pub mod etag;
 pub mod request_id;
 pub mod sensitive_headers;
 pub mod size_limit;
@@ -1,17 +1,23 @@
 use crate::app::context::AppContext;
 use crate::error::RoadsterResult;
 use crate::service::http::middleware::Middleware;
-use axum::extract::FromRef;
-use axum::http::HeaderName;
+use axum::extract::{FromRef, Request};
+use axum::http::{HeaderName, HeaderValue};
 use axum::Router;
 use serde_derive::{Deserialize, Serialize};
 use std::str::FromStr;
-use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::request_id::{
+    MakeRequestId, PropagateRequestIdLayer, RequestId, SetRequestIdLayer,
+};
+#[cfg(feature = "http-request-id-ulid")]
+use ulid::Ulid;
+use uuid::Uuid;
 use validator::Validate;
 
 pub const REQUEST_ID_HEADER_NAME: &str = "request-id";
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct CommonRequestIdConfig {
@@ -26,15 +32,67 @@ impl Default for CommonRequestIdConfig {
     }
 }
 
-#[derive(Debug, Clone, Default, Validate, Serialize, Deserialize)]
+/// The format of the request IDs generated by the [SetRequestIdMiddleware].
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum RequestIdFormat {
+    /// A random (version 4) UUID.
+    #[default]
+    UuidV4,
+    /// A time-ordered (version 7) UUID.
+    UuidV7,
+    /// A [ULID](https://github.com/ulid/spec). Requires the `http-request-id-ulid` feature.
+    #[cfg(feature = "http-request-id-ulid")]
+    Ulid,
+}
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct SetRequestIdConfig {
     #[serde(flatten)]
     pub common: CommonRequestIdConfig,
+    /// The format of the request IDs to generate.
+    pub format: RequestIdFormat,
+    /// Whether to use the request ID from the incoming request's header if one is present. If
+    /// `false`, a new request ID will always be generated, replacing any incoming request ID.
+    /// This may be desirable if the app is exposed directly to the public internet and the
+    /// request IDs provided by clients should not be trusted.
+    pub reuse_existing: bool,
+}
+
+impl Default for SetRequestIdConfig {
+    fn default() -> Self {
+        Self {
+            common: Default::default(),
+            format: Default::default(),
+            reuse_existing: true,
+        }
+    }
+}
+
+/// [MakeRequestId] implementation that generates request IDs using the configured
+/// [RequestIdFormat].
+#[derive(Debug, Clone, Copy)]
+struct MakeRequestIdWithFormat(RequestIdFormat);
+
+impl MakeRequestId for MakeRequestIdWithFormat {
+    fn make_request_id<B>(&mut self, _request: &axum::http::Request<B>) -> Option<RequestId> {
+        let id = match self.0 {
+            RequestIdFormat::UuidV4 => Uuid::new_v4().to_string(),
+            RequestIdFormat::UuidV7 => Uuid::now_v7().to_string(),
+            #[cfg(feature = "http-request-id-ulid")]
+            RequestIdFormat::Ulid => Ulid::new().to_string(),
+        };
+        HeaderValue::from_str(&id).ok().map(RequestId::new)
+    }
 }
 
 #[derive(Debug, Clone, Default, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct PropagateRequestIdConfig {
@@ -78,22 +136,37 @@ where
 
     fn install(&self, router: Router, state: &S) -> RoadsterResult<Router> {
         let context = AppContext::from_ref(state);
-        let header_name = &context
+        let config = &context
             .config()
             .service
             .http
             .custom
             .middleware
             .set_request_id
-            .custom
-            .common
-            .header_name;
+            .custom;
+        let header_name = HeaderName::from_str(&config.common.header_name)?;
 
         let router = router.layer(SetRequestIdLayer::new(
-            HeaderName::from_str(header_name)?,
-            MakeRequestUuid,
+            header_name.clone(),
+            MakeRequestIdWithFormat(config.format),
         ));
 
+        // `SetRequestIdLayer` does not replace an existing request ID, so remove the incoming
+        // request ID before the `SetRequestIdLayer` runs if it should not be reused.
+        let router = if config.reuse_existing {
+            router
+        } else {
+            router.layer(axum::middleware::map_request(
+                move |mut request: Request| {
+                    let header_name = header_name.clone();
+                    async move {
+                        request.headers_mut().remove(&header_name);
+                        request
+                    }
+                },
+            ))
+        };
+
         Ok(router)
     }
 }
@@ -157,7 +230,10 @@ where
 mod tests {
     use super::*;
     use crate::config::app_config::AppConfig;
+    use axum::body::{to_bytes, Body};
+    use axum::routing::get;
     use rstest::rstest;
+    use tower::ServiceExt;
 
     #[rstest]
     #[case(false, Some(true), true)]
@@ -274,4 +350,74 @@ mod tests {
         // Act/Assert
         assert_eq!(middleware.priority(&context), expected_priority);
     }
+
+    #[rstest]
+    #[case(RequestIdFormat::UuidV4, true, None)]
+    #[case(RequestIdFormat::UuidV7, true, None)]
+    #[cfg_attr(
+        feature = "http-request-id-ulid",
+        case(RequestIdFormat::Ulid, true, None)
+    )]
+    #[case(RequestIdFormat::UuidV4, true, Some("incoming-id"))]
+    #[case(RequestIdFormat::UuidV4, false, Some("incoming-id"))]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn set_request_id_install(
+        #[case] format: RequestIdFormat,
+        #[case] reuse_existing: bool,
+        #[case] incoming_id: Option<&str>,
+    ) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        let set_request_id = &mut config.service.http.custom.middleware.set_request_id.custom;
+        set_request_id.format = format;
+        set_request_id.reuse_existing = reuse_existing;
+
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        let router = Router::new().route(
+            "/",
+            get(|request: Request| async move {
+                request
+                    .headers()
+                    .get(REQUEST_ID_HEADER_NAME)
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+            }),
+        );
+        let router = SetRequestIdMiddleware.install(router, &context).unwrap();
+
+        let mut request = axum::http::Request::builder().uri("/");
+        if let Some(incoming_id) = incoming_id {
+            request = request.header(REQUEST_ID_HEADER_NAME, incoming_id);
+        }
+
+        // Act
+        let response = router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        // Assert
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let request_id = String::from_utf8(body.to_vec()).unwrap();
+        match incoming_id {
+            Some(incoming_id) if reuse_existing => assert_eq!(request_id, incoming_id),
+            _ => {
+                assert_ne!(Some(request_id.as_str()), incoming_id);
+                match format {
+                    RequestIdFormat::UuidV4 => {
+                        assert_eq!(Uuid::parse_str(&request_id).unwrap().get_version_num(), 4)
+                    }
+                    RequestIdFormat::UuidV7 => {
+                        assert_eq!(Uuid::parse_str(&request_id).unwrap().get_version_num(), 7)
+                    }
+                    #[cfg(feature = "http-request-id-ulid")]
+                    RequestIdFormat::Ulid => assert!(Ulid::from_string(&request_id).is_ok()),
+                }
+            }
+        }
+    }
 }
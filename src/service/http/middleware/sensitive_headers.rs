@@ -14,6 +14,7 @@ use tower_http::sensitive_headers::{
 use validator::Validate;
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct CommonSensitiveHeadersConfig {
@@ -45,6 +46,7 @@ impl CommonSensitiveHeadersConfig {
 }
 
 #[derive(Debug, Clone, Default, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct SensitiveRequestHeadersConfig {
@@ -53,6 +55,7 @@ pub struct SensitiveRequestHeadersConfig {
 }
 
 #[derive(Debug, Clone, Default, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct SensitiveResponseHeadersConfig {
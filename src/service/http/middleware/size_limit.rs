@@ -12,9 +12,11 @@ use tower_http::limit::RequestBodyLimitLayer;
 use validator::Validate;
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct SizeLimitConfig {
+    #[cfg_attr(feature = "config-schema", schemars(with = "String"))]
     pub limit: Byte,
 }
 
@@ -1,31 +1,64 @@
 use crate::app::context::AppContext;
+use crate::error::api::http::HttpError;
 use crate::error::RoadsterResult;
 use crate::service::http::middleware::Middleware;
-use axum::extract::FromRef;
+use axum::extract::{FromRef, MatchedPath, Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
 use axum::Router;
 use serde_derive::{Deserialize, Serialize};
 use serde_with::serde_as;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use std::time::Duration;
-use tower_http::timeout::TimeoutLayer;
+use tracing::warn;
 use validator::Validate;
 
 #[serde_as]
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct TimeoutConfig {
+    /// The maximum amount of time to spend handling a request. If a request takes longer than
+    /// this, a `504 Gateway Timeout` response will be returned.
     #[serde_as(as = "serde_with::DurationMilliSeconds")]
+    #[cfg_attr(feature = "config-schema", schemars(with = "u64"))]
     pub timeout: Duration,
+
+    /// Per-path overrides of the `timeout`. The keys are the route paths as they were registered
+    /// with the router, e.g. `/api/user/:id`.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [service.http.middleware.timeout.path-overrides]
+    /// "/api/report" = 60000
+    /// ```
+    #[serde_as(as = "BTreeMap<_, serde_with::DurationMilliSeconds>")]
+    #[cfg_attr(feature = "config-schema", schemars(with = "BTreeMap<String, u64>"))]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub path_overrides: BTreeMap<String, Duration>,
 }
 
 impl Default for TimeoutConfig {
     fn default() -> Self {
         Self {
             timeout: Duration::from_secs(10),
+            path_overrides: Default::default(),
         }
     }
 }
 
+impl TimeoutConfig {
+    fn timeout_for_path(&self, path: &str) -> Duration {
+        self.path_overrides
+            .get(path)
+            .copied()
+            .unwrap_or(self.timeout)
+    }
+}
+
 pub struct TimeoutMiddleware;
 impl<S> Middleware<S> for TimeoutMiddleware
 where
@@ -62,7 +95,7 @@ where
 
     fn install(&self, router: Router, state: &S) -> RoadsterResult<Router> {
         let context = AppContext::from_ref(state);
-        let timeout = &context
+        let config = context
             .config()
             .service
             .http
@@ -70,19 +103,49 @@ where
             .middleware
             .timeout
             .custom
-            .timeout;
+            .clone();
 
-        let router = router.layer(TimeoutLayer::new(*timeout));
+        let router = router.layer(axum::middleware::from_fn_with_state(
+            Arc::new(config),
+            timeout,
+        ));
 
         Ok(router)
     }
 }
 
+async fn timeout(
+    State(config): State<Arc<TimeoutConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let duration = config.timeout_for_path(&path);
+
+    match tokio::time::timeout(duration, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            warn!(path, timeout = ?duration, "Request timed out");
+            HttpError::gateway_timeout()
+                .error("Request timed out")
+                .into_response()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::app_config::AppConfig;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::get;
     use rstest::rstest;
+    use tower::ServiceExt;
 
     #[rstest]
     #[case(false, Some(true), true)]
@@ -131,4 +194,48 @@ mod tests {
         // Act/Assert
         assert_eq!(middleware.priority(&context), expected_priority);
     }
+
+    #[rstest]
+    #[case("/fast", None, StatusCode::OK)]
+    #[case("/slow", None, StatusCode::GATEWAY_TIMEOUT)]
+    #[case("/slow", Some(1000), StatusCode::OK)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn timeout_install(
+        #[case] path: &str,
+        #[case] slow_override_millis: Option<u64>,
+        #[case] expected_status: StatusCode,
+    ) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        let timeout_config = &mut config.service.http.custom.middleware.timeout.custom;
+        timeout_config.timeout = Duration::from_millis(10);
+        if let Some(millis) = slow_override_millis {
+            timeout_config
+                .path_overrides
+                .insert("/slow".to_string(), Duration::from_millis(millis));
+        }
+
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        let router = Router::new().route("/fast", get(|| async {})).route(
+            "/slow",
+            get(|| async { tokio::time::sleep(Duration::from_millis(100)).await }),
+        );
+
+        // Act
+        let router = TimeoutMiddleware.install(router, &context).unwrap();
+        let response = router
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri(path)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), expected_status);
+    }
 }
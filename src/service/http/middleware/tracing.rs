@@ -2,23 +2,91 @@ use crate::app::context::AppContext;
 use crate::error::RoadsterResult;
 use crate::service::http::middleware::Middleware;
 use axum::extract::{FromRef, MatchedPath};
-use axum::http::{Request, Response};
+use axum::http::{Extensions, HeaderMap, HeaderName, HeaderValue, Request, Response};
 use axum::Router;
+use itertools::Itertools;
 use opentelemetry_semantic_conventions::trace::{
     HTTP_REQUEST_METHOD, HTTP_RESPONSE_STATUS_CODE, HTTP_ROUTE, URL_PATH,
 };
 use serde_derive::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
-use tower_http::trace::{DefaultOnResponse, MakeSpan, OnRequest, OnResponse, TraceLayer};
-use tracing::{event, field, info_span, Level, Span, Value};
+use tower_http::trace::{
+    DefaultOnEos, DefaultOnResponse, MakeSpan, OnEos, OnRequest, OnResponse, TraceLayer,
+};
+use tracing::{debug, event, field, info_span, Level, Span, Value};
 use validator::Validate;
 
 #[derive(Debug, Clone, Default, Validate, Serialize, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
-pub struct TracingConfig {}
+pub struct TracingConfig {
+    /// The names of request/response headers whose values should be logged as `REDACTED`.
+    /// Unlike omitting a header from the logs, this still shows whether the header was present.
+    ///
+    /// Note: The [sensitive_headers][crate::service::http::middleware::sensitive_headers]
+    /// middleware can also be used to hide header values. However, those headers are hidden from
+    /// all logs, not just this middleware's logs.
+    pub redacted_header_names: Vec<String>,
+}
+
+impl TracingConfig {
+    pub fn redacted_header_names(&self) -> RoadsterResult<Vec<HeaderName>> {
+        let header_names = self
+            .redacted_header_names
+            .iter()
+            .map(|header_name| HeaderName::from_str(header_name))
+            .try_collect()?;
+        Ok(header_names)
+    }
+}
+
+/// The value that's logged instead of the value of a redacted header.
+const REDACTED: &str = "REDACTED";
+
+/// The names of the additional fields that can be added to the HTTP request span via
+/// [TracingMiddleware::with_extra_span_fields]. `tracing` requires all of a span's fields to be
+/// declared when the span is created, so only these field names are supported.
+pub const EXTRA_SPAN_FIELD_NAMES: [&str; 4] = ["tenant_id", "user_id", "session_id", "client_id"];
+
+/// Function that provides additional fields to add to the HTTP request span. The function
+/// receives the request's headers and extensions (e.g., values inserted by an earlier
+/// middleware), and returns a list of field name/value pairs. The field names must be one of
+/// [EXTRA_SPAN_FIELD_NAMES]; any other fields are ignored.
+pub type ExtraSpanFields =
+    Arc<dyn Fn(&HeaderMap, &Extensions) -> Vec<(&'static str, String)> + Send + Sync>;
+
+/// Middleware that creates a span for each HTTP request and logs the request and response.
+///
+/// Note: This used to be a unit struct, so it can no longer be constructed as `TracingMiddleware`.
+/// Use [TracingMiddleware::new] (or [Default::default]) instead.
+#[derive(Default)]
+pub struct TracingMiddleware {
+    extra_span_fields: Option<ExtraSpanFields>,
+}
+
+impl TracingMiddleware {
+    /// Create a [TracingMiddleware] that doesn't add any additional fields to the HTTP request
+    /// spans.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a [TracingMiddleware] that adds the fields returned by the provided function to
+    /// each HTTP request span. See [ExtraSpanFields] for more details.
+    pub fn with_extra_span_fields<F>(extra_span_fields: F) -> Self
+    where
+        F: Fn(&HeaderMap, &Extensions) -> Vec<(&'static str, String)> + Send + Sync + 'static,
+    {
+        Self {
+            extra_span_fields: Some(Arc::new(extra_span_fields)),
+        }
+    }
+}
 
-pub struct TracingMiddleware;
 impl<S> Middleware<S> for TracingMiddleware
 where
     S: Clone + Send + Sync + 'static,
@@ -65,43 +133,98 @@ where
             .common
             .header_name;
 
+        let make_span = CustomMakeSpan::new(request_id_header_name.clone());
+        let redacted_header_names: Arc<[HeaderName]> = context
+            .config()
+            .service
+            .http
+            .custom
+            .middleware
+            .tracing
+            .custom
+            .redacted_header_names()?
+            .into();
+
+        let make_span = if let Some(extra_span_fields) = self.extra_span_fields.as_ref() {
+            make_span.with_extra_span_fields(extra_span_fields.clone())
+        } else {
+            make_span
+        };
+
         let router = router.layer(
             TraceLayer::new_for_http()
-                .make_span_with(CustomMakeSpan::new(request_id_header_name.clone()))
-                .on_request(CustomOnRequest)
-                .on_response(CustomOnResponse::new()),
+                .make_span_with(make_span)
+                .on_request(
+                    CustomOnRequest::new().with_redacted_headers(redacted_header_names.clone()),
+                )
+                .on_response(CustomOnResponse::new().with_redacted_headers(redacted_header_names))
+                .on_eos(CustomOnEos::new()),
         );
 
         Ok(router)
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 #[non_exhaustive]
 pub struct CustomMakeSpan {
     pub request_id_header_name: String,
+    pub extra_span_fields: Option<ExtraSpanFields>,
 }
 
 impl CustomMakeSpan {
     pub fn new(request_id_header_name: String) -> Self {
         Self {
             request_id_header_name,
+            extra_span_fields: None,
         }
     }
+
+    /// Add the fields returned by the provided function to each HTTP request span. See
+    /// [ExtraSpanFields] for more details.
+    pub fn with_extra_span_fields(mut self, extra_span_fields: ExtraSpanFields) -> Self {
+        self.extra_span_fields = Some(extra_span_fields);
+        self
+    }
+}
+
+impl Debug for CustomMakeSpan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomMakeSpan")
+            .field("request_id_header_name", &self.request_id_header_name)
+            .field("extra_span_fields", &self.extra_span_fields.is_some())
+            .finish()
+    }
 }
 
 impl<B> MakeSpan<B> for CustomMakeSpan {
     fn make_span(&mut self, request: &Request<B>) -> Span {
         let path = get_path(request);
         let request_id = get_request_id(&self.request_id_header_name, request);
-        info_span!("http_request",
+        let span = info_span!("http_request",
             { HTTP_REQUEST_METHOD } = %request.method(),
             { HTTP_ROUTE } = optional_trace_field(path),
             request_id = optional_trace_field(request_id),
+            // Extra fields that may be provided by `extra_span_fields`. These need to match
+            // the names in `EXTRA_SPAN_FIELD_NAMES`.
+            tenant_id = field::Empty,
+            user_id = field::Empty,
+            session_id = field::Empty,
+            client_id = field::Empty,
             // Fields that aren't know at request time, but will (may?) be known by
             // response time
             { HTTP_RESPONSE_STATUS_CODE } = field::Empty,
-        )
+        );
+        if let Some(extra_span_fields) = self.extra_span_fields.as_ref() {
+            for (name, value) in extra_span_fields(request.headers(), request.extensions()) {
+                if EXTRA_SPAN_FIELD_NAMES.contains(&name) {
+                    span.record(name, value);
+                } else {
+                    debug!(name, "Unsupported extra span field, skipping");
+                }
+            }
+        }
+        span
     }
 }
 
@@ -131,24 +254,67 @@ where
         .unwrap_or(Box::new(field::Empty))
 }
 
-#[derive(Debug, Copy, Clone)]
-pub struct CustomOnRequest;
+/// Copy the `headers`, replacing the values of the headers in `redacted_header_names` with
+/// [REDACTED].
+fn redact_headers(headers: &HeaderMap, redacted_header_names: &[HeaderName]) -> HeaderMap {
+    let mut headers = headers.clone();
+    for header_name in redacted_header_names {
+        if headers.contains_key(header_name) {
+            headers.insert(header_name.clone(), HeaderValue::from_static(REDACTED));
+        }
+    }
+    headers
+}
+
+/// Logs the start of each HTTP request, including the request's headers.
+///
+/// Note: This used to be a unit struct, so it can no longer be constructed as `CustomOnRequest`.
+/// Use [CustomOnRequest::new] (or [Default::default]) instead.
+#[derive(Debug, Clone, Default)]
+pub struct CustomOnRequest {
+    redacted_header_names: Arc<[HeaderName]>,
+}
+
+impl CustomOnRequest {
+    pub fn new() -> CustomOnRequest {
+        Default::default()
+    }
+
+    /// Log the values of the given headers as `REDACTED`.
+    pub fn with_redacted_headers(mut self, redacted_header_names: Arc<[HeaderName]>) -> Self {
+        self.redacted_header_names = redacted_header_names;
+        self
+    }
+}
 
 impl<B> OnRequest<B> for CustomOnRequest {
     fn on_request(&mut self, request: &Request<B>, _: &Span) {
-        event!(
-            Level::INFO,
-            version = ?request.version(),
-            { URL_PATH } = %request.uri(),
-            request_headers = ?request.headers(),
-            "started processing request",
-        )
+        // Avoid copying the headers if there are none to redact
+        if self.redacted_header_names.is_empty() {
+            log_request(request, request.headers());
+        } else {
+            log_request(
+                request,
+                &redact_headers(request.headers(), &self.redacted_header_names),
+            );
+        }
     }
 }
 
+fn log_request<B>(request: &Request<B>, headers: &HeaderMap) {
+    event!(
+        Level::INFO,
+        version = ?request.version(),
+        { URL_PATH } = %request.uri(),
+        request_headers = ?headers,
+        "started processing request",
+    )
+}
+
 #[derive(Debug, Clone)]
 pub struct CustomOnResponse {
     default: DefaultOnResponse,
+    redacted_header_names: Arc<[HeaderName]>,
 }
 
 impl CustomOnResponse {
@@ -158,8 +324,15 @@ impl CustomOnResponse {
                 .include_headers(true)
                 // TODO: Configure the level via AppConfig?
                 .level(Level::INFO),
+            redacted_header_names: Arc::new([]),
         }
     }
+
+    /// Log the values of the given headers as `REDACTED`.
+    pub fn with_redacted_headers(mut self, redacted_header_names: Arc<[HeaderName]>) -> Self {
+        self.redacted_header_names = redacted_header_names;
+        self
+    }
 }
 
 impl Default for CustomOnResponse {
@@ -171,7 +344,46 @@ impl Default for CustomOnResponse {
 impl<B> OnResponse<B> for CustomOnResponse {
     fn on_response(self, response: &Response<B>, latency: Duration, span: &Span) {
         span.record(HTTP_RESPONSE_STATUS_CODE, response.status().as_u16());
-        self.default.on_response(response, latency, span);
+        if self.redacted_header_names.is_empty() {
+            self.default.on_response(response, latency, span);
+            return;
+        }
+        // `DefaultOnResponse` logs the headers of the response it's given, so give it a copy of
+        // the response (without the body) with the redacted headers.
+        let mut redacted = Response::new(());
+        *redacted.status_mut() = response.status();
+        *redacted.version_mut() = response.version();
+        *redacted.headers_mut() = redact_headers(response.headers(), &self.redacted_header_names);
+        self.default.on_response(&redacted, latency, span);
+    }
+}
+
+/// Records the time it took to send the full response body, which is logged once the body
+/// stream completes. For most responses this is negligible, but for
+/// [streaming responses][crate::api::http::stream] the latency recorded by [CustomOnResponse]
+/// only includes the time until the response headers were sent.
+#[derive(Debug, Clone)]
+pub struct CustomOnEos {
+    default: DefaultOnEos,
+}
+
+impl CustomOnEos {
+    pub fn new() -> CustomOnEos {
+        CustomOnEos {
+            default: DefaultOnEos::new().level(Level::DEBUG),
+        }
+    }
+}
+
+impl Default for CustomOnEos {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OnEos for CustomOnEos {
+    fn on_eos(self, trailers: Option<&HeaderMap>, stream_duration: Duration, span: &Span) {
+        self.default.on_eos(trailers, stream_duration, span);
     }
 }
 
@@ -180,6 +392,13 @@ mod tests {
     use super::*;
     use crate::config::app_config::AppConfig;
     use rstest::rstest;
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Id, Record};
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
 
     #[rstest]
     #[case(false, Some(true), true)]
@@ -197,7 +416,7 @@ mod tests {
 
         let context = AppContext::test(Some(config), None, None).unwrap();
 
-        let middleware = TracingMiddleware;
+        let middleware = TracingMiddleware::default();
 
         // Act/Assert
         assert_eq!(middleware.enabled(&context), expected_enabled);
@@ -223,9 +442,170 @@ mod tests {
 
         let context = AppContext::test(Some(config), None, None).unwrap();
 
-        let middleware = TracingMiddleware;
+        let middleware = TracingMiddleware::default();
 
         // Act/Assert
         assert_eq!(middleware.priority(&context), expected_priority);
     }
+
+    /// Layer that captures the values recorded on spans after they're created.
+    #[derive(Clone, Default)]
+    struct RecordedFields(Arc<Mutex<BTreeMap<String, String>>>);
+
+    impl Visit for RecordedFields {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for RecordedFields {
+        fn on_record(&self, _span: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+            values.record(&mut self.clone());
+        }
+    }
+
+    #[derive(Clone)]
+    struct TenantId(String);
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn make_span_extra_span_fields() {
+        // Arrange
+        let recorded = RecordedFields::default();
+        let subscriber = tracing_subscriber::registry().with(recorded.clone());
+
+        let mut make_span = CustomMakeSpan::new("request-id".to_string()).with_extra_span_fields(
+            Arc::new(|_headers, extensions| {
+                let mut fields = Vec::new();
+                if let Some(tenant_id) = extensions.get::<TenantId>() {
+                    fields.push(("tenant_id", tenant_id.0.clone()));
+                }
+                fields.push(("unsupported", "foo".to_string()));
+                fields
+            }),
+        );
+        let mut request = Request::builder().uri("/").body(()).unwrap();
+        request
+            .extensions_mut()
+            .insert(TenantId("tenant-a".to_string()));
+
+        // Act
+        tracing::subscriber::with_default(subscriber, || {
+            let _span = make_span.make_span(&request);
+        });
+
+        // Assert
+        let recorded = recorded.0.lock().unwrap();
+        assert_eq!(recorded.get("tenant_id").unwrap(), "tenant-a");
+        assert!(!recorded.contains_key("unsupported"));
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn on_eos_streaming_response() {
+        use crate::api::http::stream::NdJson;
+        use crate::testing::tracing::capture_events;
+        use axum::body::{to_bytes, Body};
+        use axum::routing::get;
+        use futures::{stream, StreamExt};
+        use tower::ServiceExt;
+
+        // Arrange
+        let (events, _guard) = capture_events();
+        let router = Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    NdJson(stream::iter(0..100).map(|id| serde_json::json!({ "id": id })))
+                }),
+            )
+            .layer(
+                TraceLayer::new_for_http()
+                    .on_response(CustomOnResponse::new())
+                    .on_eos(CustomOnEos::new()),
+            );
+
+        // Act
+        let response = router
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let events_before_body = events.with_message("end of stream").len();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        // Assert
+        assert_eq!(
+            String::from_utf8(body.to_vec()).unwrap().lines().count(),
+            100
+        );
+        assert_eq!(events.with_message("finished processing request").len(), 1);
+        assert_eq!(events_before_body, 0);
+        let events = events.with_message("end of stream");
+        assert_eq!(events.len(), 1);
+        assert!(events[0].fields.contains_key("stream_duration"));
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn redacted_headers() {
+        use crate::testing::tracing::capture_events;
+        use axum::body::Body;
+        use axum::http::header::{AUTHORIZATION, SET_COOKIE};
+        use axum::routing::get;
+        use tower::ServiceExt;
+
+        // Arrange
+        let (events, _guard) = capture_events();
+        let mut config = AppConfig::test(None).unwrap();
+        config
+            .service
+            .http
+            .custom
+            .middleware
+            .tracing
+            .custom
+            .redacted_header_names = vec![AUTHORIZATION.to_string(), SET_COOKIE.to_string()];
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let router = Router::new().route(
+            "/",
+            get(|| async { ([(SET_COOKIE, "session=secret"), ("x-foo", "bar")], "") }),
+        );
+        let router = TracingMiddleware::default()
+            .install(router, &context)
+            .unwrap();
+
+        // Act
+        router
+            .oneshot(
+                Request::get("/")
+                    .header(AUTHORIZATION, "Bearer secret")
+                    .header("x-baz", "qux")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        let request = events.with_message("started processing request");
+        let request_headers = request[0].fields.get("request_headers").unwrap();
+        assert!(request_headers.contains(REDACTED));
+        assert!(!request_headers.contains("secret"));
+        assert!(request_headers.contains("qux"));
+        let response = events.with_message("finished processing request");
+        let response_headers = response[0].fields.get("response_headers").unwrap();
+        assert!(response_headers.contains(REDACTED));
+        assert!(!response_headers.contains("secret"));
+        assert!(response_headers.contains("bar"));
+    }
 }
@@ -1,4 +1,7 @@
 pub mod builder;
 pub mod initializer;
+#[cfg(feature = "leptos")]
+pub mod leptos;
 pub mod middleware;
+mod server;
 pub mod service;
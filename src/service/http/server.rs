@@ -0,0 +1,488 @@
+#[cfg(feature = "http-tls")]
+use crate::config::service::http::tls::Tls;
+use crate::config::service::http::HttpServiceConfig;
+use crate::error::RoadsterResult;
+#[cfg(feature = "http-tls")]
+use anyhow::anyhow;
+use axum::extract::ConnectInfo;
+use axum::middleware::AddExtension;
+use axum::{Extension, Router};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use hyper_util::service::TowerToHyperService;
+use std::io;
+use std::net::SocketAddr;
+#[cfg(feature = "http-tls")]
+use std::path::Path;
+#[cfg(feature = "http-tls")]
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+#[cfg(feature = "http-tls")]
+use tokio_rustls::rustls::ServerConfig;
+#[cfg(feature = "http-tls")]
+use tokio_rustls::TlsAcceptor;
+use tokio_util::sync::CancellationToken;
+use tower::Layer;
+use tracing::{debug, error};
+
+/// How long to wait before accepting new connections after an error that isn't specific to a
+/// single connection, e.g. when the process has run out of file descriptors.
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_secs(1);
+
+/// How long to wait for a client to complete the TLS handshake before closing the connection.
+/// Without this, a client that connects but never sends anything would hold the connection (and
+/// therefore the server's graceful shutdown) open indefinitely.
+#[cfg(feature = "http-tls")]
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serve the [Router] on the given [TcpListener] until the `cancel_token` is cancelled. Once
+/// cancelled, in-flight connections are gracefully shut down.
+///
+/// This is similar to [axum::serve], but allows configuring the underlying `hyper` server,
+/// e.g. to enable HTTP/2 or TLS.
+pub(crate) async fn serve(
+    listener: TcpListener,
+    router: Router,
+    config: &HttpServiceConfig,
+    cancel_token: CancellationToken,
+) -> RoadsterResult<()> {
+    let mut builder = auto::Builder::new(TokioExecutor::new());
+    builder
+        .http2()
+        .keep_alive_interval(config.keep_alive_interval)
+        .max_concurrent_streams(config.max_concurrent_streams);
+
+    // Used directly when HTTP/2 is disabled, see `Connection::serve`.
+    let mut http1_builder = hyper::server::conn::http1::Builder::new();
+    if let Some(max_header_bytes) = config.max_header_bytes {
+        builder.http1().max_buf_size(max_header_bytes);
+        builder
+            .http2()
+            .max_header_list_size(u32::try_from(max_header_bytes).unwrap_or(u32::MAX));
+        http1_builder.max_buf_size(max_header_bytes);
+    }
+    if let Some(max_headers) = config.max_headers {
+        builder.http1().max_headers(max_headers);
+        http1_builder.max_headers(max_headers);
+    }
+
+    #[cfg(feature = "http-tls")]
+    let tls_acceptor = config
+        .tls
+        .as_ref()
+        .map(|tls| tls_acceptor(tls, config.http2_enabled))
+        .transpose()?;
+
+    // Used to wait for all connections to close during shutdown. Each connection holds a
+    // receiver, and the sender is notified once all receivers are dropped.
+    let (close_tx, close_rx) = watch::channel(());
+
+    loop {
+        let (stream, remote_addr) = tokio::select! {
+            result = listener.accept() => match result {
+                Ok(conn) => conn,
+                Err(err) if is_connection_error(&err) => {
+                    debug!(%err, "Unable to accept connection");
+                    continue;
+                }
+                Err(err) => {
+                    // Retrying immediately would likely fail again and spin the loop, so
+                    // back off for a bit first. This mirrors the behavior of `axum::serve`.
+                    error!(%err, "Unable to accept connection, retrying after a delay");
+                    tokio::select! {
+                        _ = tokio::time::sleep(ACCEPT_ERROR_BACKOFF) => continue,
+                        _ = cancel_token.cancelled() => break,
+                    }
+                }
+            },
+            _ = cancel_token.cancelled() => break,
+        };
+
+        let connection = Connection {
+            // Make the client's address available to handlers via the `ConnectInfo` extractor.
+            service: TowerToHyperService::new(
+                Extension(ConnectInfo(remote_addr)).layer(router.clone()),
+            ),
+            http2_enabled: config.http2_enabled,
+            builder: builder.clone(),
+            http1_builder: http1_builder.clone(),
+            remote_addr,
+            cancel_token: cancel_token.clone(),
+            _close_rx: close_rx.clone(),
+        };
+
+        #[cfg(feature = "http-tls")]
+        if let Some(tls_acceptor) = tls_acceptor.clone() {
+            tokio::spawn(async move {
+                let cancel_token = connection.cancel_token.clone();
+                let handshake =
+                    tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, tls_acceptor.accept(stream));
+                let result = tokio::select! {
+                    result = handshake => result,
+                    _ = cancel_token.cancelled() => {
+                        debug!(%remote_addr, "Server shutting down, aborting TLS handshake");
+                        return;
+                    }
+                };
+                match result {
+                    Ok(Ok(stream)) => connection.serve(TokioIo::new(stream)).await,
+                    Ok(Err(err)) => debug!(%remote_addr, %err, "TLS handshake failed"),
+                    Err(_) => debug!(%remote_addr, "TLS handshake timed out"),
+                }
+            });
+            continue;
+        }
+
+        tokio::spawn(connection.serve(TokioIo::new(stream)));
+    }
+
+    drop(close_rx);
+    drop(listener);
+    close_tx.closed().await;
+
+    Ok(())
+}
+
+/// Whether the error only affects the connection that was being accepted, in which case the
+/// next connection can be accepted immediately.
+fn is_connection_error(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionReset
+    )
+}
+
+/// A single accepted connection.
+struct Connection {
+    service: TowerToHyperService<AddExtension<Router, ConnectInfo<SocketAddr>>>,
+    http2_enabled: bool,
+    builder: auto::Builder<TokioExecutor>,
+    http1_builder: hyper::server::conn::http1::Builder,
+    remote_addr: SocketAddr,
+    cancel_token: CancellationToken,
+    _close_rx: watch::Receiver<()>,
+}
+
+impl Connection {
+    async fn serve<I>(self, io: I)
+    where
+        I: hyper::rt::Read + hyper::rt::Write + Unpin + Send + 'static,
+    {
+        // The `auto` builder does not support disabling HTTP/2 when serving connections that
+        // can be upgraded (e.g. for websockets), so we use the HTTP/1 builder directly if
+        // HTTP/2 is disabled.
+        let result = if self.http2_enabled {
+            let conn = self
+                .builder
+                .serve_connection_with_upgrades(io, self.service);
+            tokio::pin!(conn);
+            tokio::select! {
+                result = conn.as_mut() => result,
+                _ = self.cancel_token.cancelled() => {
+                    conn.as_mut().graceful_shutdown();
+                    conn.await
+                }
+            }
+        } else {
+            let conn = self
+                .http1_builder
+                .serve_connection(io, self.service)
+                .with_upgrades();
+            tokio::pin!(conn);
+            tokio::select! {
+                result = conn.as_mut() => result,
+                _ = self.cancel_token.cancelled() => {
+                    conn.as_mut().graceful_shutdown();
+                    conn.await
+                }
+            }
+            .map_err(|err| err.into())
+        };
+
+        if let Err(err) = result {
+            debug!(remote_addr = %self.remote_addr, %err, "Error while serving connection");
+        }
+    }
+}
+
+#[cfg(feature = "http-tls")]
+fn tls_acceptor(tls: &Tls, http2_enabled: bool) -> RoadsterResult<TlsAcceptor> {
+    let certs = rustls_pemfile::certs(&mut open(&tls.cert_path)?)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| {
+            anyhow!(
+                "Unable to parse TLS certificate file `{}`: {err}",
+                tls.cert_path.display()
+            )
+        })?;
+    if certs.is_empty() {
+        return Err(anyhow!(
+            "No certificates found in TLS certificate file `{}`",
+            tls.cert_path.display()
+        )
+        .into());
+    }
+
+    let key = rustls_pemfile::private_key(&mut open(&tls.key_path)?)
+        .map_err(|err| {
+            anyhow!(
+                "Unable to parse TLS private key file `{}`: {err}",
+                tls.key_path.display()
+            )
+        })?
+        .ok_or_else(|| {
+            anyhow!(
+                "No private key found in TLS private key file `{}`",
+                tls.key_path.display()
+            )
+        })?;
+
+    let provider = Arc::new(tokio_rustls::rustls::crypto::ring::default_provider());
+    let mut server_config = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|err| anyhow!("Unable to build TLS config: {err}"))?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|err| anyhow!("Invalid TLS certificate or private key: {err}"))?;
+    server_config.alpn_protocols = if http2_enabled {
+        vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+    } else {
+        vec![b"http/1.1".to_vec()]
+    };
+
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+#[cfg(feature = "http-tls")]
+fn open(path: &Path) -> RoadsterResult<std::io::BufReader<std::fs::File>> {
+    let file = std::fs::File::open(path)
+        .map_err(|err| anyhow!("Unable to open file `{}`: {err}", path.display()))?;
+    Ok(std::io::BufReader::new(file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::app_config::AppConfig;
+    #[cfg(feature = "http-tls")]
+    use crate::util::test_util::TempDir;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, Version};
+    use axum::routing::get;
+    use rstest::rstest;
+    use tokio::net::TcpStream;
+    use tokio::task::JoinHandle;
+
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn start_server(
+        config: HttpServiceConfig,
+    ) -> (
+        SocketAddr,
+        CancellationToken,
+        JoinHandle<RoadsterResult<()>>,
+    ) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = Router::new().route("/", get(|| async { "hello" }));
+        let cancel_token = CancellationToken::new();
+        let server = {
+            let cancel_token = cancel_token.clone();
+            tokio::spawn(async move { serve(listener, router, &config, cancel_token).await })
+        };
+        (addr, cancel_token, server)
+    }
+
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn serve_http2(#[case] http2_enabled: bool) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap().service.http.custom;
+        config.http2_enabled = http2_enabled;
+        let (addr, cancel_token, server) = start_server(config).await;
+
+        // Act
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let result = async {
+            let (mut sender, conn) =
+                hyper::client::conn::http2::handshake(TokioExecutor::new(), TokioIo::new(stream))
+                    .await?;
+            tokio::spawn(conn);
+            let request = Request::builder()
+                .uri(format!("http://{addr}/"))
+                .body(Body::empty())
+                .unwrap();
+            sender.send_request(request).await
+        }
+        .await;
+
+        // Assert
+        if http2_enabled {
+            let response = result.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.version(), Version::HTTP_2);
+        } else {
+            assert!(result.is_err());
+        }
+        cancel_token.cancel();
+        server.await.unwrap().unwrap();
+    }
+
+    #[rstest]
+    #[case(5, 10, StatusCode::OK)]
+    #[case::oversized_header(1, 20_000, StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE)]
+    #[case::too_many_headers(50, 10, StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn serve_header_limits(
+        #[case] header_count: usize,
+        #[case] header_size: usize,
+        #[case] expected_status: StatusCode,
+    ) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap().service.http.custom;
+        config.max_header_bytes = Some(8192);
+        config.max_headers = Some(20);
+        let (addr, cancel_token, server) = start_server(config).await;
+
+        // Act
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (mut sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .unwrap();
+        tokio::spawn(conn);
+        let request = (0..header_count)
+            .fold(Request::builder(), |request, i| {
+                request.header(format!("x-test-{i}"), "a".repeat(header_size))
+            })
+            .uri("/")
+            .header("host", "localhost")
+            .body(Body::empty())
+            .unwrap();
+        let response = sender.send_request(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), expected_status);
+
+        drop(sender);
+        cancel_token.cancel();
+        server.await.unwrap().unwrap();
+    }
+
+    /// Write a self-signed certificate for `localhost` to a new temp dir and return the service
+    /// config with TLS enabled, the generated certificate, and the temp dir.
+    #[cfg(feature = "http-tls")]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn tls_config() -> (HttpServiceConfig, rcgen::CertifiedKey, TempDir) {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let dir = TempDir::new();
+        let cert_path = dir.write("cert.pem", cert.cert.pem());
+        let key_path = dir.write("key.pem", cert.key_pair.serialize_pem());
+
+        let mut config = AppConfig::test(None).unwrap().service.http.custom;
+        config.tls = Some(Tls {
+            cert_path,
+            key_path,
+        });
+        (config, cert, dir)
+    }
+
+    #[cfg(feature = "http-tls")]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn serve_tls() {
+        use tokio_rustls::rustls::pki_types::ServerName;
+        use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+        use tokio_rustls::TlsConnector;
+
+        // Arrange
+        let (config, cert, _dir) = tls_config();
+        let (addr, cancel_token, server) = start_server(config).await;
+
+        let mut roots = RootCertStore::empty();
+        roots.add(cert.cert.der().clone()).unwrap();
+        let provider = Arc::new(tokio_rustls::rustls::crypto::ring::default_provider());
+        let client_config = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connector = TlsConnector::from(Arc::new(client_config));
+
+        // Act
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let stream = connector
+            .connect(ServerName::try_from("localhost").unwrap(), stream)
+            .await
+            .unwrap();
+        let (mut sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .unwrap();
+        tokio::spawn(conn);
+        let request = Request::builder()
+            .uri("/")
+            .header("host", "localhost")
+            .body(Body::empty())
+            .unwrap();
+        let response = sender.send_request(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        drop(sender);
+        cancel_token.cancel();
+        server.await.unwrap().unwrap();
+    }
+
+    #[cfg(feature = "http-tls")]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn serve_tls_shutdown_with_pending_handshake() {
+        // Arrange
+        let (config, _cert, _dir) = tls_config();
+        let (addr, cancel_token, server) = start_server(config).await;
+        // Connect, but never send the TLS client hello.
+        let _stream = TcpStream::connect(addr).await.unwrap();
+        // Give the server a chance to accept the connection and start the handshake.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // Act
+        cancel_token.cancel();
+        let result = tokio::time::timeout(Duration::from_secs(5), server).await;
+
+        // Assert
+        result
+            .expect("Server did not shut down while a TLS handshake was pending")
+            .unwrap()
+            .unwrap();
+    }
+
+    #[cfg(feature = "http-tls")]
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn tls_acceptor_missing_files() {
+        let tls = Tls {
+            cert_path: "/invalid/cert.pem".into(),
+            key_path: "/invalid/key.pem".into(),
+        };
+
+        let result = tls_acceptor(&tls, false);
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    #[case(io::ErrorKind::ConnectionReset, true)]
+    #[case(io::ErrorKind::ConnectionAborted, true)]
+    #[case(io::ErrorKind::Other, false)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn is_connection_error(#[case] kind: io::ErrorKind, #[case] expected: bool) {
+        assert_eq!(super::is_connection_error(&io::Error::from(kind)), expected);
+    }
+}
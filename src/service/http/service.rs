@@ -8,6 +8,7 @@ use crate::app::context::AppContext;
 use crate::app::App;
 use crate::error::RoadsterResult;
 use crate::service::http::builder::HttpServiceBuilder;
+use crate::service::http::server::serve;
 use crate::service::AppService;
 #[cfg(feature = "open-api")]
 use aide::openapi::OpenApi;
@@ -24,8 +25,9 @@ use std::io::Write;
 use std::path::PathBuf;
 #[cfg(feature = "open-api")]
 use std::sync::Arc;
+use tokio::net::TcpListener;
 use tokio_util::sync::CancellationToken;
-use tracing::info;
+use tracing::{info, warn};
 
 pub(crate) const NAME: &str = "http";
 
@@ -89,19 +91,18 @@ where
         state: &S,
         cancel_token: CancellationToken,
     ) -> RoadsterResult<()> {
-        let server_addr = AppContext::from_ref(state)
-            .config()
-            .service
-            .http
-            .custom
-            .address
-            .url();
+        let context = AppContext::from_ref(state);
+        let config = &context.config().service.http.custom;
+        let server_addr = config.address.url();
         info!("Http server will start at {server_addr}");
 
-        let app_listener = tokio::net::TcpListener::bind(server_addr).await?;
-        axum::serve(app_listener, self.router)
-            .with_graceful_shutdown(Box::pin(async move { cancel_token.cancelled().await }))
-            .await?;
+        let app_listener = TcpListener::bind(server_addr).await?;
+        let bound_addr = app_listener.local_addr()?;
+        if let Err(err) = context.set_http_bound_addr(bound_addr) {
+            warn!("Unable to set the http service's bound address: {err}");
+        }
+        info!("Http server bound to {bound_addr}");
+        serve(app_listener, self.router, config, cancel_token).await?;
 
         Ok(())
     }
@@ -117,6 +118,28 @@ impl HttpService {
         HttpServiceBuilder::new(path_root, state)
     }
 
+    /// Consume the [HttpService] and return its [Router], with all of the configured middleware
+    /// and initializers applied. This allows sending requests to the app's routes in-process
+    /// (e.g., using [tower::ServiceExt::oneshot]) without starting the server, which is useful
+    /// for testing handlers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let service = AppServiceBuilder::<App, AppContext, HttpService>::build(
+    ///     HttpService::builder(Some("/api"), &context).api_router(api_router),
+    ///     &context,
+    /// )
+    /// .await?;
+    /// let response = service
+    ///     .into_router()
+    ///     .oneshot(Request::get("/api/_health").body(Body::empty())?)
+    ///     .await?;
+    /// ```
+    pub fn into_router(self) -> Router {
+        self.router
+    }
+
     /// List the available HTTP API routes.
     #[cfg(feature = "open-api")]
     pub fn list_routes(&self) -> Vec<(&str, &str)> {
@@ -153,6 +176,90 @@ impl HttpService {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::app::MockApp;
+    use crate::config::app_config::AppConfig;
+    use std::time::Duration;
+    use tokio::net::TcpStream;
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn bound_addr() {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.service.http.custom.address.host = "127.0.0.1".to_string();
+        config.service.http.custom.address.port = 0;
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let service = HttpService {
+            router: Router::new(),
+            #[cfg(feature = "open-api")]
+            api: Arc::new(OpenApi::default()),
+        };
+        assert!(context.http_bound_addr().is_none());
+        let cancel_token = CancellationToken::new();
+
+        // Act
+        let handle = {
+            let context = context.clone();
+            let cancel_token = cancel_token.clone();
+            tokio::spawn(async move {
+                AppService::<MockApp<AppContext>, AppContext>::run(
+                    Box::new(service),
+                    &context,
+                    cancel_token,
+                )
+                .await
+            })
+        };
+        let mut bound_addr = None;
+        for _ in 0..100 {
+            bound_addr = context.http_bound_addr();
+            if bound_addr.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // Assert
+        let bound_addr = bound_addr.unwrap();
+        assert_ne!(bound_addr.port(), 0);
+        assert!(TcpStream::connect(bound_addr).await.is_ok());
+
+        cancel_token.cancel();
+        handle.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn into_router() {
+        use crate::service::AppServiceBuilder;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{Request, StatusCode};
+        use axum::routing::get;
+        use tower::ServiceExt;
+
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let builder = HttpService::builder(Some("/api"), &context)
+            .router(Router::new().route("/api/foo", get(|| async { "bar" })));
+        let service = AppServiceBuilder::<MockApp<AppContext>, AppContext, HttpService>::build(
+            builder, &context,
+        )
+        .await
+        .unwrap();
+
+        // Act
+        let response = service
+            .into_router()
+            .oneshot(Request::get("/api/foo").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "bar");
+    }
 
     #[test]
     #[cfg(feature = "open-api")]
@@ -55,6 +55,9 @@ where
     ///
     /// For example, checking that the service is healthy, removing stale items from the
     /// service's queue, etc.
+    ///
+    /// This is called for all registered services before any service is [run][Self::run]. If
+    /// this returns an error for any service, the app will fail to start.
     async fn before_run(&self, _state: &S) -> RoadsterResult<()> {
         Ok(())
     }
@@ -3,16 +3,20 @@ use crate::api::cli::roadster::RoadsterCli;
 use crate::api::core::health::health_check;
 use crate::app::context::AppContext;
 use crate::app::App;
+use crate::config::app_config::ShutdownSignal;
 use crate::error::RoadsterResult;
 use crate::health_check::Status;
 use crate::service::registry::ServiceRegistry;
 use anyhow::anyhow;
 use axum::extract::FromRef;
+use futures::future::{self, BoxFuture};
+#[cfg(unix)]
+use itertools::Itertools;
 use std::future::Future;
 use std::time::Duration;
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info, instrument};
+use tracing::{error, info, instrument, warn};
 
 #[cfg(feature = "cli")]
 pub(crate) async fn handle_cli<A, S>(
@@ -82,7 +86,9 @@ where
     AppContext: FromRef<S>,
     A: App<S>,
 {
-    let cancel_token = CancellationToken::new();
+    // Use the context's token so tasks spawned via `AppContext::spawn_cancellable` are also
+    // stopped when the app shuts down.
+    let cancel_token = AppContext::from_ref(state).cancellation_token();
     let mut join_set = JoinSet::new();
 
     // Spawn tasks for the app's services
@@ -124,11 +130,15 @@ where
             let context = context.clone();
             Box::pin(async move { A::graceful_shutdown_signal(&context).await })
         };
-        let graceful_shutdown_signal =
-            graceful_shutdown_signal(cancel_token.clone(), app_graceful_shutdown_signal);
+        let graceful_shutdown_signal = graceful_shutdown_signal(
+            cancel_token.clone(),
+            app_graceful_shutdown_signal,
+            os_signal_received(&AppContext::from_ref(state).config().app.shutdown_signals),
+        );
         join_set.spawn(cancel_token_on_signal_received(
             graceful_shutdown_signal,
             cancel_token.clone(),
+            AppContext::from_ref(state),
         ));
     }
 
@@ -153,55 +163,132 @@ where
     Ok(())
 }
 
-async fn graceful_shutdown_signal<F>(cancellation_token: CancellationToken, app_shutdown_signal: F)
+/// Listen for the signal to gracefully shutdown the app. The `os_signal` future should resolve to
+/// the name of the OS signal that was received; see [os_signal_received].
+fn graceful_shutdown_signal<F1, F2>(
+    cancellation_token: CancellationToken,
+    app_shutdown_signal: F1,
+    os_signal: F2,
+) -> impl Future<Output = ()> + Send + 'static
 where
-    F: Future<Output = ()> + Send + 'static,
+    F1: Future<Output = ()> + Send + 'static,
+    F2: Future<Output = &'static str> + Send + 'static,
 {
+    async move {
+        tokio::select! {
+            signal = os_signal => {
+                info!("Shutting down due to {signal} signal received");
+            },
+            _ = cancellation_token.cancelled() => {
+                info!("Shutting down due to cancellation token cancelled");
+            }
+            _ = app_shutdown_signal => {
+                info!("Shutting down due to app's custom shutdown signal received");
+            }
+        }
+    }
+}
+
+/// Install listeners for the provided [ShutdownSignal]s. The listeners are installed immediately,
+/// before the returned future is first polled. The returned future resolves to the name of the
+/// first signal received.
+#[cfg(unix)]
+fn os_signal_received(signals: &[ShutdownSignal]) -> BoxFuture<'static, &'static str> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let listeners = signals
+        .iter()
+        .unique()
+        .map(|shutdown_signal| {
+            let name: &'static str = shutdown_signal.into();
+            let kind = match shutdown_signal {
+                ShutdownSignal::Interrupt => SignalKind::interrupt(),
+                ShutdownSignal::Terminate => SignalKind::terminate(),
+                ShutdownSignal::Hangup => SignalKind::hangup(),
+                ShutdownSignal::Quit => SignalKind::quit(),
+            };
+            let mut listener = signal(kind)
+                .unwrap_or_else(|err| panic!("Failed to install {name} signal handler: {err}"));
+            Box::pin(async move {
+                listener.recv().await;
+                name
+            }) as BoxFuture<'static, &'static str>
+        })
+        .collect_vec();
+
+    if listeners.is_empty() {
+        return Box::pin(future::pending());
+    }
+
+    Box::pin(async move { future::select_all(listeners).await.0 })
+}
+
+/// Unix signals are not supported on this platform, so the configured [ShutdownSignal]s are
+/// ignored and Ctrl-C (and Ctrl-Break, on Windows) are used instead.
+#[cfg(not(unix))]
+fn os_signal_received(_signals: &[ShutdownSignal]) -> BoxFuture<'static, &'static str> {
     let ctrl_c = async {
         tokio::signal::ctrl_c()
             .await
             .expect("Failed to install Ctrl+C handler");
+        "ctrl-c"
     };
 
-    #[cfg(unix)]
-    let sigterm = async {
-        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
-            .expect("Failed to install SIGTERM handler")
-            .recv()
-            .await;
+    #[cfg(windows)]
+    let ctrl_break = {
+        let mut listener =
+            tokio::signal::windows::ctrl_break().expect("Failed to install Ctrl+Break handler");
+        async move {
+            listener.recv().await;
+            "ctrl-break"
+        }
     };
 
-    #[cfg(not(unix))]
-    let sigterm = std::future::pending::<()>();
+    #[cfg(not(windows))]
+    let ctrl_break = future::pending::<&'static str>();
 
-    tokio::select! {
-        _ = ctrl_c => {
-            info!("Shutting down due to ctrl-c signal received");
-        },
-        _ = sigterm => {
-            info!("Shutting down due to sigterm signal received");
-        },
-        _ = cancellation_token.cancelled() => {
-            info!("Shutting down due to cancellation token cancelled");
-        }
-        _ = app_shutdown_signal => {
-            info!("Shutting down due to app's custom shutdown signal received");
+    Box::pin(async move {
+        tokio::select! {
+            signal = ctrl_c => signal,
+            signal = ctrl_break => signal,
         }
-    }
+    })
 }
 
 async fn cancel_token_on_signal_received<F>(
     shutdown_signal: F,
     cancellation_token: CancellationToken,
+    context: AppContext,
 ) -> RoadsterResult<()>
 where
     F: Future<Output = ()> + Send + 'static,
 {
     shutdown_signal.await;
+    pre_shutdown_delay(&context, &cancellation_token).await;
     cancellation_token.cancel();
     Ok(())
 }
 
+/// Mark the app as not [ready][AppContext::ready] and wait for the configured
+/// [pre-shutdown-delay][crate::config::app_config::App::pre_shutdown_delay] before the app starts
+/// shutting down. The app's services keep running during the delay. The delay is skipped if the
+/// cancellation token was already cancelled, e.g. due to an error in one of the app's tasks.
+async fn pre_shutdown_delay(context: &AppContext, cancellation_token: &CancellationToken) {
+    context.set_ready(false);
+    let delay = context.config().app.pre_shutdown_delay;
+    if delay.is_zero() || cancellation_token.is_cancelled() {
+        return;
+    }
+    info!(
+        "Waiting {} ms before shutting down to allow load balancers to stop routing requests to the app",
+        delay.as_millis()
+    );
+    tokio::select! {
+        _ = tokio::time::sleep(delay) => {},
+        _ = cancellation_token.cancelled() => {},
+    }
+}
+
 async fn token_shutdown_signal(cancellation_token: CancellationToken) {
     cancellation_token.cancelled().await
 }
@@ -225,8 +312,7 @@ where
 async fn graceful_shutdown<F1, F2>(
     shutdown_signal: F1,
     app_graceful_shutdown: F2,
-    // This parameter is (currently) not used when no features are enabled.
-    #[allow(unused_variables)] context: AppContext,
+    context: AppContext,
 ) -> RoadsterResult<()>
 where
     F1: Future<Output = ()> + Send + 'static,
@@ -236,6 +322,9 @@ where
 
     info!("Received shutdown signal. Shutting down gracefully.");
 
+    // Wait for background tasks first, as they may still be using the app's resources.
+    wait_for_background_tasks(&context).await;
+
     #[cfg(feature = "db-sql")]
     let db_close_result = {
         info!("Closing the DB connection pool.");
@@ -253,3 +342,200 @@ where
 
     Ok(())
 }
+
+/// Wait for the app's background tasks to complete, up to the configured
+/// [background-task-shutdown-timeout][crate::config::app_config::App::background_task_shutdown_timeout].
+async fn wait_for_background_tasks(context: &AppContext) {
+    info!("Waiting for the app's background tasks to complete.");
+    let timeout = context.config().app.background_task_shutdown_timeout;
+    if tokio::time::timeout(timeout, context.wait_for_tasks())
+        .await
+        .is_err()
+    {
+        warn!(
+            count = context.background_task_count(),
+            timeout = %timeout.as_millis(),
+            "Timed out waiting for the app's background tasks to complete, continuing to shut down"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::MockApp;
+    use crate::service::MockAppService;
+    use crate::testing::tracing::capture_events;
+    use rstest::rstest;
+
+    fn service(name: &str, before_run_ok: bool) -> MockAppService<MockApp<AppContext>, AppContext> {
+        let mut service: MockAppService<MockApp<AppContext>, AppContext> =
+            MockAppService::default();
+        service.expect_enabled().return_const(true);
+        service.expect_name().return_const(name.to_string());
+        service.expect_before_run().times(..=1).returning(move |_| {
+            if before_run_ok {
+                Ok(())
+            } else {
+                Err(anyhow!("before_run failed").into())
+            }
+        });
+        service
+    }
+
+    #[rstest]
+    #[case(true, true, true)]
+    #[case(true, false, false)]
+    #[case(false, true, false)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn before_run(#[case] a_ok: bool, #[case] b_ok: bool, #[case] expect_ok: bool) {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let mut registry: ServiceRegistry<MockApp<AppContext>, AppContext> =
+            ServiceRegistry::new(&context);
+        registry.register_service(service("a", a_ok)).unwrap();
+        registry.register_service(service("b", b_ok)).unwrap();
+
+        // Act
+        let result = super::before_run(&registry, &context).await;
+
+        // Assert
+        assert_eq!(result.is_ok(), expect_ok);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn wait_for_background_tasks_timeout() {
+        use crate::config::app_config::AppConfig;
+
+        // Arrange
+        let (events, _guard) = capture_events();
+        let mut config = AppConfig::test(None).unwrap();
+        config.app.background_task_shutdown_timeout = Duration::from_millis(10);
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        context.spawn(future::pending::<()>());
+        context.spawn(future::ready(()));
+
+        // Act
+        tokio::time::timeout(Duration::from_secs(5), wait_for_background_tasks(&context))
+            .await
+            .unwrap();
+
+        // Assert
+        let events = events.with_message(
+            "Timed out waiting for the app's background tasks to complete, continuing to shut down",
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].fields.get("count").unwrap(), "1");
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn graceful_shutdown_on_os_signal() {
+        // Arrange
+        let (events, _guard) = capture_events();
+        let cancel_token = CancellationToken::new();
+        let shutdown_signal = graceful_shutdown_signal(
+            cancel_token.clone(),
+            future::pending(),
+            future::ready("SIGTERM"),
+        );
+
+        // Act
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            cancel_token_on_signal_received(
+                shutdown_signal,
+                cancel_token.clone(),
+                AppContext::test(None, None, None).unwrap(),
+            ),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        // Assert
+        assert!(cancel_token.is_cancelled());
+        assert_eq!(
+            events
+                .with_message("Shutting down due to SIGTERM signal received")
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn os_signal_received_no_signals() {
+        // Act
+        let result = tokio::time::timeout(Duration::from_millis(10), os_signal_received(&[])).await;
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[cfg(feature = "http")]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn pre_shutdown_delay() {
+        use crate::config::app_config::AppConfig;
+        use crate::service::http::builder::HttpServiceBuilder;
+        use crate::service::http::service::HttpService;
+        use crate::service::AppServiceBuilder;
+        use axum::body::Body;
+        use axum::http::{Request, StatusCode};
+        use tower::ServiceExt;
+
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.app.pre_shutdown_delay = Duration::from_millis(200);
+        config.health_check.default_enable = false;
+        config.service.http.custom.default_routes.default_enable = true;
+        let context = AppContext::test(Some(config), None, None).unwrap();
+        let cancel_token = CancellationToken::new();
+        let service = AppServiceBuilder::<MockApp<AppContext>, AppContext, HttpService>::build(
+            HttpServiceBuilder::new(Some("/api"), &context),
+            &context,
+        )
+        .await
+        .unwrap();
+        let router = service.router;
+        let request = |path: &str| Request::get(path).body(Body::empty()).unwrap();
+        let health_before = router
+            .clone()
+            .oneshot(request("/api/_health"))
+            .await
+            .unwrap();
+
+        // Act
+        let handle = tokio::spawn(cancel_token_on_signal_received(
+            future::ready(()),
+            cancel_token.clone(),
+            context.clone(),
+        ));
+        // Let the task receive the signal and start the delay
+        tokio::task::yield_now().await;
+        tokio::time::advance(Duration::from_millis(100)).await;
+        let health_during = router
+            .clone()
+            .oneshot(request("/api/_health"))
+            .await
+            .unwrap();
+        let ping_during = router.oneshot(request("/api/_ping")).await.unwrap();
+        let cancelled_during_delay = cancel_token.is_cancelled();
+        tokio::time::advance(Duration::from_millis(100)).await;
+        handle.await.unwrap().unwrap();
+
+        // Assert
+        assert_eq!(health_before.status(), StatusCode::OK);
+        // During the delay, the app reports as unhealthy but continues handling requests
+        assert!(!context.ready());
+        assert_eq!(health_during.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(ping_during.status(), StatusCode::OK);
+        assert!(!cancelled_during_delay);
+        // The app starts shutting down after the delay
+        assert!(cancel_token.is_cancelled());
+    }
+}
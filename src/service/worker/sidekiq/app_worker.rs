@@ -1,20 +1,27 @@
 use crate::app::context::AppContext;
+use crate::error::sidekiq::EnqueueError;
 use crate::error::RoadsterResult;
 use async_trait::async_trait;
 use axum::extract::FromRef;
 use serde_derive::{Deserialize, Serialize};
 use serde_with::{serde_as, skip_serializing_none};
-use sidekiq::Worker;
+use sidekiq::{Worker, WorkerOpts};
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 use typed_builder::TypedBuilder;
 use validator::Validate;
 
+/// The maximum number of custom fields a worker can add to its span via
+/// [AppWorker::span_fields].
+pub const MAX_SPAN_FIELDS: usize = 4;
+
 /// Additional configuration options that can be configured via the app's configuration files.
 /// The options can also be overridden on a per-worker basis by implementing the corresponding
 /// method in the [AppWorker] trait.
 #[serde_as]
 #[skip_serializing_none]
 #[derive(Debug, Clone, Validate, Serialize, Deserialize, TypedBuilder)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(default, rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct AppWorkerConfig {
@@ -28,11 +35,18 @@ pub struct AppWorkerConfig {
     /// The maximum duration workers should run for. The timeout is only enforced if `timeout`
     /// is `true`.
     #[serde_as(as = "serde_with::DurationSeconds")]
+    #[cfg_attr(feature = "config-schema", schemars(with = "u64"))]
     #[builder(default = AppWorkerConfig::default().max_duration)]
     pub max_duration: Duration,
     /// See <https://docs.rs/rusty-sidekiq/latest/sidekiq/trait.Worker.html#method.disable_argument_coercion>
     #[builder(default = AppWorkerConfig::default().disable_argument_coercion)]
     pub disable_argument_coercion: bool,
+    /// The maximum number of jobs of this worker type that are allowed to run at the same time,
+    /// independent of the number of Sidekiq worker tasks. Jobs that exceed the limit will wait
+    /// until a slot is available instead of failing. If not provided, no limit is enforced.
+    #[builder(default)]
+    #[validate(range(min = 1))]
+    pub max_concurrency: Option<usize>,
 }
 
 impl Default for AppWorkerConfig {
@@ -46,6 +60,19 @@ impl Default for AppWorkerConfig {
     }
 }
 
+/// The outcome of a job that was processed successfully. Returned by
+/// [AppWorker::perform_with_outcome].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum WorkerOutcome {
+    /// The job is complete.
+    Complete,
+    /// The job can't make progress yet (e.g., a dependency isn't ready), and should be run again
+    /// after the given delay. Rescheduling a job is not considered a failure, so it does not
+    /// count against the job's retries.
+    Reschedule(Duration),
+}
+
 #[async_trait]
 pub trait AppWorker<S, Args>: Worker<Args>
 where
@@ -60,11 +87,113 @@ where
     /// Enqueue the worker into its Sidekiq queue. This is a helper method around [Worker::perform_async]
     /// so the caller can simply provide the app state instead of needing to access the
     /// [sidekiq::RedisPool] from inside the app state.
+    ///
+    /// If [Self::queue_for] returns a queue for the given args, the job will be enqueued into
+    /// that queue instead of the worker's default queue.
+    ///
+    /// The args are validated with [Self::validate_args] before the job is enqueued, and the
+    /// validation error is returned if they are invalid. If the job can't be sent to Redis, an
+    /// [EnqueueError] is returned, which indicates whether the failure is transient.
     async fn enqueue(state: &S, args: Args) -> RoadsterResult<()> {
-        Self::perform_async(AppContext::from_ref(state).redis_enqueue(), args).await?;
+        Self::validate_args(state, &args)?;
+        enqueue_opts::<S, Args, Self>(state, &args, None)
+            .perform_async(AppContext::from_ref(state).redis_enqueue(), args)
+            .await
+            .map_err(EnqueueError::from)?;
+        Ok(())
+    }
+
+    /// Enqueue the worker into its Sidekiq queue, unless a job for the worker with the same args
+    /// was already enqueued within the given `window`. This is useful to debounce jobs that may
+    /// be triggered many times in a short period, but only need to run once.
+    ///
+    /// This uses Sidekiq.rs's unique jobs support, which stores a key derived from the worker's
+    /// class, queue, and args in Redis with an expiration of `window`. Unlike an idempotency key,
+    /// the de-duplication is purely time-based: once the window expires, the same job can be
+    /// enqueued again even if the previous job has not run yet.
+    async fn enqueue_debounced(state: &S, args: Args, window: Duration) -> RoadsterResult<()> {
+        Self::validate_args(state, &args)?;
+        enqueue_opts::<S, Args, Self>(state, &args, Some(window))
+            .perform_async(AppContext::from_ref(state).redis_enqueue(), args)
+            .await
+            .map_err(EnqueueError::from)?;
+        Ok(())
+    }
+
+    /// Validate the args of a job before it's enqueued via [Self::enqueue] or
+    /// [Self::enqueue_debounced]. Returning an error prevents the job from being enqueued, and
+    /// the error is returned to the caller. This allows rejecting obviously-invalid jobs at the
+    /// call site (e.g., in an HTTP handler) instead of having them fail after they're fetched by
+    /// a worker.
+    ///
+    /// The default implementation does not perform any validation.
+    fn validate_args(_state: &S, _args: &Args) -> RoadsterResult<()> {
         Ok(())
     }
 
+    /// Provide the name of the queue to enqueue a job with the given args into, e.g., to shard
+    /// the worker's jobs across multiple queues based on a tenant id in the args. If `None` is
+    /// returned, the job will be enqueued into the worker's default queue (see [Worker::opts]).
+    ///
+    /// Note: Sidekiq.rs only fetches jobs from the queues the processor was created with, so any
+    /// queue returned by this method must also be returned by [Self::queues], and the queues
+    /// must be included in the `service.sidekiq.queues` config (or provided to
+    /// [SidekiqWorkerServiceBuilder::with_default_processor][crate::service::worker::sidekiq::builder::SidekiqWorkerServiceBuilder::with_default_processor]).
+    fn queue_for(_state: &S, _args: &Args) -> Option<String> {
+        None
+    }
+
+    /// Declare all the queues (other than the worker's default queue) that [Self::queue_for]
+    /// may return for this worker.
+    ///
+    /// The default implementation returns an empty list.
+    fn queues(_state: &S) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Provide custom fields to add to the span the job is processed in, e.g. the tenant from the
+    /// job's args, to allow filtering the job's traces.
+    ///
+    /// `tracing` requires a span's fields to be declared when the span is created, so the span
+    /// has a small number of reserved slots for custom fields: `field.0` through `field.3`
+    /// (see [MAX_SPAN_FIELDS]). Each field is recorded into the next slot as `name=value`, and
+    /// any fields beyond the reserved slots are ignored.
+    ///
+    /// The default implementation returns an empty list.
+    fn span_fields(&self, _args: &Args) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Process a job and return its [WorkerOutcome]. Workers that need to reschedule a job can
+    /// override this method instead of [Worker::perform].
+    ///
+    /// Sidekiq does not support changing the visibility of a job that was already fetched, so
+    /// when [WorkerOutcome::Reschedule] is returned, the job is enqueued again with the requested
+    /// delay (into the queue provided by [Self::queue_for], if any) and the current job is
+    /// marked as completed. If the job can't be enqueued again, an error is returned and the
+    /// job will be retried as usual.
+    ///
+    /// The default implementation calls [Worker::perform] and returns [WorkerOutcome::Complete]
+    /// if it succeeds.
+    async fn perform_with_outcome(&self, args: Args) -> sidekiq::Result<WorkerOutcome> {
+        self.perform(args).await?;
+        Ok(WorkerOutcome::Complete)
+    }
+
+    /// Process a job with a [CancellationToken] that's cancelled when the app starts shutting
+    /// down. Long-running workers can override this method to stop cooperatively (e.g., after
+    /// checkpointing their progress) instead of being stopped when the app exits. Roadster calls
+    /// this method instead of [Worker::perform] when processing the worker's jobs.
+    ///
+    /// The default implementation ignores the token and calls [Self::perform_with_outcome].
+    async fn perform_cancellable(
+        &self,
+        args: Args,
+        _cancel_token: CancellationToken,
+    ) -> sidekiq::Result<WorkerOutcome> {
+        self.perform_with_outcome(args).await
+    }
+
     /// Provide the [AppWorkerConfig] for [Self]. The default implementation populates the
     /// [AppWorkerConfig] using the values from the corresponding methods on [Self], e.g.,
     /// [Self::max_retries].
@@ -74,6 +203,7 @@ where
             .timeout(self.timeout(state))
             .max_duration(self.max_duration(state))
             .disable_argument_coercion(AppWorker::disable_argument_coercion(self, state))
+            .max_concurrency(self.max_concurrency(state))
             .build()
     }
 
@@ -128,14 +258,211 @@ where
             .app_worker
             .disable_argument_coercion
     }
+
+    /// See [AppWorkerConfig::max_concurrency].
+    ///
+    /// The default implementation uses the value from the app's config file.
+    fn max_concurrency(&self, state: &S) -> Option<usize> {
+        AppContext::from_ref(state)
+            .config()
+            .service
+            .sidekiq
+            .custom
+            .app_worker
+            .max_concurrency
+    }
+
+    /// Whether the given error returned by the worker should cause the job to be retried.
+    /// Returning `false` indicates that the error is permanent (e.g., the job's args failed
+    /// validation), in which case the job will not be retried, even if it has retries
+    /// remaining. Instead, the error will be logged and the job will be marked as completed.
+    ///
+    /// The default implementation considers all errors to be retryable.
+    fn is_retryable(&self, _err: &sidekiq::Error) -> bool {
+        true
+    }
+}
+
+/// Build the [WorkerOpts] used to enqueue a job for the worker with the given args. If
+/// `unique_for` is provided, the job will not be enqueued if a job for the worker with the same
+/// args was already enqueued within that duration.
+fn enqueue_opts<S, Args, W>(
+    state: &S,
+    args: &Args,
+    unique_for: Option<Duration>,
+) -> WorkerOpts<Args, W>
+where
+    Args: Send + Sync + serde::Serialize + 'static,
+    S: Clone + Send + Sync + 'static,
+    AppContext: FromRef<S>,
+    W: AppWorker<S, Args>,
+{
+    let opts = W::opts();
+    let opts = match unique_for {
+        Some(unique_for) => opts.unique_for(unique_for),
+        None => opts,
+    };
+    match W::queue_for(state, args) {
+        Some(queue) => opts.queue(queue),
+        None => opts,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::sidekiq::SidekiqError;
     use crate::util::serde_util::Wrapper;
+    use anyhow::anyhow;
+    use rstest::rstest;
     use serde_json::from_str;
 
+    struct ValidatingWorker;
+
+    #[async_trait]
+    impl Worker<String> for ValidatingWorker {
+        async fn perform(&self, _args: String) -> sidekiq::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AppWorker<AppContext, String> for ValidatingWorker {
+        fn build(_state: &AppContext) -> Self {
+            ValidatingWorker
+        }
+
+        fn validate_args(_state: &AppContext, args: &String) -> RoadsterResult<()> {
+            if args.is_empty() {
+                return Err(anyhow!("Args must not be empty").into());
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn enqueue_invalid_args() {
+        // Arrange
+        // No Redis pool is provided, so the test context will panic if the job is sent to Redis.
+        let context = AppContext::test(None, None, None).unwrap();
+
+        // Act
+        let result = ValidatingWorker::enqueue(&context, "".to_string()).await;
+        let debounced_result =
+            ValidatingWorker::enqueue_debounced(&context, "".to_string(), Duration::from_secs(1))
+                .await;
+
+        // Assert
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Args must not be empty".to_string()
+        );
+        assert!(debounced_result.is_err());
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn enqueue_redis_unavailable() {
+        // Arrange
+        let redis = sidekiq::RedisConnectionManager::new("redis://invalid_host:1234").unwrap();
+        let redis = bb8::Pool::builder()
+            .connection_timeout(Duration::from_millis(10))
+            .build_unchecked(redis);
+        let context = AppContext::test(None, None, Some(redis)).unwrap();
+
+        // Act
+        let result = ValidatingWorker::enqueue(&context, "foo".to_string()).await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::Sidekiq(SidekiqError::Enqueue(
+                EnqueueError::Backend {
+                    transient: true,
+                    ..
+                }
+            )))
+        ));
+    }
+
+    struct ShardedWorker;
+
+    #[async_trait]
+    impl Worker<String> for ShardedWorker {
+        async fn perform(&self, _args: String) -> sidekiq::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AppWorker<AppContext, String> for ShardedWorker {
+        fn build(_state: &AppContext) -> Self {
+            ShardedWorker
+        }
+
+        fn queue_for(_state: &AppContext, args: &String) -> Option<String> {
+            if args.is_empty() {
+                None
+            } else {
+                Some(format!("tenant-{args}"))
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::tenant_a("a", "tenant-a")]
+    #[case::tenant_b("b", "tenant-b")]
+    #[case::default_queue("", "default")]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn enqueue_opts_queue_for(#[case] args: &str, #[case] expected_queue: &str) {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let args = args.to_string();
+
+        // Act
+        let job = enqueue_opts::<_, _, ShardedWorker>(&context, &args, None)
+            .into_opts()
+            .create_job(ShardedWorker::class_name(), &args)
+            .unwrap();
+
+        // Assert
+        assert_eq!(job.queue, expected_queue);
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn enqueue_opts_debounced() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let window = Duration::from_secs(10);
+        let args = "foo".to_string();
+
+        // Act
+        let job = |args: &String| {
+            enqueue_opts::<_, _, ValidatingWorker>(&context, args, Some(window))
+                .into_opts()
+                .create_job(ValidatingWorker::class_name(), args)
+                .unwrap()
+        };
+        let first = job(&args);
+        let second = job(&args);
+        let not_debounced = enqueue_opts::<_, _, ValidatingWorker>(&context, &args, None)
+            .into_opts()
+            .create_job(ValidatingWorker::class_name(), &args)
+            .unwrap();
+
+        // Assert
+        // Sidekiq.rs de-duplicates jobs based on the job's queue, class, and args for the
+        // `unique_for` duration, so these two jobs are treated as the same job within the window.
+        assert_eq!(first.unique_for, Some(window));
+        assert_eq!(second.unique_for, Some(window));
+        assert_eq!(first.queue, second.queue);
+        assert_eq!(first.class, second.class);
+        assert_eq!(first.args, second.args);
+        assert_eq!(not_debounced.unique_for, None);
+    }
+
     #[test]
     #[cfg_attr(coverage_nightly, coverage(off))]
     fn deserialize_config_override_max_retries() {
@@ -174,6 +501,14 @@ mod tests {
             from_str(r#"{"inner": {"disable-argument-coercion": true } }"#).unwrap();
         assert!(value.inner.disable_argument_coercion);
     }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn deserialize_config_override_max_concurrency() {
+        let value: Wrapper<AppWorkerConfig> =
+            from_str(r#"{"inner": {"max-concurrency": 2 } }"#).unwrap();
+        assert_eq!(value.inner.max_concurrency, Some(2));
+    }
 }
 
 #[cfg(test)]
@@ -211,6 +546,11 @@ mod deserialize_tests {
         disable-argument-coercion = true
         "#
     )]
+    #[case(
+        r#"
+        max-concurrency = 2
+        "#
+    )]
     #[cfg_attr(coverage_nightly, coverage(off))]
     fn app_worker(_case: TestCase, #[case] config: &str) {
         let app_worker: AppWorkerConfig = toml::from_str(config).unwrap();
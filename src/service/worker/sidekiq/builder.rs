@@ -3,6 +3,7 @@ use crate::app::App;
 use crate::config::service::worker::sidekiq::StaleCleanUpBehavior;
 use crate::error::RoadsterResult;
 use crate::service::worker::sidekiq::app_worker::AppWorker;
+use crate::service::worker::sidekiq::queue_latency::QueueLatencyMiddleware;
 use crate::service::worker::sidekiq::roadster_worker::RoadsterWorker;
 use crate::service::worker::sidekiq::service::{enabled, SidekiqWorkerService, NAME};
 #[cfg_attr(test, mockall_double::double)]
@@ -16,7 +17,9 @@ use num_traits::ToPrimitive;
 use serde::Serialize;
 use sidekiq::{periodic, ProcessorConfig, ServerMiddleware};
 use std::collections::HashSet;
-use tracing::{debug, info};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tracing::{debug, info, warn};
 
 pub(crate) const PERIODIC_KEY: &str = "periodic";
 
@@ -38,8 +41,15 @@ where
         state: S,
         registered_workers: HashSet<String>,
         registered_periodic_workers: HashSet<String>,
+        /// The queues handled by the processor, if known.
+        queues: Option<HashSet<String>>,
+        /// Shared by all registered workers to limit the total number of in-flight jobs.
+        in_flight_limit: Option<Arc<Semaphore>>,
     },
-    Disabled,
+    /// The Sidekiq service is not enabled, so jobs will not be processed. The state is kept so
+    /// workers can still be added to the app's
+    /// [DynEnqueuer][crate::service::worker::sidekiq::dyn_enqueuer::DynEnqueuer].
+    Disabled { state: S },
 }
 
 #[async_trait]
@@ -56,7 +66,7 @@ where
     fn enabled(&self, state: &S) -> bool {
         match self.state {
             BuilderState::Enabled { .. } => enabled(&AppContext::from_ref(state)),
-            BuilderState::Disabled => false,
+            BuilderState::Disabled { .. } => false,
         }
     }
 
@@ -70,7 +80,7 @@ where
                 registered_periodic_workers,
                 processor: processor.into_sidekiq_processor(),
             },
-            BuilderState::Disabled => {
+            BuilderState::Disabled { .. } => {
                 return Err(anyhow!(
                     "This builder is not enabled; it's build method should not have been called."
                 )
@@ -88,7 +98,7 @@ where
     AppContext: FromRef<S>,
 {
     pub async fn with_processor(state: &S, processor: sidekiq::Processor) -> RoadsterResult<Self> {
-        Self::new(state.clone(), Some(Processor::new(processor))).await
+        Self::new(state.clone(), Some(Processor::new(processor)), None).await
     }
 
     pub async fn with_default_processor(
@@ -96,6 +106,7 @@ where
         worker_queues: Option<Vec<String>>,
     ) -> RoadsterResult<Self> {
         let context = AppContext::from_ref(state);
+        let mut processor_queues = None;
         let processor = if !enabled(&context) {
             debug!("Sidekiq service not enabled, not creating the Sidekiq processor");
             None
@@ -132,10 +143,12 @@ where
                     })?;
                 let processor_config: ProcessorConfig = Default::default();
                 let processor_config = processor_config.num_workers(num_workers);
-                let processor = sidekiq::Processor::new(redis_fetch.clone(), queues.clone())
+                let mut processor = sidekiq::Processor::new(redis_fetch.clone(), queues.clone())
                     .with_config(processor_config);
+                processor.using(QueueLatencyMiddleware).await;
                 Processor::new(processor)
             };
+            processor_queues = Some(queues.into_iter().collect());
 
             Some(processor)
         } else {
@@ -145,22 +158,36 @@ where
             None
         };
 
-        Self::new(state.clone(), processor).await
+        Self::new(state.clone(), processor, processor_queues).await
     }
 
-    async fn new(state: S, processor: Option<Processor>) -> RoadsterResult<Self> {
+    async fn new(
+        state: S,
+        processor: Option<Processor>,
+        queues: Option<HashSet<String>>,
+    ) -> RoadsterResult<Self> {
         let context = AppContext::from_ref(&state);
         let processor = if enabled(&context) { processor } else { None };
 
+        let in_flight_limit = context
+            .config()
+            .service
+            .sidekiq
+            .custom
+            .max_in_flight
+            .map(|max_in_flight| Arc::new(Semaphore::new(max_in_flight)));
+
         let state = if let Some(processor) = processor {
             BuilderState::Enabled {
                 processor,
                 state,
                 registered_workers: Default::default(),
                 registered_periodic_workers: Default::default(),
+                queues,
+                in_flight_limit,
             }
         } else {
-            BuilderState::Disabled
+            BuilderState::Disabled { state }
         };
 
         Ok(Self { state })
@@ -213,25 +240,58 @@ where
     ///
     /// The worker will be wrapped by a [RoadsterWorker], which provides some common behavior, such
     /// as enforcing a timeout/max duration of worker jobs.
+    ///
+    /// The worker will also be added to the app's
+    /// [DynEnqueuer][crate::service::worker::sidekiq::dyn_enqueuer::DynEnqueuer], even if the
+    /// Sidekiq service is not enabled.
+    ///
+    /// If the processor's queues are known, a warning will be logged for any of the worker's
+    /// [queues][AppWorker::queues] that are not handled by the processor.
     pub fn register_app_worker<Args, W>(mut self, worker: W) -> RoadsterResult<Self>
     where
         Args: Sync + Send + Serialize + for<'de> serde::Deserialize<'de> + 'static,
         W: AppWorker<S, Args> + 'static,
     {
-        if let BuilderState::Enabled {
-            processor,
-            registered_workers,
-            state: context,
-            ..
-        } = &mut self.state
-        {
-            let class_name = W::class_name();
-            debug!(worker = %class_name, "Registering worker");
-            if !registered_workers.insert(class_name.clone()) {
-                return Err(anyhow!("Worker `{class_name}` was already registered").into());
+        match &mut self.state {
+            BuilderState::Enabled {
+                processor,
+                registered_workers,
+                state: context,
+                queues,
+                in_flight_limit,
+                ..
+            } => {
+                let class_name = W::class_name();
+                debug!(worker = %class_name, "Registering worker");
+                if !registered_workers.insert(class_name.clone()) {
+                    return Err(anyhow!("Worker `{class_name}` was already registered").into());
+                }
+                if let Some(queues) = queues {
+                    W::queues(context)
+                        .into_iter()
+                        .filter(|queue| !queues.contains(queue))
+                        .for_each(|queue| {
+                            warn!(
+                                worker = %class_name,
+                                %queue,
+                                "Worker may enqueue jobs into a queue that is not handled by the Sidekiq processor"
+                            )
+                        });
+                }
+                AppContext::from_ref(context)
+                    .sidekiq_dyn_enqueuer()
+                    .register::<S, Args, W>()?;
+                let roadster_worker = RoadsterWorker::new(worker, context, in_flight_limit.clone());
+                processor.register(roadster_worker);
+            }
+            BuilderState::Disabled { state } => {
+                // Jobs can still be enqueued from an app instance that doesn't process them (e.g.,
+                // an instance that only serves HTTP requests), so the worker is still added to the
+                // `DynEnqueuer` even though it isn't registered with a processor.
+                AppContext::from_ref(state)
+                    .sidekiq_dyn_enqueuer()
+                    .register::<S, Args, W>()?;
             }
-            let roadster_worker = RoadsterWorker::new(worker, context);
-            processor.register(roadster_worker);
         }
 
         Ok(self)
@@ -258,12 +318,13 @@ where
             processor,
             state: context,
             registered_periodic_workers,
+            in_flight_limit,
             ..
         } = &mut self.state
         {
             let class_name = W::class_name();
             debug!(worker = %class_name, "Registering periodic worker");
-            let roadster_worker = RoadsterWorker::new(worker, context);
+            let roadster_worker = RoadsterWorker::new(worker, context, in_flight_limit.clone());
             let builder = builder.args(args)?;
             let job_json = serde_json::to_string(&builder.into_periodic_job(class_name.clone())?)?;
             if !registered_periodic_workers.insert(job_json.clone()) {
@@ -297,6 +358,7 @@ mod tests {
     use crate::app::context::AppContext;
     use crate::config::app_config::AppConfig;
     use crate::service::worker::sidekiq::MockProcessor;
+    use crate::testing::tracing::capture_events;
     use bb8::Pool;
     use futures::StreamExt;
     use rstest::rstest;
@@ -323,6 +385,13 @@ mod tests {
         // Assert
         validate_registered_workers(&builder, enabled, expected_size, expected_class_names);
         validate_registered_periodic_workers(&builder, enabled, 0, Default::default());
+        let context = match &builder.state {
+            BuilderState::Enabled { state, .. } | BuilderState::Disabled { state } => state,
+        };
+        assert_eq!(
+            context.sidekiq_dyn_enqueuer().worker_names(),
+            vec![MockTestAppWorker::class_name()]
+        );
     }
 
     #[tokio::test]
@@ -368,6 +437,69 @@ mod tests {
         validate_registered_periodic_workers(&builder, enabled, job_names.len(), job_names)
     }
 
+    struct QueuesTestAppWorker;
+
+    #[async_trait]
+    impl Worker<()> for QueuesTestAppWorker {
+        async fn perform(&self, _args: ()) -> sidekiq::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl AppWorker<AppContext, ()> for QueuesTestAppWorker {
+        fn build(_state: &AppContext) -> Self {
+            QueuesTestAppWorker
+        }
+
+        fn queues(_state: &AppContext) -> Vec<String> {
+            vec!["foo".to_string(), "bar".to_string()]
+        }
+    }
+
+    #[rstest]
+    #[case(Some(vec!["foo".to_string()]), vec!["bar"])]
+    #[case(Some(vec!["foo".to_string(), "bar".to_string()]), Default::default())]
+    #[case(None, Default::default())]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn register_app_worker_unhandled_queues(
+        #[case] processor_queues: Option<Vec<String>>,
+        #[case] expected_unhandled: Vec<&str>,
+    ) {
+        // Arrange
+        let (events, _guard) = capture_events();
+        let mut config = AppConfig::test(None).unwrap();
+        config.service.default_enable = true;
+        let redis_fetch = RedisConnectionManager::new("redis://invalid_host:1234").unwrap();
+        let pool = Pool::builder().build_unchecked(redis_fetch);
+        let context = AppContext::test(Some(config), None, Some(pool)).unwrap();
+        let mut processor = MockProcessor::default();
+        processor
+            .expect_register::<AppContext, (), QueuesTestAppWorker>()
+            .times(1)
+            .returning(|_| ());
+        let builder = SidekiqWorkerServiceBuilder::new(
+            context,
+            Some(processor),
+            processor_queues.map(|queues| queues.into_iter().collect()),
+        )
+        .await
+        .unwrap();
+
+        // Act
+        builder.register_app_worker(QueuesTestAppWorker).unwrap();
+
+        // Assert
+        let unhandled = events
+            .with_message(
+                "Worker may enqueue jobs into a queue that is not handled by the Sidekiq processor",
+            )
+            .into_iter()
+            .map(|event| event.fields.get("queue").unwrap().clone())
+            .collect_vec();
+        assert_eq!(unhandled, expected_unhandled);
+    }
+
     mockall::mock! {
         TestAppWorker{}
 
@@ -408,7 +540,7 @@ mod tests {
             .times(periodic_count)
             .returning(|_, _| Ok(()));
 
-        SidekiqWorkerServiceBuilder::new(context, Some(processor))
+        SidekiqWorkerServiceBuilder::new(context, Some(processor), None)
             .await
             .unwrap()
     }
@@ -430,7 +562,7 @@ mod tests {
                     .iter()
                     .for_each(|class_name| assert!(registered_workers.contains(class_name)));
             }
-            BuilderState::Disabled => {
+            BuilderState::Disabled { .. } => {
                 assert!(!enabled, "Builder should not be disabled!");
             }
         }
@@ -456,7 +588,7 @@ mod tests {
                         .any(|registered| registered.contains(job_string)));
                 });
             }
-            BuilderState::Disabled => {
+            BuilderState::Disabled { .. } => {
                 assert!(!enabled, "Builder should not be disabled!");
             }
         }
@@ -0,0 +1,226 @@
+use crate::app::context::AppContext;
+use crate::error::RoadsterResult;
+use crate::service::worker::sidekiq::app_worker::AppWorker;
+use anyhow::anyhow;
+use axum::extract::FromRef;
+use futures::future::BoxFuture;
+use serde::Serialize;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+type EnqueueFn = Box<
+    dyn Fn(&(dyn Any + Send + Sync), Value) -> BoxFuture<'static, RoadsterResult<()>> + Send + Sync,
+>;
+
+/// Allows enqueuing jobs for the app's registered [AppWorker]s using the worker's
+/// [class name][sidekiq::Worker::class_name] and JSON args, without needing to know the
+/// worker's concrete type. This is useful for building generic tools, e.g. an admin API to
+/// (re-)enqueue arbitrary jobs.
+///
+/// Workers are added automatically when they are registered with the
+/// [SidekiqWorkerServiceBuilder][crate::service::worker::sidekiq::builder::SidekiqWorkerServiceBuilder].
+/// The [DynEnqueuer] for the app can be accessed via [AppContext::sidekiq_dyn_enqueuer].
+#[derive(Default)]
+pub struct DynEnqueuer {
+    workers: RwLock<BTreeMap<String, EnqueueFn>>,
+}
+
+impl DynEnqueuer {
+    pub(crate) fn register<S, Args, W>(&self) -> RoadsterResult<()>
+    where
+        S: Clone + Send + Sync + 'static,
+        AppContext: FromRef<S>,
+        Args: Sync + Send + Serialize + for<'de> serde::Deserialize<'de> + 'static,
+        W: AppWorker<S, Args> + 'static,
+    {
+        let class_name = W::class_name();
+        // The state is provided when enqueuing instead of being captured here. The state contains
+        // the `AppContext`, which holds this `DynEnqueuer`, so capturing it would create a
+        // reference cycle.
+        let enqueue: EnqueueFn = {
+            let class_name = class_name.clone();
+            Box::new(move |state, args| {
+                let state = state.downcast_ref::<S>().cloned();
+                let class_name = class_name.clone();
+                Box::pin(async move {
+                    let state = state.ok_or_else(|| {
+                        anyhow!("Unable to enqueue worker `{class_name}`: unexpected state type")
+                    })?;
+                    let args: Args = serde_json::from_value(args)?;
+                    W::enqueue(&state, args).await
+                })
+            })
+        };
+
+        let mut workers = self
+            .workers
+            .write()
+            .map_err(|err| anyhow!("Unable to register worker `{class_name}`: {err}"))?;
+        if workers.insert(class_name.clone(), enqueue).is_some() {
+            return Err(anyhow!("Worker `{class_name}` was already registered").into());
+        }
+
+        Ok(())
+    }
+
+    /// Get the class names of the workers that can be enqueued.
+    pub fn worker_names(&self) -> Vec<String> {
+        self.workers
+            .read()
+            .map(|workers| workers.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Enqueue a job for the worker with the given class name. The `state` must be the app's
+    /// state, i.e. the same type of state that the workers were registered with.
+    ///
+    /// Returns an error if no worker with the given name was registered, if the `state` has a
+    /// different type than the worker was registered with, or if the `args` can't be
+    /// deserialized into the worker's args type.
+    pub async fn enqueue<S>(&self, state: &S, worker_name: &str, args: Value) -> RoadsterResult<()>
+    where
+        S: Send + Sync + 'static,
+    {
+        let enqueue = {
+            let workers = self
+                .workers
+                .read()
+                .map_err(|err| anyhow!("Unable to enqueue worker `{worker_name}`: {err}"))?;
+            let enqueue = workers
+                .get(worker_name)
+                .ok_or_else(|| anyhow!("Worker `{worker_name}` was not registered"))?;
+            enqueue(state, args)
+        };
+        enqueue.await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_derive::Deserialize;
+    use serde_json::json;
+    use sidekiq::Worker;
+    use std::sync::Mutex;
+
+    static ENQUEUED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct TestArgs {
+        foo: String,
+    }
+
+    struct TestWorker;
+
+    #[async_trait]
+    impl Worker<TestArgs> for TestWorker {
+        async fn perform(&self, _args: TestArgs) -> sidekiq::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AppWorker<AppContext, TestArgs> for TestWorker {
+        fn build(_state: &AppContext) -> Self {
+            TestWorker
+        }
+
+        async fn enqueue(_state: &AppContext, args: TestArgs) -> RoadsterResult<()> {
+            ENQUEUED.lock().unwrap().push(args.foo);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn enqueue() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let enqueuer = DynEnqueuer::default();
+        enqueuer
+            .register::<AppContext, TestArgs, TestWorker>()
+            .unwrap();
+
+        // Act
+        let result = enqueuer
+            .enqueue(&context, &TestWorker::class_name(), json!({"foo": "bar"}))
+            .await;
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(enqueuer.worker_names(), vec![TestWorker::class_name()]);
+        assert!(ENQUEUED.lock().unwrap().contains(&"bar".to_string()));
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn enqueue_invalid_args() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let enqueuer = DynEnqueuer::default();
+        enqueuer
+            .register::<AppContext, TestArgs, TestWorker>()
+            .unwrap();
+
+        // Act
+        let result = enqueuer
+            .enqueue(&context, &TestWorker::class_name(), json!({"bar": 1}))
+            .await;
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn enqueue_unknown_worker() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let enqueuer = DynEnqueuer::default();
+
+        // Act
+        let result = enqueuer.enqueue(&context, "Unknown", json!({})).await;
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn enqueue_wrong_state_type() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let enqueuer = DynEnqueuer::default();
+        enqueuer
+            .register::<AppContext, TestArgs, TestWorker>()
+            .unwrap();
+
+        // Act
+        let result = enqueuer
+            .enqueue(&(), &TestWorker::class_name(), json!({"foo": "bar"}))
+            .await;
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn register_twice() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let enqueuer = DynEnqueuer::default();
+        enqueuer
+            .register::<AppContext, TestArgs, TestWorker>()
+            .unwrap();
+
+        // Act
+        let result = enqueuer.register::<AppContext, TestArgs, TestWorker>();
+
+        // Assert
+        assert!(result.is_err());
+    }
+}
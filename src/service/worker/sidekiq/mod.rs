@@ -8,6 +8,8 @@ use sidekiq::{periodic, ServerMiddleware};
 
 pub mod app_worker;
 pub mod builder;
+pub mod dyn_enqueuer;
+pub mod queue_latency;
 pub mod roadster_worker;
 pub mod service;
 
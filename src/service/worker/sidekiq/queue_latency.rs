@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+use sidekiq::{ChainIter, Job, RedisPool, ServerMiddleware, ServerResult, WorkerRef};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::field::Empty;
+use tracing::{info, info_span, Instrument, Span};
+
+/// Sidekiq.rs [ServerMiddleware] that measures the queue latency of each job, i.e. how long the
+/// job waited in its queue between when it was enqueued and when it started processing.
+///
+/// Each job is processed in a `job` span with a `queue_latency_ms` field. A `Job started` event
+/// is also emitted with the latency in the `histogram.sidekiq.queue_latency_ms` field, which is
+/// recorded as a histogram metric if the `otel` feature is enabled.
+///
+/// This middleware is added automatically to the processor created by
+/// [SidekiqWorkerServiceBuilder::with_default_processor][crate::service::worker::sidekiq::builder::SidekiqWorkerServiceBuilder::with_default_processor].
+pub struct QueueLatencyMiddleware;
+
+#[async_trait]
+impl ServerMiddleware for QueueLatencyMiddleware {
+    async fn call(
+        &self,
+        chain: ChainIter,
+        job: &Job,
+        worker: Arc<WorkerRef>,
+        redis: RedisPool,
+    ) -> ServerResult {
+        let span = job_span(job, SystemTime::now());
+        chain.next(job, worker, redis).instrument(span).await
+    }
+}
+
+/// The time between when the job was enqueued and `now`. Sidekiq stores the `enqueued_at`
+/// timestamp as fractional seconds since the Unix epoch.
+fn queue_latency(job: &Job, now: SystemTime) -> Option<Duration> {
+    let enqueued_at = Duration::try_from_secs_f64(job.enqueued_at?).ok()?;
+    now.duration_since(UNIX_EPOCH + enqueued_at).ok()
+}
+
+fn job_span(job: &Job, now: SystemTime) -> Span {
+    let span = info_span!(
+        "job",
+        worker = %job.class,
+        queue = %job.queue,
+        jid = %job.jid,
+        queue_latency_ms = Empty
+    );
+    let queue_latency_ms =
+        queue_latency(job, now).and_then(|latency| u64::try_from(latency.as_millis()).ok());
+    if let Some(queue_latency_ms) = queue_latency_ms {
+        span.record("queue_latency_ms", queue_latency_ms);
+        span.in_scope(|| {
+            info!(
+                histogram.sidekiq.queue_latency_ms = queue_latency_ms,
+                "Job started"
+            )
+        });
+    }
+    span
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::tracing::capture_events;
+    use rstest::rstest;
+    use serde_json::json;
+
+    fn job(enqueued_at: Option<f64>) -> Job {
+        serde_json::from_value(json!({
+            "queue": "default",
+            "args": [],
+            "retry": true,
+            "class": "TestWorker",
+            "jid": "1234",
+            "created_at": 1000.0,
+            "enqueued_at": enqueued_at,
+        }))
+        .unwrap()
+    }
+
+    #[rstest]
+    #[case(Some(1000.0), Some(Duration::from_millis(1500)))]
+    #[case(Some(1002.0), None)]
+    #[case(None, None)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn queue_latency(#[case] enqueued_at: Option<f64>, #[case] expected: Option<Duration>) {
+        // Arrange
+        let job = job(enqueued_at);
+        let now = UNIX_EPOCH + Duration::from_millis(1_001_500);
+
+        // Act
+        let latency = super::queue_latency(&job, now);
+
+        // Assert
+        assert_eq!(job.enqueued_at, enqueued_at);
+        assert_eq!(latency, expected);
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn job_span() {
+        // Arrange
+        let (events, _guard) = capture_events();
+        let job = job(Some(1000.0));
+        let now = UNIX_EPOCH + Duration::from_millis(1_000_250);
+
+        // Act
+        let span = super::job_span(&job, now);
+
+        // Assert
+        assert!(span.has_field("queue_latency_ms"));
+        let events = events.with_message("Job started");
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].fields.get("histogram.sidekiq.queue_latency_ms"),
+            Some(&"250".to_string())
+        );
+    }
+}
@@ -1,13 +1,28 @@
 use crate::app::context::AppContext;
 use crate::service::worker::sidekiq::app_worker::AppWorker;
 use crate::service::worker::sidekiq::app_worker::AppWorkerConfig;
+use crate::service::worker::sidekiq::app_worker::WorkerOutcome;
+use crate::service::worker::sidekiq::app_worker::MAX_SPAN_FIELDS;
 use async_trait::async_trait;
 use axum::extract::FromRef;
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 use sidekiq::{RedisPool, Worker, WorkerOpts};
 use std::marker::PhantomData;
+use std::sync::Arc;
 use std::time::Duration;
-use tracing::{error, instrument};
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio_util::sync::CancellationToken;
+use tracing::field::Empty;
+use tracing::{debug, error, info, info_span, Instrument, Span};
+
+/// How often to check whether Sidekiq processing has been resumed while it's paused via
+/// [AppContext::set_sidekiq_fetch_paused].
+const FETCH_PAUSED_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The names of the span fields reserved for the custom fields provided by
+/// [AppWorker::span_fields].
+const SPAN_FIELD_SLOTS: [&str; MAX_SPAN_FIELDS] = ["field.0", "field.1", "field.2", "field.3"];
 
 /// Worker used by Roadster to wrap the consuming app's workers to add additional behavior. For
 /// example, [RoadsterWorker] is by default configured to automatically abort the app's worker
@@ -21,7 +36,15 @@ where
 {
     inner: W,
     inner_config: AppWorkerConfig,
-    _state: PhantomData<S>,
+    state: S,
+    context: AppContext,
+    /// Limits the number of concurrent jobs for this worker if
+    /// [AppWorkerConfig::max_concurrency] is set.
+    concurrency_limit: Option<Arc<Semaphore>>,
+    /// Limits the number of concurrent jobs across all workers if
+    /// [SidekiqServiceConfig::max_in_flight][crate::config::service::worker::sidekiq::SidekiqServiceConfig::max_in_flight]
+    /// is set. Shared by all of the app's [RoadsterWorker]s.
+    in_flight_limit: Option<Arc<Semaphore>>,
     _args: PhantomData<Args>,
 }
 
@@ -32,15 +55,120 @@ where
     Args: Send + Sync + Serialize,
     W: AppWorker<S, Args>,
 {
-    pub(crate) fn new(inner: W, state: &S) -> Self {
+    pub(crate) fn new(inner: W, state: &S, in_flight_limit: Option<Arc<Semaphore>>) -> Self {
         let config = inner.config(state);
+        let concurrency_limit = config
+            .max_concurrency
+            .map(|max_concurrency| Arc::new(Semaphore::new(max_concurrency)));
         Self {
             inner,
             inner_config: config,
-            _state: PhantomData,
+            state: state.clone(),
+            context: AppContext::from_ref(state),
+            concurrency_limit,
+            in_flight_limit,
             _args: PhantomData,
         }
     }
+
+    /// Build the span to process the job in, including the custom fields provided by
+    /// [AppWorker::span_fields].
+    fn span(&self, args: &Args) -> Span {
+        let span = info_span!(
+            "perform",
+            worker = %W::class_name(),
+            field.0 = Empty,
+            field.1 = Empty,
+            field.2 = Empty,
+            field.3 = Empty
+        );
+        let fields = self.inner.span_fields(args);
+        if fields.len() > MAX_SPAN_FIELDS {
+            debug!(
+                worker = %W::class_name(),
+                count = fields.len(),
+                max = MAX_SPAN_FIELDS,
+                "Worker provided too many span fields, the extra fields will be ignored"
+            );
+        }
+        fields
+            .iter()
+            .zip(SPAN_FIELD_SLOTS)
+            .for_each(|((name, value), slot)| {
+                span.record(slot, format!("{name}={value}").as_str());
+            });
+        span
+    }
+
+    /// Enqueue the job again to be run after the given `delay`. The job's args are passed in
+    /// their serialized form because the original args were consumed by the inner worker.
+    async fn reschedule(
+        &self,
+        args: serde_json::Value,
+        queue: Option<String>,
+        delay: Duration,
+    ) -> sidekiq::Result<()>
+    where
+        Args: DeserializeOwned,
+    {
+        let args: Args =
+            serde_json::from_value(args).map_err(|err| sidekiq::Error::Any(Box::new(err)))?;
+        let opts = W::opts();
+        let opts = match queue {
+            Some(queue) => opts.queue(queue),
+            None => opts,
+        };
+        info!(
+            worker = %W::class_name(),
+            delay = %delay.as_millis(),
+            "Rescheduling job"
+        );
+        opts.perform_in(self.context.redis_enqueue(), delay, args)
+            .await
+    }
+
+    /// Wait until processing is resumed via [AppContext::set_sidekiq_fetch_paused].
+    async fn wait_for_resume(&self) {
+        while self.context.sidekiq_fetch_paused() {
+            tokio::time::sleep(FETCH_PAUSED_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Enqueue the job again without processing it, e.g. because the app started shutting down
+    /// before the job could start. The current job is then marked as completed. If the job can't
+    /// be enqueued again, an error is returned and the job will be retried as usual.
+    async fn requeue(
+        &self,
+        args: Args,
+        queue: Option<String>,
+        reason: &str,
+    ) -> sidekiq::Result<()> {
+        let opts = W::opts();
+        let opts = match queue {
+            Some(queue) => opts.queue(queue),
+            None => opts,
+        };
+        info!(
+            worker = %W::class_name(),
+            %reason,
+            "Enqueuing job again without processing it"
+        );
+        opts.perform_async(self.context.redis_enqueue(), args).await
+    }
+}
+
+/// Acquire a permit from the `semaphore`, or return `None` if the `cancel_token` is cancelled
+/// (i.e., the app starts shutting down) before a permit is available.
+async fn acquire<'a>(
+    semaphore: &'a Semaphore,
+    cancel_token: &CancellationToken,
+) -> sidekiq::Result<Option<SemaphorePermit<'a>>> {
+    tokio::select! {
+        permit = semaphore.acquire() => permit
+            .map(Some)
+            .map_err(|err| sidekiq::Error::Any(Box::new(err))),
+        _ = cancel_token.cancelled() => Ok(None),
+    }
 }
 
 #[async_trait]
@@ -48,7 +176,7 @@ impl<S, Args, W> Worker<Args> for RoadsterWorker<S, Args, W>
 where
     S: Clone + Send + Sync + 'static,
     AppContext: FromRef<S>,
-    Args: Send + Sync + Serialize,
+    Args: Send + Sync + Serialize + DeserializeOwned,
     W: AppWorker<S, Args>,
 {
     fn disable_argument_coercion(&self) -> bool {
@@ -99,11 +227,75 @@ where
         unimplemented!()
     }
 
-    #[instrument(skip_all)]
     async fn perform(&self, args: Args) -> sidekiq::Result<()> {
-        let inner = self.inner.perform(args);
+        let span = self.span(&args);
+        self.perform_in_span(args).instrument(span).await
+    }
+}
 
-        if self.inner_config.timeout {
+impl<S, Args, W> RoadsterWorker<S, Args, W>
+where
+    S: Clone + Send + Sync + 'static,
+    AppContext: FromRef<S>,
+    Args: Send + Sync + Serialize + DeserializeOwned,
+    W: AppWorker<S, Args>,
+{
+    async fn perform_in_span(&self, args: Args) -> sidekiq::Result<()> {
+        let queue = W::queue_for(&self.state, &args);
+        let cancel_token = self.context.cancellation_token();
+
+        // Hold the job until processing is resumed. This also prevents the worker task from
+        // fetching additional jobs while processing is paused. If the app starts shutting down, or
+        // the job is held for longer than the worker's max duration (if enabled), the job is
+        // enqueued again so it isn't held indefinitely.
+        if self.context.sidekiq_fetch_paused() {
+            let max_hold = async {
+                if self.inner_config.timeout {
+                    tokio::time::sleep(self.inner_config.max_duration).await
+                } else {
+                    std::future::pending().await
+                }
+            };
+            let resumed = tokio::select! {
+                _ = self.wait_for_resume() => true,
+                _ = cancel_token.cancelled() => false,
+                _ = max_hold => false,
+            };
+            if !resumed {
+                return self.requeue(args, queue, "Processing is paused").await;
+            }
+        }
+
+        // Wait for a slot to become available if the worker has a concurrency limit. The permit
+        // is held until the job completes. If the app starts shutting down first, the job is
+        // enqueued again instead of blocking shutdown.
+        let _permit = match self.concurrency_limit.as_ref() {
+            Some(concurrency_limit) => match acquire(concurrency_limit, &cancel_token).await? {
+                Some(permit) => Some(permit),
+                None => return self.requeue(args, queue, "App is shutting down").await,
+            },
+            None => None,
+        };
+
+        // Wait for a slot to become available if there's a limit on the total number of jobs that
+        // can be processed at the same time across all workers. This is acquired after the
+        // worker's own permit so a job waiting for its worker's limit doesn't hold a global slot.
+        // Permits are released when they're dropped, including if the job is cancelled.
+        let _in_flight_permit = match self.in_flight_limit.as_ref() {
+            Some(in_flight_limit) => match acquire(in_flight_limit, &cancel_token).await? {
+                Some(permit) => Some(permit),
+                None => return self.requeue(args, queue, "App is shutting down").await,
+            },
+            None => None,
+        };
+
+        // Keep what's needed to reschedule the job, since the args are consumed by the worker.
+        let serialized_args =
+            serde_json::to_value(&args).map_err(|err| sidekiq::Error::Any(Box::new(err)))?;
+
+        let inner = self.inner.perform_cancellable(args, cancel_token);
+
+        let result = if self.inner_config.timeout {
             tokio::time::timeout(self.inner_config.max_duration, inner)
                 .await
                 .map_err(|err| {
@@ -114,9 +306,526 @@ where
                         "Worker timed out"
                     );
                     sidekiq::Error::Any(Box::new(err))
-                })?
+                })
+                .and_then(|result| result)
         } else {
             inner.await
+        };
+
+        match result {
+            Err(err) if !self.inner.is_retryable(&err) => {
+                // Sidekiq.rs retries any job that returns an error, so we return `Ok` to
+                // prevent the job from being retried.
+                error!(
+                    worker = %W::class_name(),
+                    %err,
+                    "Worker failed with a non-retryable error, the job will not be retried"
+                );
+                Ok(())
+            }
+            Ok(WorkerOutcome::Reschedule(delay)) => {
+                self.reschedule(serialized_args, queue, delay).await
+            }
+            result => result.map(|_| ()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::tracing::capture_events;
+    use futures::future::join_all;
+    use itertools::Itertools;
+    use rstest::rstest;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct TestWorker {
+        max_concurrency: Option<usize>,
+        current: Arc<AtomicUsize>,
+        max_observed: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Worker<()> for TestWorker {
+        async fn perform(&self, _args: ()) -> sidekiq::Result<()> {
+            let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_observed.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.current.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    impl AppWorker<AppContext, ()> for TestWorker {
+        fn build(_state: &AppContext) -> Self {
+            unimplemented!()
+        }
+
+        fn max_concurrency(&self, _state: &AppContext) -> Option<usize> {
+            self.max_concurrency
+        }
+    }
+
+    #[rstest]
+    #[case(Some(1), 1)]
+    #[case(Some(2), 2)]
+    #[case(None, 10)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn perform_max_concurrency(
+        #[case] max_concurrency: Option<usize>,
+        #[case] expected_max: usize,
+    ) {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let max_observed = Arc::new(AtomicUsize::new(0));
+        let worker = TestWorker {
+            max_concurrency,
+            current: Default::default(),
+            max_observed: max_observed.clone(),
+        };
+        let worker = RoadsterWorker::new(worker, &context, None);
+
+        // Act
+        let results = join_all((0..10).map(|_| worker.perform(()))).await;
+
+        // Assert
+        assert!(results.iter().all(|result| result.is_ok()));
+        assert_eq!(max_observed.load(Ordering::SeqCst), expected_max);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn perform_max_in_flight() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let max_observed = Arc::new(AtomicUsize::new(0));
+        let current: Arc<AtomicUsize> = Default::default();
+        let in_flight_limit = Arc::new(Semaphore::new(3));
+        let workers = (0..20)
+            .map(|_| {
+                let worker = TestWorker {
+                    max_concurrency: None,
+                    current: current.clone(),
+                    max_observed: max_observed.clone(),
+                };
+                RoadsterWorker::new(worker, &context, Some(in_flight_limit.clone()))
+            })
+            .collect_vec();
+
+        // Act
+        let results = join_all(workers.iter().map(|worker| worker.perform(()))).await;
+
+        // Assert
+        assert!(results.iter().all(|result| result.is_ok()));
+        assert_eq!(max_observed.load(Ordering::SeqCst), 3);
+        assert_eq!(in_flight_limit.available_permits(), 3);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn perform_max_in_flight_cancelled() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let in_flight_limit = Arc::new(Semaphore::new(1));
+        let worker = RoadsterWorker::new(SlowWorker, &context, Some(in_flight_limit.clone()));
+
+        // Act
+        let result = tokio::time::timeout(Duration::from_millis(1), worker.perform(())).await;
+
+        // Assert
+        assert!(result.is_err());
+        assert_eq!(in_flight_limit.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn perform_waiting_for_permit_cancelled() {
+        // Arrange
+        let (events, _guard) = capture_events();
+        let redis = sidekiq::RedisConnectionManager::new("redis://invalid_host:1234").unwrap();
+        let redis = bb8::Pool::builder()
+            .connection_timeout(Duration::from_millis(10))
+            .build_unchecked(redis);
+        let context = AppContext::test(None, None, Some(redis)).unwrap();
+        let max_observed = Arc::new(AtomicUsize::new(0));
+        let worker = TestWorker {
+            max_concurrency: None,
+            current: Default::default(),
+            max_observed: max_observed.clone(),
+        };
+        // No permits are available, so the job waits until the app starts shutting down.
+        let worker = RoadsterWorker::new(worker, &context, Some(Arc::new(Semaphore::new(0))));
+
+        // Act
+        let (result, _) = tokio::join!(worker.perform(()), async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            context.cancellation_token().cancel();
+        });
+
+        // Assert
+        assert_eq!(
+            events
+                .with_message("Enqueuing job again without processing it")
+                .len(),
+            1
+        );
+        // Redis is not available in the test, so the job can't be enqueued again. In that case,
+        // an error is returned so the job is retried as usual instead of being dropped.
+        assert!(result.is_err());
+        assert_eq!(max_observed.load(Ordering::SeqCst), 0);
+    }
+
+    struct FailingWorker {
+        retryable: bool,
+        count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Worker<()> for FailingWorker {
+        async fn perform(&self, _args: ()) -> sidekiq::Result<()> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Err(sidekiq::Error::Message("failed".to_string()))
+        }
+    }
+
+    impl AppWorker<AppContext, ()> for FailingWorker {
+        fn build(_state: &AppContext) -> Self {
+            unimplemented!()
+        }
+
+        fn is_retryable(&self, _err: &sidekiq::Error) -> bool {
+            self.retryable
+        }
+    }
+
+    #[rstest]
+    #[case(true, true)]
+    #[case(false, false)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn perform_is_retryable(#[case] retryable: bool, #[case] expect_err: bool) {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let count = Arc::new(AtomicUsize::new(0));
+        let worker = FailingWorker {
+            retryable,
+            count: count.clone(),
+        };
+        let worker = RoadsterWorker::new(worker, &context, None);
+
+        // Act
+        let result = worker.perform(()).await;
+
+        // Assert
+        // Sidekiq.rs only retries jobs that return an error
+        assert_eq!(result.is_err(), expect_err);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn perform_fetch_paused() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        context.set_sidekiq_fetch_paused(true);
+        let max_observed = Arc::new(AtomicUsize::new(0));
+        let worker = TestWorker {
+            max_concurrency: None,
+            current: Default::default(),
+            max_observed: max_observed.clone(),
+        };
+        let worker = Arc::new(RoadsterWorker::new(worker, &context, None));
+
+        // Act
+        let handle = {
+            let worker = worker.clone();
+            tokio::spawn(async move { worker.perform(()).await })
+        };
+        tokio::time::sleep(FETCH_PAUSED_POLL_INTERVAL * 3).await;
+
+        // Assert
+        assert!(!handle.is_finished());
+        assert_eq!(max_observed.load(Ordering::SeqCst), 0);
+
+        // Act
+        context.set_sidekiq_fetch_paused(false);
+        let result = handle.await.unwrap();
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(max_observed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn perform_fetch_paused_cancelled() {
+        // Arrange
+        let (events, _guard) = capture_events();
+        let redis = sidekiq::RedisConnectionManager::new("redis://invalid_host:1234").unwrap();
+        let redis = bb8::Pool::builder()
+            .connection_timeout(Duration::from_millis(10))
+            .build_unchecked(redis);
+        let context = AppContext::test(None, None, Some(redis)).unwrap();
+        context.set_sidekiq_fetch_paused(true);
+        let max_observed = Arc::new(AtomicUsize::new(0));
+        let worker = TestWorker {
+            max_concurrency: None,
+            current: Default::default(),
+            max_observed: max_observed.clone(),
+        };
+        let worker = RoadsterWorker::new(worker, &context, None);
+
+        // Act
+        let (result, _) = tokio::join!(worker.perform(()), async {
+            tokio::time::sleep(FETCH_PAUSED_POLL_INTERVAL).await;
+            context.cancellation_token().cancel();
+        });
+
+        // Assert
+        assert_eq!(
+            events
+                .with_message("Enqueuing job again without processing it")
+                .len(),
+            1
+        );
+        // Redis is not available in the test, so the job can't be enqueued again.
+        assert!(result.is_err());
+        assert_eq!(max_observed.load(Ordering::SeqCst), 0);
+    }
+
+    struct SlowWorker;
+
+    #[async_trait]
+    impl Worker<()> for SlowWorker {
+        async fn perform(&self, _args: ()) -> sidekiq::Result<()> {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        }
+    }
+
+    impl AppWorker<AppContext, ()> for SlowWorker {
+        fn build(_state: &AppContext) -> Self {
+            unimplemented!()
+        }
+
+        fn timeout(&self, _state: &AppContext) -> bool {
+            true
+        }
+
+        fn max_duration(&self, _state: &AppContext) -> Duration {
+            Duration::from_millis(10)
         }
     }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn perform_timeout() {
+        // Arrange
+        let (events, _guard) = capture_events();
+        let context = AppContext::test(None, None, None).unwrap();
+        let worker = RoadsterWorker::new(SlowWorker, &context, None);
+
+        // Act
+        let result = worker.perform(()).await;
+
+        // Assert
+        assert!(result.is_err());
+        let events = events.with_message("Worker timed out");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].level, tracing::Level::ERROR);
+        assert_eq!(
+            events[0].fields.get("worker").unwrap(),
+            &SlowWorker::class_name()
+        );
+    }
+
+    struct ReschedulingWorker {
+        count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Worker<()> for ReschedulingWorker {
+        async fn perform(&self, _args: ()) -> sidekiq::Result<()> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait]
+    impl AppWorker<AppContext, ()> for ReschedulingWorker {
+        fn build(_state: &AppContext) -> Self {
+            unimplemented!()
+        }
+
+        async fn perform_with_outcome(&self, _args: ()) -> sidekiq::Result<WorkerOutcome> {
+            if self.count.fetch_add(1, Ordering::SeqCst) == 0 {
+                Ok(WorkerOutcome::Reschedule(Duration::from_secs(1)))
+            } else {
+                Ok(WorkerOutcome::Complete)
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn perform_reschedule() {
+        // Arrange
+        let (events, _guard) = capture_events();
+        let redis = sidekiq::RedisConnectionManager::new("redis://invalid_host:1234").unwrap();
+        let redis = bb8::Pool::builder()
+            .connection_timeout(Duration::from_millis(10))
+            .build_unchecked(redis);
+        let context = AppContext::test(None, None, Some(redis)).unwrap();
+        let count = Arc::new(AtomicUsize::new(0));
+        let worker = ReschedulingWorker {
+            count: count.clone(),
+        };
+        let worker = RoadsterWorker::new(worker, &context, None);
+
+        // Act
+        let rescheduled = worker.perform(()).await;
+        let completed = worker.perform(()).await;
+
+        // Assert
+        assert_eq!(events.with_message("Rescheduling job").len(), 1);
+        // Redis is not available in the test, so the job can't be enqueued again. In that case,
+        // an error is returned so the job is retried as usual instead of being dropped.
+        assert!(rescheduled.is_err());
+        assert!(completed.is_ok());
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    struct CancellableWorker {
+        cancelled: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Worker<()> for CancellableWorker {
+        async fn perform(&self, _args: ()) -> sidekiq::Result<()> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait]
+    impl AppWorker<AppContext, ()> for CancellableWorker {
+        fn build(_state: &AppContext) -> Self {
+            unimplemented!()
+        }
+
+        async fn perform_cancellable(
+            &self,
+            _args: (),
+            cancel_token: CancellationToken,
+        ) -> sidekiq::Result<WorkerOutcome> {
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(5)) => {}
+                _ = cancel_token.cancelled() => {
+                    self.cancelled.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+            Ok(WorkerOutcome::Complete)
+        }
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn perform_cancellable() {
+        // Arrange
+        let context = AppContext::test(None, None, None).unwrap();
+        let cancelled = Arc::new(AtomicUsize::new(0));
+        let worker = CancellableWorker {
+            cancelled: cancelled.clone(),
+        };
+        let worker = RoadsterWorker::new(worker, &context, None);
+
+        // Act
+        let (result, _) = tokio::join!(worker.perform(()), async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            context.cancellation_token().cancel();
+        });
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(cancelled.load(Ordering::SeqCst), 1);
+    }
+
+    struct TenantWorker;
+
+    #[async_trait]
+    impl Worker<String> for TenantWorker {
+        async fn perform(&self, _args: String) -> sidekiq::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AppWorker<AppContext, String> for TenantWorker {
+        fn build(_state: &AppContext) -> Self {
+            TenantWorker
+        }
+
+        fn span_fields(&self, args: &String) -> Vec<(&'static str, String)> {
+            vec![("tenant", args.clone())]
+        }
+    }
+
+    /// Captures the fields recorded on spans named `perform`.
+    #[derive(Clone, Default)]
+    struct SpanFields(Arc<std::sync::Mutex<Vec<(String, String)>>>);
+
+    impl tracing::field::Visit for SpanFields {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((field.name().to_string(), value.to_string()));
+        }
+    }
+
+    impl<T> tracing_subscriber::Layer<T> for SpanFields
+    where
+        T: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_record(
+            &self,
+            id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            ctx: tracing_subscriber::layer::Context<'_, T>,
+        ) {
+            if ctx.span(id).map(|span| span.name()) == Some("perform") {
+                values.record(&mut self.clone());
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn perform_span_fields() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        // Arrange
+        let fields = SpanFields::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(fields.clone()));
+        let context = AppContext::test(None, None, None).unwrap();
+        let worker = RoadsterWorker::new(TenantWorker, &context, None);
+
+        // Act
+        let result = worker.perform("foo".to_string()).await;
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(
+            fields.0.lock().unwrap().clone(),
+            vec![("field.0".to_string(), "tenant=foo".to_string())]
+        );
+    }
 }
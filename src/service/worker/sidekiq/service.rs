@@ -62,7 +62,16 @@ where
     async fn before_run(&self, state: &S) -> RoadsterResult<()> {
         let context = AppContext::from_ref(state);
         let mut conn = context.redis_enqueue().get().await?;
-        remove_stale_periodic_jobs(&mut conn, &context, &self.registered_periodic_workers).await
+        let summary =
+            remove_stale_periodic_jobs(&mut conn, &context, &self.registered_periodic_workers)
+                .await?;
+        info!(
+            registered = summary.registered,
+            stale = summary.stale,
+            removed = summary.removed,
+            "Periodic jobs summary"
+        );
+        Ok(())
     }
 
     async fn run(
@@ -108,6 +117,19 @@ impl SidekiqWorkerService {
     }
 }
 
+/// Summary of the outcome of registering the app's periodic jobs during app startup.
+///
+/// Note: Registering the same periodic job twice returns an error, so duplicates are not counted.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub(crate) struct PeriodicJobsSummary {
+    /// The number of periodic jobs that were registered by the app during app startup.
+    pub(crate) registered: usize,
+    /// The number of periodic jobs in Redis that weren't registered during app startup.
+    pub(crate) stale: usize,
+    /// The number of stale periodic jobs that were removed from Redis.
+    pub(crate) removed: usize,
+}
+
 /// Compares the list of periodic jobs that were registered by the app during app startup with
 /// the list of periodic jobs in Redis, and removes any that exist in Redis but weren't
 /// registered during start up.
@@ -120,7 +142,7 @@ async fn remove_stale_periodic_jobs<C: RedisCommands>(
     conn: &mut C,
     context: &AppContext,
     registered_periodic_workers: &HashSet<String>,
-) -> RoadsterResult<()> {
+) -> RoadsterResult<PeriodicJobsSummary> {
     let stale_jobs = conn
         .zrange(PERIODIC_KEY.to_string(), 0, -1)
         .await?
@@ -128,9 +150,15 @@ async fn remove_stale_periodic_jobs<C: RedisCommands>(
         .filter(|job| !registered_periodic_workers.contains(job))
         .collect_vec();
 
+    let mut summary = PeriodicJobsSummary {
+        registered: registered_periodic_workers.len(),
+        stale: stale_jobs.len(),
+        removed: 0,
+    };
+
     if stale_jobs.is_empty() {
         info!("No stale periodic jobs found");
-        return Ok(());
+        return Ok(summary);
     }
 
     if context
@@ -149,6 +177,7 @@ async fn remove_stale_periodic_jobs<C: RedisCommands>(
         );
         conn.zrem(PERIODIC_KEY.to_string(), stale_jobs.clone())
             .await?;
+        summary.removed = stale_jobs.len();
     } else {
         warn!(
             "Found {} stale periodic jobs:\n{}",
@@ -157,7 +186,7 @@ async fn remove_stale_periodic_jobs<C: RedisCommands>(
         );
     }
 
-    Ok(())
+    Ok(summary)
 }
 
 /// Trait to help with mocking responses from Redis.
@@ -289,4 +318,54 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[rstest]
+    #[case(true, 2)]
+    #[case(false, 0)]
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn periodic_jobs_summary(#[case] clean_stale: bool, #[case] expected_removed: usize) {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.service.sidekiq.custom.periodic.stale_cleanup = if clean_stale {
+            StaleCleanUpBehavior::AutoCleanStale
+        } else {
+            StaleCleanUpBehavior::Manual
+        };
+        let context = AppContext::test(Some(config), None, None).unwrap();
+
+        // The previous run registered `foo`, `bar`, and `baz`; this run only registers `foo` and
+        // `qux`, so `bar` and `baz` are stale.
+        let jobs_in_redis = ["foo", "bar", "baz", "qux"]
+            .into_iter()
+            .map(|job| job.to_string())
+            .collect_vec();
+        let registered_jobs: HashSet<String> = ["foo", "qux"]
+            .into_iter()
+            .map(|job| job.to_string())
+            .collect();
+
+        let mut redis = MockRedisCommands::default();
+        redis
+            .expect_zrange()
+            .return_once(move |_, _, _| Ok(jobs_in_redis));
+        redis
+            .expect_zrem()
+            .return_once(|_, _: Vec<String>| Ok(true));
+
+        // Act
+        let summary = super::remove_stale_periodic_jobs(&mut redis, &context, &registered_jobs)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(
+            summary,
+            PeriodicJobsSummary {
+                registered: 2,
+                stale: 2,
+                removed: expected_removed,
+            }
+        );
+    }
 }
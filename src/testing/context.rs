@@ -0,0 +1,107 @@
+//! Helpers for creating an [AppContext] for use in unit tests.
+
+use crate::app::context::AppContext;
+use crate::app::metadata::AppMetadata;
+use crate::config::app_config::AppConfig;
+use crate::error::RoadsterResult;
+use sea_orm::{DatabaseBackend, MockDatabase};
+
+/// Create an [AppContext] whose [db][AppContext::db] (and
+/// [db_read_replica][AppContext::db_read_replica]) is a connection to the provided sea-orm
+/// [MockDatabase]. This allows unit testing code that queries the DB without needing a real DB.
+///
+/// Note: if the `sidekiq` feature is enabled, the [AppContext]'s Redis connection pool will be
+/// created without establishing any connections to Redis.
+///
+/// # Examples
+///
+/// ```rust
+/// # use roadster::config::app_config::AppConfig;
+/// # use roadster::error::RoadsterResult;
+/// use roadster::testing::context::mock_db_context;
+/// use sea_orm::{DatabaseBackend, MockDatabase};
+///
+/// fn context(config: AppConfig) -> RoadsterResult<()> {
+///     let db = MockDatabase::new(DatabaseBackend::Postgres);
+///     let _context = mock_db_context(config, db)?;
+///     Ok(())
+/// }
+/// ```
+pub fn mock_db_context(config: AppConfig, db: MockDatabase) -> RoadsterResult<AppContext> {
+    AppContext::with_db(config, AppMetadata::default(), db.into_connection())
+}
+
+/// Same as [mock_db_context], but creates a Postgres [MockDatabase] that returns the provided
+/// query results, in order, for each query that's executed.
+pub fn mock_db_context_with_query_results<T, I, II>(
+    config: AppConfig,
+    query_results: II,
+) -> RoadsterResult<AppContext>
+where
+    T: sea_orm::IntoMockRow,
+    I: IntoIterator<Item = T>,
+    II: IntoIterator<Item = I>,
+{
+    let db = MockDatabase::new(DatabaseBackend::Postgres).append_query_results(query_results);
+    mock_db_context(config, db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+
+    mod user {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(table_name = "user")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub name: String,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    async fn user_names(context: &AppContext) -> RoadsterResult<Vec<String>> {
+        let names = user::Entity::find()
+            .all(context.db())
+            .await?
+            .into_iter()
+            .map(|user| user.name)
+            .collect();
+        Ok(names)
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    async fn mock_db_context_query_results() {
+        // Arrange
+        let config = AppConfig::test(None).unwrap();
+        let context = mock_db_context_with_query_results(
+            config,
+            [[
+                user::Model {
+                    id: 1,
+                    name: "foo".to_string(),
+                },
+                user::Model {
+                    id: 2,
+                    name: "bar".to_string(),
+                },
+            ]],
+        )
+        .unwrap();
+
+        // Act
+        let names = user_names(&context).await.unwrap();
+
+        // Assert
+        assert_eq!(names, vec!["foo".to_string(), "bar".to_string()]);
+    }
+}
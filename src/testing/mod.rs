@@ -0,0 +1,3 @@
+#[cfg(feature = "testing-mocks")]
+pub mod context;
+pub mod tracing;
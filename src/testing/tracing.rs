@@ -0,0 +1,150 @@
+//! Helpers for asserting on the [tracing] events emitted by the code under test.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::subscriber::DefaultGuard;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::Layer;
+
+/// A [tracing] event that was captured by [capture_events].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct CapturedEvent {
+    pub target: String,
+    pub level: Level,
+    /// The event's message, if it has one.
+    pub message: Option<String>,
+    /// The event's fields (other than the message), formatted as strings. Fields recorded with
+    /// `%` use their [std::fmt::Display] impl, and all other non-string fields use their [Debug]
+    /// impl.
+    pub fields: BTreeMap<String, String>,
+}
+
+/// The [tracing] events captured by [capture_events].
+#[derive(Debug, Clone, Default)]
+pub struct CapturedEvents(Arc<Mutex<Vec<CapturedEvent>>>);
+
+impl CapturedEvents {
+    /// Get all of the events that have been captured so far, in the order they were emitted.
+    pub fn events(&self) -> Vec<CapturedEvent> {
+        self.0
+            .lock()
+            .map(|events| events.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get the captured events that have the given message.
+    pub fn with_message(&self, message: &str) -> Vec<CapturedEvent> {
+        self.events()
+            .into_iter()
+            .filter(|event| event.message.as_deref() == Some(message))
+            .collect()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for CapturedEvents {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let captured = CapturedEvent {
+            target: metadata.target().to_string(),
+            level: *metadata.level(),
+            message: visitor.fields.remove("message"),
+            fields: visitor.fields,
+        };
+        if let Ok(mut events) = self.0.lock() {
+            events.push(captured);
+        }
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    fields: BTreeMap<String, String>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields
+            .insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.fields
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
+
+/// Install a subscriber that captures all [tracing] events as the default subscriber for the
+/// current thread. The subscriber is removed when the returned [DefaultGuard] is dropped.
+///
+/// Note: Because the subscriber is only installed for the current thread, events emitted on
+/// other threads (e.g., by tasks spawned on a multi-threaded Tokio runtime) will not be
+/// captured. `#[tokio::test]` uses a single-threaded runtime by default, so this is usually
+/// not a concern in async tests.
+///
+/// # Examples
+///
+/// ```rust
+/// use roadster::testing::tracing::capture_events;
+/// use tracing::warn;
+///
+/// let (events, _guard) = capture_events();
+///
+/// warn!(worker.name = "Example", "Worker timed out");
+///
+/// let events = events.with_message("Worker timed out");
+/// assert_eq!(events.len(), 1);
+/// assert_eq!(events[0].fields.get("worker.name").unwrap(), "Example");
+/// ```
+pub fn capture_events() -> (CapturedEvents, DefaultGuard) {
+    let events = CapturedEvents::default();
+    let subscriber = tracing_subscriber::registry().with(events.clone());
+    let guard = tracing::subscriber::set_default(subscriber);
+    (events, guard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::{info, warn};
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn capture() {
+        // Arrange
+        let (events, _guard) = capture_events();
+
+        // Act
+        info!(foo = 1, "First");
+        warn!(bar = %"baz", "Second");
+
+        // Assert
+        let events = events.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].level, Level::INFO);
+        assert_eq!(events[0].message.as_deref(), Some("First"));
+        assert_eq!(events[0].fields.get("foo").unwrap(), "1");
+        assert_eq!(events[1].level, Level::WARN);
+        assert_eq!(events[1].message.as_deref(), Some("Second"));
+        assert_eq!(events[1].fields.get("bar").unwrap(), "baz");
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn capture_guard_dropped() {
+        // Arrange
+        let (events, guard) = capture_events();
+
+        // Act
+        drop(guard);
+        info!("Not captured");
+
+        // Assert
+        assert!(events.events().is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use std::str::FromStr;
+use std::sync::OnceLock;
 
 use crate::app::metadata::AppMetadata;
 #[cfg(feature = "otel")]
@@ -12,48 +13,92 @@ use opentelemetry_sdk::propagation::TraceContextPropagator;
 #[cfg(feature = "otel")]
 use opentelemetry_sdk::runtime::Tokio;
 #[cfg(feature = "otel")]
+use opentelemetry_sdk::trace::ShouldSample;
+#[cfg(feature = "otel")]
 use opentelemetry_semantic_conventions::resource::{SERVICE_NAME, SERVICE_VERSION};
-use tracing::Level;
+#[cfg(feature = "otel")]
+use tracing::warn;
+use tracing::{Level, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 #[cfg(feature = "otel")]
 use tracing_opentelemetry::MetricsLayer;
 use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::reload::Handle;
 use tracing_subscriber::util::SubscriberInitExt;
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
 
 use crate::config::app_config::AppConfig;
+use crate::config::tracing::{FileRotation, FileTracing, LogFormat};
 use crate::error::RoadsterResult;
 
+/// Handle used to replace the [EnvFilter] installed by [init_tracing] at runtime. See
+/// [set_trace_filter].
+static TRACE_FILTER_RELOAD_HANDLE: OnceLock<Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Guard for the background thread that writes logs to the file configured in
+/// `tracing.file`. Logs are no longer written once the guard is dropped, so it's kept for the
+/// lifetime of the process.
+static FILE_WRITER_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+
+/// The OpenTelemetry `Resource` attributes that are set by Roadster and can't be overridden via
+/// the `tracing.resource-attributes` config.
+#[cfg(feature = "otel")]
+const RESERVED_RESOURCE_ATTRIBUTES: [&str; 2] = [SERVICE_NAME, SERVICE_VERSION];
+
 // Todo: make this configurable
 pub fn init_tracing(
     config: &AppConfig,
     #[allow(unused_variables)] // This parameter isn't used in some feature combinations
     metadata: &AppMetadata,
+) -> RoadsterResult<()> {
+    init_tracing_inner(
+        config,
+        metadata,
+        #[cfg(feature = "otel")]
+        None,
+    )
+}
+
+/// Same as [init_tracing], but allows providing a custom OpenTelemetry [ShouldSample] to use
+/// when building the tracer provider instead of the SDK's default sampler. This allows, e.g.,
+/// making sampling decisions based on the span's attributes. If `sampler` is `None`, the SDK's
+/// default sampler will be used.
+#[cfg(feature = "otel")]
+pub fn init_tracing_with_sampler(
+    config: &AppConfig,
+    metadata: &AppMetadata,
+    sampler: Option<Box<dyn ShouldSample>>,
+) -> RoadsterResult<()> {
+    init_tracing_inner(config, metadata, sampler)
+}
+
+fn init_tracing_inner(
+    config: &AppConfig,
+    #[allow(unused_variables)] // This parameter isn't used in some feature combinations
+    metadata: &AppMetadata,
+    #[cfg(feature = "otel")] sampler: Option<Box<dyn ShouldSample>>,
 ) -> RoadsterResult<()> {
     // Stdout Layer
     let stdout_layer = tracing_subscriber::fmt::layer();
 
+    // File layer
+    let (file_layer, file_writer_guard) = config
+        .tracing
+        .file
+        .as_ref()
+        .map(file_layer)
+        .transpose()?
+        .unzip();
+
     #[cfg(feature = "otel")]
     if config.tracing.trace_propagation {
         opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
     }
 
     #[cfg(feature = "otel")]
-    let otel_resource = {
-        let service_name = config
-            .tracing
-            .service_name
-            .clone()
-            .or(metadata.name.clone())
-            .unwrap_or(config.app.name.to_case(Case::Snake));
-
-        let mut resource_metadata = vec![opentelemetry::KeyValue::new(SERVICE_NAME, service_name)];
-
-        if let Some(version) = metadata.version.clone() {
-            resource_metadata.push(opentelemetry::KeyValue::new(SERVICE_VERSION, version))
-        }
-
-        opentelemetry_sdk::Resource::new(resource_metadata)
-    };
+    let otel_resource = build_otel_resource(config, metadata);
 
     // Trace layer
     #[cfg(feature = "otel")]
@@ -65,9 +110,7 @@ pub fn init_tracing(
                     .tonic()
                     .with_endpoint(otlp_endpoint.to_string()),
             )
-            .with_trace_config(
-                opentelemetry_sdk::trace::config().with_resource(otel_resource.clone()),
-            )
+            .with_trace_config(build_trace_config(otel_resource.clone(), sampler))
             .install_batch(Tokio)?;
         // Create a tracing layer with the configured tracer
         Some(tracing_opentelemetry::layer().with_tracer(otlp_tracer))
@@ -95,21 +138,354 @@ pub fn init_tracing(
         None
     };
 
-    // Hide some noisy logs from traces
-    let env_filter = EnvFilter::builder()
-        .with_default_directive(Level::from_str(&config.tracing.level)?.into())
-        .from_env()?
-        .add_directive("h2=warn".parse()?)
-        .add_directive("tower::buffer::worker=warn".parse()?);
+    let (env_filter, reload_handle) = reload::Layer::new(env_filter(&config.tracing.level)?);
 
     let registry = tracing_subscriber::Registry::default()
         .with(env_filter)
-        .with(stdout_layer);
+        .with(stdout_layer)
+        .with(file_layer);
 
     #[cfg(feature = "otel")]
     let registry = { registry.with(oltp_traces_layer).with(otlp_metrics_layer) };
 
     registry.try_init()?;
 
+    // `try_init` would have failed above if tracing was already initialized, so the handle
+    // should not have been set yet.
+    let _ = TRACE_FILTER_RELOAD_HANDLE.set(reload_handle);
+    if let Some(guard) = file_writer_guard {
+        let _ = FILE_WRITER_GUARD.set(guard);
+    }
+
+    // This is done after initializing tracing so the warnings are actually emitted.
+    #[cfg(feature = "otel")]
+    warn_reserved_resource_attributes(config);
+
     Ok(())
 }
+
+/// Replace the app's trace filter at runtime, e.g. to temporarily enable `trace` logs for a noisy
+/// module while debugging an issue without needing to restart the app. The `directives` use
+/// the same format as the `RUST_LOG` env var (see [EnvFilter]), e.g. `info,my_app::db=trace`,
+/// and completely replace the filter that was created by [init_tracing].
+///
+/// Returns an error if [init_tracing] was not used to initialize tracing.
+pub fn set_trace_filter(directives: &str) -> RoadsterResult<()> {
+    reload_trace_filter(trace_filter_reload_handle()?, directives)
+}
+
+/// Replace the app's trace filter at runtime with the filter [init_tracing] would create for the
+/// given `level`, e.g. when the `tracing.level` config changes.
+///
+/// Returns an error if [init_tracing] was not used to initialize tracing.
+#[cfg(feature = "config-watch")]
+pub(crate) fn set_trace_level(level: &str) -> RoadsterResult<()> {
+    trace_filter_reload_handle()?.reload(env_filter(level)?)?;
+    Ok(())
+}
+
+fn trace_filter_reload_handle() -> RoadsterResult<&'static Handle<EnvFilter, Registry>> {
+    let handle = TRACE_FILTER_RELOAD_HANDLE.get().ok_or_else(|| {
+        anyhow::anyhow!("Unable to set the trace filter; tracing was not initialized by Roadster")
+    })?;
+    Ok(handle)
+}
+
+fn reload_trace_filter<S>(handle: &Handle<EnvFilter, S>, directives: &str) -> RoadsterResult<()> {
+    let env_filter = EnvFilter::try_new(directives)?;
+    handle.reload(env_filter)?;
+    Ok(())
+}
+
+/// Build the [EnvFilter] for the given default `level`. Directives from the `RUST_LOG` env var
+/// take precedence over the default level.
+fn env_filter(level: &str) -> RoadsterResult<EnvFilter> {
+    let env_filter = EnvFilter::builder()
+        .with_default_directive(Level::from_str(level)?.into())
+        .from_env()?
+        // Hide some noisy logs from traces
+        .add_directive("h2=warn".parse()?)
+        .add_directive("tower::buffer::worker=warn".parse()?);
+    Ok(env_filter)
+}
+
+/// Build a layer that writes logs to a rolling file using the given [FileTracing] config. The
+/// logs are written on a background thread so file I/O doesn't block the threads that emit
+/// them; the returned [WorkerGuard] must be kept alive for as long as logs should be written.
+fn file_layer<S>(
+    config: &FileTracing,
+) -> RoadsterResult<(Box<dyn Layer<S> + Send + Sync>, WorkerGuard)>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let rotation = match config.rotation {
+        FileRotation::Minutely => Rotation::MINUTELY,
+        FileRotation::Hourly => Rotation::HOURLY,
+        FileRotation::Daily => Rotation::DAILY,
+        FileRotation::Never => Rotation::NEVER,
+    };
+    let appender = RollingFileAppender::builder()
+        .rotation(rotation)
+        .filename_prefix(&config.file_name_prefix);
+    let appender = if let Some(max_files) = config.max_files {
+        appender.max_log_files(max_files)
+    } else {
+        appender
+    };
+    let appender = appender.build(&config.dir).map_err(|err| {
+        anyhow::anyhow!(
+            "Unable to create rolling log file in `{}`: {err}",
+            config.dir.display()
+        )
+    })?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_writer(writer);
+    let layer = match config.format {
+        LogFormat::Full => layer.boxed(),
+        LogFormat::Compact => layer.compact().boxed(),
+        LogFormat::Json => layer.json().boxed(),
+    };
+    Ok((layer, guard))
+}
+
+#[cfg(feature = "otel")]
+fn build_otel_resource(config: &AppConfig, metadata: &AppMetadata) -> opentelemetry_sdk::Resource {
+    let service_name = config
+        .tracing
+        .service_name
+        .clone()
+        .or(metadata.name.clone())
+        .unwrap_or(config.app.name.to_case(Case::Snake));
+
+    let mut resource_metadata = vec![opentelemetry::KeyValue::new(SERVICE_NAME, service_name)];
+
+    if let Some(version) = metadata.version.clone() {
+        resource_metadata.push(opentelemetry::KeyValue::new(SERVICE_VERSION, version))
+    }
+
+    // Custom attributes are not allowed to override the reserved attributes set above.
+    resource_metadata.extend(
+        config
+            .tracing
+            .resource_attributes
+            .iter()
+            .filter(|(key, _)| !RESERVED_RESOURCE_ATTRIBUTES.contains(&key.as_str()))
+            .map(|(key, value)| opentelemetry::KeyValue::new(key.clone(), value.clone())),
+    );
+
+    opentelemetry_sdk::Resource::new(resource_metadata)
+}
+
+/// Log a warning for each reserved attribute in the `tracing.resource-attributes` config, since
+/// they are ignored by [build_otel_resource].
+#[cfg(feature = "otel")]
+fn warn_reserved_resource_attributes(config: &AppConfig) {
+    config
+        .tracing
+        .resource_attributes
+        .keys()
+        .filter(|key| RESERVED_RESOURCE_ATTRIBUTES.contains(&key.as_str()))
+        .for_each(|key| {
+            warn!(
+                attribute = %key,
+                "Ignoring reserved OpenTelemetry resource attribute from the `tracing.resource-attributes` config"
+            );
+        });
+}
+
+#[cfg(feature = "otel")]
+fn build_trace_config(
+    resource: opentelemetry_sdk::Resource,
+    sampler: Option<Box<dyn ShouldSample>>,
+) -> opentelemetry_sdk::trace::Config {
+    let mut trace_config = opentelemetry_sdk::trace::config().with_resource(resource);
+    if let Some(sampler) = sampler {
+        trace_config.sampler = sampler;
+    }
+    trace_config
+}
+
+#[cfg(test)]
+mod reload_tests {
+    use super::*;
+    use crate::testing::tracing::CapturedEvents;
+    use tracing::debug;
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn reload_trace_filter() {
+        // Arrange
+        let events = CapturedEvents::default();
+        let (env_filter, handle) = reload::Layer::new(EnvFilter::try_new("info").unwrap());
+        let subscriber = Registry::default().with(env_filter).with(events.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        // Act
+        debug!("Before reload");
+        super::reload_trace_filter(&handle, "debug").unwrap();
+        debug!("After reload");
+
+        // Assert
+        assert!(events.with_message("Before reload").is_empty());
+        assert_eq!(events.with_message("After reload").len(), 1);
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn reload_trace_filter_invalid_directives() {
+        // Arrange
+        let (_env_filter, handle) =
+            reload::Layer::<_, Registry>::new(EnvFilter::try_new("info").unwrap());
+
+        // Act
+        let result = super::reload_trace_filter(&handle, "foo=bar=baz");
+
+        // Assert
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod file_tests {
+    use super::*;
+    use crate::util::test_util::TempDir;
+    use rstest::rstest;
+    use tracing::info;
+
+    #[rstest]
+    #[case(LogFormat::Full)]
+    #[case(LogFormat::Compact)]
+    #[case(LogFormat::Json)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn file_layer(#[case] format: LogFormat) {
+        // Arrange
+        let dir = TempDir::new();
+        let config: FileTracing = toml::from_str(&format!(
+            r#"
+            dir = "{}"
+            rotation = "daily"
+            max-files = 2
+            "#,
+            dir.path().display()
+        ))
+        .unwrap();
+        let config = FileTracing { format, ..config };
+        let (layer, guard) = super::file_layer(&config).unwrap();
+        let subscriber = Registry::default().with(layer);
+
+        // Act
+        tracing::subscriber::with_default(subscriber, || info!("Written to file"));
+        // Dropping the guard flushes the logs to the file
+        drop(guard);
+
+        // Assert
+        let files = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        assert_eq!(files.len(), 1);
+        let file_name = files[0].file_name().unwrap().to_str().unwrap();
+        assert!(file_name.starts_with("app.log."));
+        let contents = std::fs::read_to_string(&files[0]).unwrap();
+        assert!(contents.contains("Written to file"));
+    }
+}
+
+#[cfg(all(test, feature = "otel"))]
+mod tests {
+    use super::*;
+    use crate::testing::tracing::capture_events;
+    use opentelemetry::trace::{SamplingDecision, SpanKind, TraceId};
+    use opentelemetry::{Key, Value};
+    use opentelemetry_sdk::trace::Sampler;
+    use rstest::rstest;
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn build_otel_resource_custom_attributes() {
+        // Arrange
+        let mut config = AppConfig::test(None).unwrap();
+        config.tracing.service_name = Some("foo".to_string());
+        config.tracing.resource_attributes = [
+            ("deployment.environment", "prod"),
+            ("service.namespace", "bar"),
+            (SERVICE_NAME, "baz"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+        // Act
+        let resource = build_otel_resource(&config, &AppMetadata::default());
+
+        // Assert
+        assert_eq!(
+            resource.get(Key::new("deployment.environment")),
+            Some(Value::from("prod"))
+        );
+        assert_eq!(
+            resource.get(Key::new("service.namespace")),
+            Some(Value::from("bar"))
+        );
+        assert_eq!(
+            resource.get(Key::new(SERVICE_NAME)),
+            Some(Value::from("foo"))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn warn_reserved_resource_attributes() {
+        // Arrange
+        let (events, _guard) = capture_events();
+        let mut config = AppConfig::test(None).unwrap();
+        config.tracing.resource_attributes = [
+            ("service.namespace", "bar"),
+            (SERVICE_NAME, "baz"),
+            (SERVICE_VERSION, "1.0.0"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+        // Act
+        super::warn_reserved_resource_attributes(&config);
+
+        // Assert
+        assert_eq!(
+            events
+                .with_message("Ignoring reserved OpenTelemetry resource attribute from the `tracing.resource-attributes` config")
+                .len(),
+            2
+        );
+    }
+
+    #[rstest]
+    #[case(None, SamplingDecision::RecordAndSample)]
+    #[case(Some(Box::new(Sampler::AlwaysOff) as Box<dyn ShouldSample>), SamplingDecision::Drop)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn build_trace_config_sampler(
+        #[case] sampler: Option<Box<dyn ShouldSample>>,
+        #[case] expected: SamplingDecision,
+    ) {
+        // Arrange
+        let resource = build_otel_resource(&AppConfig::test(None).unwrap(), &Default::default());
+
+        // Act
+        let trace_config = build_trace_config(resource, sampler);
+
+        // Assert
+        let result = trace_config.sampler.should_sample(
+            None,
+            TraceId::from_u128(1),
+            "test",
+            &SpanKind::Internal,
+            &[],
+            &[],
+        );
+        assert_eq!(result.decision, expected);
+    }
+}
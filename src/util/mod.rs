@@ -1,3 +1,5 @@
+#[cfg(any(feature = "cli", feature = "db-sql"))]
+pub(crate) mod redact;
 pub mod serde_util;
 #[cfg(test)]
 pub mod test_util;
@@ -37,6 +37,7 @@ where
 /// back to deserializing as a string.
 // Intentionally not annotated with `#[non_exhaustive]`
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum UriOrString {
     Uri(Url),
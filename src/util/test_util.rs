@@ -1,5 +1,7 @@
 use insta::internals::SettingsBindDropGuard;
+use std::path::{Path, PathBuf};
 use std::thread::current;
+use uuid::Uuid;
 
 /// Set the `insta` snapshot suffix. Useful for using `insta` together with `rstest`.
 ///
@@ -70,3 +72,41 @@ fn test_case() -> TestCase {
         description,
     }
 }
+
+/// A uniquely named directory in the system's temp dir that's removed (along with its contents)
+/// when dropped, even if the test panics.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    pub fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("roadster-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Write the `contents` to a file with the given `name` in the directory, and return the
+    /// file's path.
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    pub fn write(&self, name: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+        let path = self.0.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+}
+
+impl Default for TempDir {
+    fn default() -> Self {
+        TempDir::new()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}